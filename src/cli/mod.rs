@@ -1,3 +1,4 @@
+use crate::g::{Field, PrimaryKey};
 use clap::{Args, Parser, Subcommand};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -19,13 +20,13 @@ impl GcpConfig {
     pub fn gcr_region(&self) -> &str {
         let asia = regex("asia");
         let eu = regex("europe");
-        return if asia.is_match(&self.region) {
+        if asia.is_match(&self.region) {
             "asia.gcr.io"
         } else if eu.is_match(&self.region) {
             "eu.gcr.io"
         } else {
             "gcr.io"
-        };
+        }
     }
 }
 
@@ -48,6 +49,7 @@ pub enum Commands {
     Docker(Docker),
     Sql(Sql),
     G(G),
+    Destroy(Destroy),
     Db(Db),
     New { app_name: String },
     Gcloud(Gcloud),
@@ -109,6 +111,13 @@ pub struct G {
     pub command: Option<GCommands>,
 }
 
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+pub struct Destroy {
+    #[clap(subcommand)]
+    pub command: Option<DestroyCommands>,
+}
+
 #[derive(Debug, Args)]
 #[clap(args_conflicts_with_subcommands = true)]
 pub struct Db {
@@ -177,6 +186,125 @@ pub enum SqlCommands {
 pub enum GCommands {
     Model {
         model: String,
+        /// Columns as `name:type[:rule...]`, e.g. `name:string:len(1,100) age:i32`
+        fields: Vec<Field>,
+        /// Delete by setting a `deleted_at` column, with restore and hard delete mutations
+        #[clap(long)]
+        soft_delete: bool,
+        /// Type of the `id` primary key: i32, i64 or uuid
+        #[clap(long, default_value = "i32")]
+        pk: PrimaryKey,
+        /// Leave out the `created_at` and `updated_at` columns
+        #[clap(long, conflicts_with = "timestamptz")]
+        no_timestamps: bool,
+        /// Make `created_at` and `updated_at` `timestamp with time zone` columns
+        #[clap(long)]
+        timestamptz: bool,
+        /// Add created, updated and deleted subscriptions published by the mutations
+        #[clap(long)]
+        subscriptions: bool,
+        #[clap(parse(from_os_str), short, long)]
+        path: Option<PathBuf>,
+        /// Print a diff of the files that would change without writing them
+        #[clap(long)]
+        dry_run: bool,
+        /// Overwrite files that already exist
+        #[clap(long, conflicts_with = "skip")]
+        force: bool,
+        /// Keep files that already exist
+        #[clap(long)]
+        skip: bool,
+    },
+    Join {
+        left: String,
+        right: String,
+        #[clap(parse(from_os_str), short, long)]
+        path: Option<PathBuf>,
+        /// Print a diff of the files that would change without writing them
+        #[clap(long)]
+        dry_run: bool,
+        /// Overwrite files that already exist
+        #[clap(long, conflicts_with = "skip")]
+        force: bool,
+        /// Keep files that already exist
+        #[clap(long)]
+        skip: bool,
+    },
+    Migration {
+        /// What to change, e.g. `add_age_to_user`, `remove_age_from_user`,
+        /// `rename_name_to_nickname_in_user` or `add_index_to_user`
+        #[clap(required_unless_present = "auto")]
+        name: Option<String>,
+        /// Columns as `name:type` or, for an index, column names
+        args: Vec<String>,
+        /// Make the added index unique
+        #[clap(long)]
+        unique: bool,
+        /// Diff the entity files against the last snapshot instead
+        #[clap(long, conflicts_with_all = &["args", "unique"])]
+        auto: bool,
+        #[clap(parse(from_os_str), short, long)]
+        path: Option<PathBuf>,
+        /// Print a diff of the files that would change without writing them
+        #[clap(long)]
+        dry_run: bool,
+    },
+    Import {
+        /// Postgres DDL dump, e.g. from `pg_dump --schema-only`
+        #[clap(parse(from_os_str))]
+        file: PathBuf,
+        #[clap(parse(from_os_str), short, long)]
+        path: Option<PathBuf>,
+        /// Print a diff of the files that would change without writing them
+        #[clap(long)]
+        dry_run: bool,
+        /// Overwrite files that already exist
+        #[clap(long, conflicts_with = "skip")]
+        force: bool,
+        /// Keep files that already exist
+        #[clap(long)]
+        skip: bool,
+    },
+    Seed {
+        model: String,
+        #[clap(parse(from_os_str), short, long)]
+        path: Option<PathBuf>,
+        /// Print a diff of the files that would change without writing them
+        #[clap(long)]
+        dry_run: bool,
+        /// Overwrite files that already exist
+        #[clap(long, conflicts_with = "skip")]
+        force: bool,
+        /// Keep files that already exist
+        #[clap(long)]
+        skip: bool,
+    },
+    Schema {
+        /// Schema file, `zapp.schema.yaml` in the project by default
+        #[clap(parse(from_os_str), short, long)]
+        file: Option<PathBuf>,
+        #[clap(parse(from_os_str), short, long)]
+        path: Option<PathBuf>,
+        /// Print a diff of the files that would change without writing them
+        #[clap(long)]
+        dry_run: bool,
+        /// Overwrite files that already exist
+        #[clap(long, conflicts_with = "skip")]
+        force: bool,
+        /// Keep files that already exist
+        #[clap(long)]
+        skip: bool,
+    },
+    Help,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DestroyCommands {
+    Model {
+        model: String,
+        /// Keep the create migration even if it has not been applied yet
+        #[clap(long)]
+        keep_migration: bool,
         #[clap(parse(from_os_str), short, long)]
         path: Option<PathBuf>,
     },
@@ -189,6 +317,8 @@ pub enum DbCommands {
     Reset,
     Refresh,
     Rollback,
+    /// Run the seeders of `src/bin/seed`
+    Seed,
     Help,
 }
 
@@ -9,7 +9,7 @@ fn regex(re_str: &str) -> Regex {
 
 pub fn process_create_network(project_id: &str, service_name: &str) {
     let output = Command::new("gcloud")
-        .args(&[
+        .args([
             "compute",
             "networks",
             "create",
@@ -39,7 +39,7 @@ pub fn process_create_network(project_id: &str, service_name: &str) {
 pub fn process_create_firewall_tcp(project_id: &str, service_name: &str) {
     let firewall = String::from(service_name) + "-tcp";
     let output = Command::new("gcloud")
-        .args(&[
+        .args([
             "compute",
             "firewall-rules",
             "create",
@@ -75,7 +75,7 @@ pub fn process_create_firewall_tcp(project_id: &str, service_name: &str) {
 pub fn process_create_firewall_ssh(project_id: &str, service_name: &str) {
     let firewall = String::from(service_name) + "-ssh";
     let output = Command::new("gcloud")
-        .args(&[
+        .args([
             "compute",
             "firewall-rules",
             "create",
@@ -109,7 +109,7 @@ pub fn process_create_firewall_ssh(project_id: &str, service_name: &str) {
 pub fn process_create_subnet(project_id: &str, service_name: &str, region: &str) {
     let subnet = String::from(service_name) + "-subnet";
     let output = Command::new("gcloud")
-        .args(&[
+        .args([
             "compute",
             "networks",
             "subnets",
@@ -146,7 +146,7 @@ pub fn process_create_subnet(project_id: &str, service_name: &str, region: &str)
 pub fn process_create_connector(project_id: &str, service_name: &str, region: &str) {
     let subnet = String::from(service_name) + "-subnet";
     let output = Command::new("gcloud")
-        .args(&[
+        .args([
             "compute",
             "networks",
             "vpc-access",
@@ -184,7 +184,7 @@ pub fn process_create_connector(project_id: &str, service_name: &str, region: &s
 pub fn process_create_router(project_id: &str, service_name: &str, region: &str) {
     let router = String::from(service_name) + "-router";
     let output = Command::new("gcloud")
-        .args(&[
+        .args([
             "compute",
             "routers",
             "create",
@@ -218,7 +218,7 @@ pub fn process_create_router(project_id: &str, service_name: &str, region: &str)
 pub fn process_create_external_ip(project_id: &str, service_name: &str, region: &str) {
     let external_ip = String::from(service_name) + "-ip";
     let output = Command::new("gcloud")
-        .args(&[
+        .args([
             "compute",
             "addresses",
             "create",
@@ -253,7 +253,7 @@ pub fn process_create_nat(project_id: &str, service_name: &str, region: &str) {
     let nat_custom_subnet_ip_ranges = String::from(service_name) + "-subnet";
     let nat_external_ip_pool = String::from(service_name) + "-ip";
     let output = Command::new("gcloud")
-        .args(&[
+        .args([
             "compute",
             "routers",
             "nats",
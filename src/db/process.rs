@@ -8,9 +8,7 @@ fn regex(re_str: &str) -> Regex {
 }
 
 pub fn process_db_migrate() {
-    let output = Command::new("sea-orm-cli")
-        .args(&["migrate", "up"])
-        .output();
+    let output = Command::new("sea-orm-cli").args(["migrate", "up"]).output();
     match &output {
         Ok(val) => {
             let err2 = str::from_utf8(&val.stderr).unwrap();
@@ -30,7 +28,7 @@ pub fn process_db_migrate() {
 
 pub fn process_db_reset() {
     let output = Command::new("sea-orm-cli")
-        .args(&["migrate", "reset"])
+        .args(["migrate", "reset"])
         .output();
     match &output {
         Ok(val) => {
@@ -53,7 +51,7 @@ pub fn process_db_reset() {
 
 pub fn process_db_refresh() {
     let output = Command::new("sea-orm-cli")
-        .args(&["migrate", "refresh"])
+        .args(["migrate", "refresh"])
         .output();
     match &output {
         Ok(val) => {
@@ -76,7 +74,7 @@ pub fn process_db_refresh() {
 
 pub fn process_db_rollback() {
     let output = Command::new("sea-orm-cli")
-        .args(&["migrate", "down"])
+        .args(["migrate", "down"])
         .output();
 
     match &output {
@@ -97,3 +95,57 @@ pub fn process_db_rollback() {
         }
     }
 }
+
+/// Runs the seeders of `src/bin/seed` against `DATABASE_URL`.
+pub fn process_db_seed() {
+    let output = Command::new("cargo")
+        .args(["run", "--bin", "seed"])
+        .output();
+    match &output {
+        Ok(val) => {
+            let err2 = str::from_utf8(&val.stderr).unwrap();
+            match val.status.success() {
+                true => {
+                    log_success("Successfully DB seeded!");
+                }
+                false => {
+                    log_error(err2);
+                }
+            }
+        }
+        Err(err) => {
+            panic!("error = {:?}", err)
+        }
+    }
+}
+
+/// Whether the `create_<model>_table` migration is listed as applied by
+/// `sea-orm-cli migrate status`. Assumes it is when the status can't be read.
+pub fn process_db_migration_applied(model: &str) -> bool {
+    let output = Command::new("sea-orm-cli")
+        .args(["migrate", "status"])
+        .output();
+    match &output {
+        Ok(val) => {
+            let status = format!(
+                "{}{}",
+                String::from_utf8_lossy(&val.stdout),
+                String::from_utf8_lossy(&val.stderr)
+            );
+            let rt = regex("error:");
+            if rt.is_match(&status) {
+                log_error(&status);
+                return true;
+            }
+            let applied = regex(&format!(
+                r"m\d{{8}}_\d{{6}}_create_{}_table'?\.\.\.\s*Applied",
+                model
+            ));
+            applied.is_match(&status)
+        }
+        Err(err) => {
+            log_error(&format!("Could not read migration status: {}", err));
+            true
+        }
+    }
+}
@@ -2,7 +2,6 @@ use crate::style_print::*;
 use std::process::Command;
 use std::str;
 
-
 pub fn process_psql_docker() {
     create_docker_network();
     process_docker_psql("zapp-psql");
@@ -11,7 +10,7 @@ pub fn process_psql_docker() {
 pub fn process_docker_build(project_id: &str, service_name: &str, gcr_region: &str) {
     let gcr_url = String::from(gcr_region) + "/" + project_id + "/" + service_name;
     let output = Command::new("docker")
-        .args(&["build", ".", "-t", &gcr_url])
+        .args(["build", ".", "-t", &gcr_url])
         .output();
 
     println!("output = {:?}", output);
@@ -19,23 +18,23 @@ pub fn process_docker_build(project_id: &str, service_name: &str, gcr_region: &s
 
 pub fn process_docker_push(project_id: &str, service_name: &str, gcr_region: &str) {
     let gcr_url = String::from(gcr_region) + "/" + project_id + "/" + service_name;
-    let output = Command::new("docker").args(&["push", &gcr_url]).output();
+    let output = Command::new("docker").args(["push", &gcr_url]).output();
 
     println!("output = {:?}", output);
 }
 
 fn create_docker_network() {
     let _output = Command::new("docker")
-        .args(&["network", "create", "zapp"])
+        .args(["network", "create", "zapp"])
         .output();
 }
 
 pub fn process_docker_restart() {
     let _output = Command::new("docker")
-        .args(&["rm", "-f", "zapp-psql"])
+        .args(["rm", "-f", "zapp-psql"])
         .output();
 
-    let _output2 = Command::new("zapp").args(&["docker", "psql"]).output();
+    let _output2 = Command::new("zapp").args(["docker", "psql"]).output();
 }
 
 fn process_docker_psql(service_name: &str) {
@@ -43,7 +42,7 @@ fn process_docker_psql(service_name: &str) {
     let container_name = String::from(service_name) + "-psql";
     let db_name = String::from("POSTGRES_DB=") + &underscored_name + "_db";
     let output = Command::new("docker")
-        .args(&[
+        .args([
             "run",
             "--rm",
             "-d",
@@ -0,0 +1,109 @@
+use crate::style_print::{log_error, log_input, log_success, log_white};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// What to do when a file the generator creates already exists.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Ask per file, refuse to overwrite anything when nobody can answer.
+    Prompt,
+    /// Keep the existing files.
+    Skip,
+    /// Overwrite the existing files.
+    Force,
+}
+
+/// Resolves the files of one generator run against what is already on disk
+/// before anything is written, and reports the outcome afterwards.
+pub(in crate::g) struct Conflicts<'a> {
+    policy: ConflictPolicy,
+    gen_path: &'a Path,
+    created: Vec<PathBuf>,
+    overwritten: Vec<PathBuf>,
+    skipped: Vec<PathBuf>,
+    refused: Vec<PathBuf>,
+}
+
+impl<'a> Conflicts<'a> {
+    pub(in crate::g) fn new(policy: ConflictPolicy, gen_path: &'a Path) -> Self {
+        Conflicts {
+            policy,
+            gen_path,
+            created: Vec::new(),
+            overwritten: Vec::new(),
+            skipped: Vec::new(),
+            refused: Vec::new(),
+        }
+    }
+
+    /// Whether `file_path` may be written.
+    pub(in crate::g) fn resolve(&mut self, file_path: PathBuf) -> bool {
+        if !file_path.exists() {
+            self.created.push(file_path);
+            return true;
+        }
+
+        match self.policy {
+            ConflictPolicy::Force => {
+                self.overwritten.push(file_path);
+                true
+            }
+            ConflictPolicy::Skip => {
+                self.skipped.push(file_path);
+                false
+            }
+            ConflictPolicy::Prompt if !console::user_attended() => {
+                self.refused.push(file_path);
+                false
+            }
+            ConflictPolicy::Prompt => {
+                log_input(&format!(
+                    "{} already exists, overwrite it? [y/N]",
+                    self.relative(&file_path).display()
+                ));
+                let mut answer = String::new();
+                io::stdin()
+                    .read_line(&mut answer)
+                    .expect("Failed to read line");
+                if matches!(answer.trim(), "y" | "Y" | "yes") {
+                    self.overwritten.push(file_path);
+                    true
+                } else {
+                    self.skipped.push(file_path);
+                    false
+                }
+            }
+        }
+    }
+
+    /// Whether the run has to be aborted because an existing file could not
+    /// be asked about.
+    pub(in crate::g) fn refused(&self) -> bool {
+        !self.refused.is_empty()
+    }
+
+    pub(in crate::g) fn log_summary(&self) {
+        for file_path in &self.created {
+            log_success(&format!("Created {}", self.relative(file_path).display()));
+        }
+        for file_path in &self.overwritten {
+            log_success(&format!("Overwrote {}", self.relative(file_path).display()));
+        }
+        for file_path in &self.skipped {
+            log_white(&format!("Skipped {}", self.relative(file_path).display()));
+        }
+        for file_path in &self.refused {
+            log_error(&format!(
+                "Refused to overwrite {}",
+                self.relative(file_path).display()
+            ));
+        }
+        if self.refused() {
+            log_error("Nothing was generated, rerun with --force to overwrite or --skip to keep the existing files");
+        }
+    }
+
+    fn relative<'p>(&self, file_path: &'p Path) -> &'p Path {
+        file_path.strip_prefix(self.gen_path).unwrap_or(file_path)
+    }
+}
@@ -0,0 +1,256 @@
+use crate::style_print::{log_success, log_white};
+use console::style;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Directories and files of a zapp project the generators read or write,
+/// build output in them is left out.
+const GENERATED_PATHS: [&str; 9] = [
+    "Cargo.toml",
+    "entity/Cargo.toml",
+    "entity/src",
+    "migration",
+    "src/db.rs",
+    "src/lib.rs",
+    "src/graphql",
+    "src/bin/seed",
+    "tests",
+];
+
+const CONTEXT_LINES: usize = 3;
+
+/// Runs `generate` against a scratch copy of the generated directories of
+/// `gen_path` and prints a unified diff of what it would change, leaving
+/// `gen_path` untouched.
+pub fn dry_run<F: FnOnce(&Path)>(gen_path: &Path, generate: F) {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .subsec_nanos();
+    let scratch_path = env::temp_dir().join(format!("zapp-dry-run-{}-{}", process::id(), nanos));
+    if scratch_path.exists() {
+        fs::remove_dir_all(&scratch_path).unwrap();
+    }
+    for path in GENERATED_PATHS {
+        let from = gen_path.join(path);
+        let to = scratch_path.join(path);
+        if from.is_dir() {
+            copy_dir(&from, &to).unwrap();
+        } else if from.is_file() {
+            fs::create_dir_all(to.parent().unwrap()).unwrap();
+            fs::copy(&from, &to).unwrap();
+        }
+    }
+
+    log_white(&format!(
+        "Dry run: generating into {} instead",
+        scratch_path.display()
+    ));
+    generate(&scratch_path);
+
+    let mut files = Vec::new();
+    for path in GENERATED_PATHS {
+        files.extend(list_files(gen_path, Path::new(path)));
+        files.extend(list_files(&scratch_path, Path::new(path)));
+    }
+    files.sort();
+    files.dedup();
+
+    let mut changed = 0;
+    for file in files {
+        let old = fs::read_to_string(gen_path.join(&file)).unwrap_or_default();
+        let new = fs::read_to_string(scratch_path.join(&file)).unwrap_or_default();
+        if old != new {
+            print!("{}", unified_diff(&old, &new, &file.display().to_string()));
+            changed += 1;
+        }
+    }
+    fs::remove_dir_all(&scratch_path).unwrap();
+
+    if changed == 0 {
+        log_white("Dry run: no files would change");
+    } else {
+        log_success(&format!(
+            "Dry run: {} file(s) would change, nothing was written",
+            changed
+        ));
+    }
+}
+
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        if entry.file_name() == "target" {
+            continue;
+        }
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Files below `root/dir`, or `dir` itself if it is a file, relative to
+/// `root`.
+fn list_files(root: &Path, dir: &Path) -> Vec<PathBuf> {
+    if root.join(dir).is_file() {
+        return vec![dir.to_path_buf()];
+    }
+    let entries = match fs::read_dir(root.join(dir)) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut files = Vec::new();
+    for entry in entries.filter_map(Result::ok) {
+        if entry.file_name() == "target" {
+            continue;
+        }
+        let path = dir.join(entry.file_name());
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => files.extend(list_files(root, &path)),
+            Ok(_) => files.push(path),
+            Err(_) => {}
+        }
+    }
+    files
+}
+
+enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Line based diff in unified format, an empty `old` or `new` stands for a
+/// file that does not exist.
+fn unified_diff(old: &str, new: &str, path: &str) -> String {
+    let old_lines = old.lines().collect::<Vec<_>>();
+    let new_lines = new.lines().collect::<Vec<_>>();
+    let lines = diff_lines(&old_lines, &new_lines);
+
+    let mut out = String::new();
+    let from = if old.is_empty() {
+        "/dev/null".to_string()
+    } else {
+        format!("a/{}", path)
+    };
+    let to = if new.is_empty() {
+        "/dev/null".to_string()
+    } else {
+        format!("b/{}", path)
+    };
+    out.push_str(&format!("{}\n", style(format!("--- {}", from)).bold()));
+    out.push_str(&format!("{}\n", style(format!("+++ {}", to)).bold()));
+
+    let changes = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Line::Same(_)))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+
+    let mut i = 0;
+    while i < changes.len() {
+        // Group changes closer than twice the context into one hunk.
+        let start = changes[i].saturating_sub(CONTEXT_LINES);
+        let mut end = changes[i];
+        while i + 1 < changes.len() && changes[i + 1] <= end + 2 * CONTEXT_LINES {
+            i += 1;
+            end = changes[i];
+        }
+        let end = (end + CONTEXT_LINES + 1).min(lines.len());
+        i += 1;
+
+        let (old_start, new_start) =
+            lines[..start]
+                .iter()
+                .fold((1, 1), |(o, n), line| match line {
+                    Line::Same(_) => (o + 1, n + 1),
+                    Line::Removed(_) => (o + 1, n),
+                    Line::Added(_) => (o, n + 1),
+                });
+        let old_len = lines[start..end]
+            .iter()
+            .filter(|l| !matches!(l, Line::Added(_)))
+            .count();
+        let new_len = lines[start..end]
+            .iter()
+            .filter(|l| !matches!(l, Line::Removed(_)))
+            .count();
+        // An empty range points at the line before it.
+        let old_start = if old_len == 0 {
+            old_start - 1
+        } else {
+            old_start
+        };
+        let new_start = if new_len == 0 {
+            new_start - 1
+        } else {
+            new_start
+        };
+        out.push_str(&format!(
+            "{}\n",
+            style(format!(
+                "@@ -{},{} +{},{} @@",
+                old_start, old_len, new_start, new_len
+            ))
+            .cyan()
+        ));
+
+        for line in &lines[start..end] {
+            match line {
+                Line::Same(text) => out.push_str(&format!(" {}\n", text)),
+                Line::Removed(text) => {
+                    out.push_str(&format!("{}\n", style(format!("-{}", text)).red()))
+                }
+                Line::Added(text) => {
+                    out.push_str(&format!("{}\n", style(format!("+{}", text)).green()))
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Longest common subsequence diff, generated files are small enough for the
+/// quadratic table.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Line<'a>> {
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::new();
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(Line::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(Line::Removed(old[i]));
+            i += 1;
+        } else {
+            lines.push(Line::Added(new[j]));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().map(|line| Line::Removed(line)));
+    lines.extend(new[j..].iter().map(|line| Line::Added(line)));
+    lines
+}
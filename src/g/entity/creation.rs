@@ -1,11 +1,22 @@
-use crate::g::{emit_generated_code, to_upper_camel};
+use crate::g::entity::relation::{
+    belongs_to_resolver_tokens, belongs_to_variant_tokens, id_loader_tokens, related_impl_tokens,
+};
+use crate::g::{emit_generated_code, to_upper_camel, Field, FieldType, ModelOptions, PrimaryKey};
 use crate::style_print::log_success;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use std::path::Path;
 
-pub(super) fn create_entity(model: &str, entity_src_dir: &Path) {
-    let file_content_tokens = create_model_tokens(model);
+/// `referenced` tells whether other entities belong to `model`, which then
+/// needs an `IdLoader`.
+pub(super) fn create_entity(
+    model: &str,
+    fields: &[Field],
+    options: ModelOptions,
+    referenced: bool,
+    entity_src_dir: &Path,
+) {
+    let file_content_tokens = create_model_tokens(model, fields, options, referenced);
 
     let file_path = emit_generated_code(
         entity_src_dir,
@@ -20,47 +31,355 @@ pub(super) fn create_entity(model: &str, entity_src_dir: &Path) {
     ));
 }
 
-fn create_model_tokens(model_str: &str) -> TokenStream {
-    let model = format_ident!("{}", model_str);
-    let cap_model = format_ident!("{}", to_upper_camel(model_str));
+/// `left_pk` and `right_pk` are the primary keys of the joined models.
+pub(super) fn create_join_entity(
+    left: &str,
+    right: &str,
+    left_pk: PrimaryKey,
+    right_pk: PrimaryKey,
+    entity_src_dir: &Path,
+) {
+    let join = format!("{}_{}", left, right);
+    let file_content_tokens = create_join_model_tokens(&join, left, right, [left_pk, right_pk]);
+
+    let file_path = emit_generated_code(
+        entity_src_dir,
+        &format!("{}.rs", join),
+        &file_content_tokens,
+    );
+
+    log_success(&format!(
+        "Successfully created `{}` join entity file: {}",
+        join,
+        file_path.display()
+    ));
+}
+
+fn create_model_tokens(
+    model_str: &str,
+    fields: &[Field],
+    options: ModelOptions,
+    referenced: bool,
+) -> TokenStream {
+    let table_name = syn::LitStr::new(model_str, proc_macro2::Span::call_site());
+    let columns = fields.iter().map(create_column_tokens);
+    let enums = fields
+        .iter()
+        .filter(|f| f.enum_ident().is_some())
+        .map(|f| create_enum_tokens(model_str, f));
+    let pk = options.pk.rust_type();
+    let (pk_attr, set_id) = match options.pk {
+        PrimaryKey::Uuid => (
+            quote!(#[sea_orm(primary_key, auto_increment = false)]),
+            Some(quote! {
+                if insert && self.id.is_not_set() {
+                    self.id = Set(Uuid::new_v4());
+                }
+            }),
+        ),
+        PrimaryKey::I32 | PrimaryKey::I64 => (quote!(#[sea_orm(primary_key)]), None),
+    };
+    let (timestamps, set_timestamps) = match options.timestamps.field_type() {
+        Some(ty) => {
+            let ty = ty.rust_type();
+            let now = options.timestamps.now();
+            (
+                quote! {
+                    #[sea_orm(indexed)]
+                    pub created_at: #ty,
+                    #[sea_orm(indexed)]
+                    pub updated_at: #ty,
+                },
+                Some(quote! {
+                    let now = #now;
+                    if insert && self.created_at.is_not_set() {
+                        self.created_at = Set(now);
+                    }
+                    self.updated_at = Set(now);
+                }),
+            )
+        }
+        None => (quote!(), None),
+    };
+    let (chrono_import, set_import, active_model_behavior) =
+        if set_id.is_none() && set_timestamps.is_none() {
+            (
+                None,
+                quote!(),
+                quote!(impl ActiveModelBehavior for ActiveModel {}),
+            )
+        } else {
+            (
+                set_timestamps.as_ref().map(|_| {
+                    quote!(
+                        use chrono::Utc;
+                    )
+                }),
+                quote!(, Set),
+                quote! {
+                    impl ActiveModelBehavior for ActiveModel {
+                        fn before_save(mut self, insert: bool) -> Result<Self, DbErr> {
+                            #set_id
+                            #set_timestamps
+                            Ok(self)
+                        }
+                    }
+                },
+            )
+        };
+    let (deleted_at, not_deleted, find_deleted_by_id) = if options.soft_delete {
+        let ty = options.timestamps.deleted_at_type().rust_type();
+        (
+            quote! {
+                #[sea_orm(nullable)]
+                pub deleted_at: Option<#ty>,
+            },
+            quote!(.filter(Column::DeletedAt.is_null())),
+            quote! {
+                /// A soft deleted row, to restore it.
+                pub fn find_deleted_by_id(id: #pk) -> Select<Entity> {
+                    Self::find()
+                        .filter(Column::Id.eq(id))
+                        .filter(Column::DeletedAt.is_not_null())
+                }
+            },
+        )
+    } else {
+        (quote!(), quote!(), quote!())
+    };
+    let references = fields
+        .iter()
+        .filter(|f| f.references.is_some())
+        .collect::<Vec<_>>();
+    let relation_variants = references.iter().map(|f| belongs_to_variant_tokens(f));
+    let related_impls = references
+        .iter()
+        .map(|f| related_impl_tokens(f.references.as_ref().unwrap()));
+    let not_deleted_loaded = options
+        .soft_delete
+        .then(|| quote!(.filter(Column::DeletedAt.is_null())));
+    // Loaders only exist on entities with relations, the others compile
+    // without the `dataloader` feature and `async-trait`.
+    let self_referenced = references
+        .iter()
+        .any(|f| f.references.as_deref() == Some(model_str));
+    let id_loader =
+        (referenced || self_referenced).then(|| id_loader_tokens(&pk, options.soft_delete));
+    let loader_imports = (id_loader.is_some() || !references.is_empty()).then(|| {
+        quote! {
+            use std::collections::HashMap;
+            use std::sync::Arc;
+        }
+    });
+    let reference_loaders = references
+        .iter()
+        .map(|f| reference_loader_tokens(f, &not_deleted_loaded));
+    let (complex, complex_object) = if references.is_empty() {
+        (quote!(), quote!())
+    } else {
+        let resolvers = references.iter().map(|f| belongs_to_resolver_tokens(f));
+        (
+            quote!(#[graphql(complex)]),
+            quote! {
+                #[ComplexObject]
+                impl Model {
+                    #(#resolvers)*
+                }
+            },
+        )
+    };
 
     quote! {
         use async_graphql::*;
-        use sea_orm::{entity::prelude::*, DeleteMany};
+        #chrono_import
+        use sea_orm::{entity::prelude::*, DeleteMany #set_import};
         use serde::{Deserialize, Serialize};
+        #loader_imports
 
         #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, SimpleObject)]
-        #[sea_orm(table_name = #model)]
-        #[graphql(concrete(name = "#cap_model", params()))]
+        #[sea_orm(table_name = #table_name)]
+        #complex
         pub struct Model {
-            #[sea_orm(primary_key)]
+            #pk_attr
             #[serde(skip_deserializing)]
-            pub id: i32,
-            #[sea_orm(indexed)]
-            pub created_at: DateTime,
-            #[sea_orm(indexed)]
-            pub updated_at: DateTime
+            pub id: #pk,
+            #(#columns)*
+            #timestamps
+            #deleted_at
         }
 
-        #[derive(Copy, Clone, Debug, EnumIter)]
-        pub enum Relation {}
+        #(#enums)*
 
-        impl RelationTrait for Relation {
-            fn def(&self) -> RelationDef {
-                panic!("No RelationDef")
-            }
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {
+            #(#relation_variants)*
         }
 
-        impl ActiveModelBehavior for ActiveModel {}
+        #(#related_impls)*
+
+        #active_model_behavior
 
         impl Entity {
-            pub fn find_by_id(id: i32) -> Select<Entity> {
-                Self::find().filter(Column::Id.eq(id))
+            pub fn find_by_id(id: #pk) -> Select<Entity> {
+                Self::find().filter(Column::Id.eq(id)) #not_deleted
             }
 
-            pub fn delete_by_id(id: i32) -> DeleteMany<Entity> {
+            #find_deleted_by_id
+
+            pub fn delete_by_id(id: #pk) -> DeleteMany<Entity> {
                 Self::delete_many().filter(Column::Id.eq(id))
             }
         }
+
+        #id_loader
+
+        #(#reference_loaders)*
+
+        #complex_object
+    }
+}
+
+/// `<Column>Loader` batching lookups of the rows belonging to a parent by the
+/// `<parent>_id` column of `field`, for the `has_many` side of the relation.
+fn reference_loader_tokens(field: &Field, not_deleted: &Option<TokenStream>) -> TokenStream {
+    let parent = field.references.as_ref().unwrap();
+    let loader = format_ident!("{}Loader", to_upper_camel(&field.name));
+    let column = format_ident!("{}", to_upper_camel(&field.name));
+    let name = field.ident();
+    let key = field.ty.rust_type();
+    let doc = format!(" Batches lookups of the rows belonging to `{}`s.", parent);
+    let group = if field.nullable {
+        quote! {
+            if let Some(key) = model.#name {
+                grouped.entry(key).or_default().push(model);
+            }
+        }
+    } else {
+        quote!(grouped.entry(model.#name).or_default().push(model);)
+    };
+
+    quote! {
+        #[doc = #doc]
+        pub struct #loader {
+            db: DatabaseConnection,
+        }
+
+        impl #loader {
+            pub fn new(db: DatabaseConnection) -> Self {
+                #loader { db }
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl dataloader::Loader<#key> for #loader {
+            type Value = Vec<Model>;
+            type Error = Arc<DbErr>;
+
+            async fn load(&self, keys: &[#key]) -> Result<HashMap<#key, Vec<Model>>, Self::Error> {
+                let models = Entity::find()
+                    .filter(Column::#column.is_in(keys.iter().cloned()))
+                    #not_deleted
+                    .all(&self.db)
+                    .await?;
+
+                let mut grouped = HashMap::<#key, Vec<Model>>::new();
+                for model in models {
+                    #group
+                }
+                Ok(grouped)
+            }
+        }
+    }
+}
+
+fn create_column_tokens(field: &Field) -> TokenStream {
+    let name = field.ident();
+    let ty = field.entity_type();
+
+    // `Schema::create_table_from_entity` turns these attributes into the
+    // column definition of the generated migration.
+    let mut attrs = Vec::new();
+    if let Some(column_type) = field.ty.column_type() {
+        attrs.push(quote!(column_type = #column_type));
+    }
+    if field.nullable {
+        attrs.push(quote!(nullable));
+    }
+    if let Some(default) = field.default_lit() {
+        attrs.push(quote!(default_value = #default));
+    }
+    let attrs = (!attrs.is_empty()).then(|| quote!(#[sea_orm(#(#attrs),*)]));
+
+    quote! {
+        #attrs
+        pub #name: #ty,
+    }
+}
+
+/// The enum of an enum column, stored as the `<model>_<field>` Postgres type
+/// and exposed to GraphQL as `<Model><Field>` so models can share field names.
+fn create_enum_tokens(model: &str, field: &Field) -> TokenStream {
+    let values = match &field.ty {
+        FieldType::Enum(values) => values,
+        _ => unreachable!(),
+    };
+    let ident = field.enum_ident().unwrap();
+    let enum_name = field.enum_name(model);
+    let graphql_name = to_upper_camel(&enum_name);
+    let variants = values
+        .iter()
+        .map(|value| format_ident!("{}", to_upper_camel(value)));
+
+    quote! {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, Enum)]
+        #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = #enum_name)]
+        #[graphql(name = #graphql_name)]
+        pub enum #ident {
+            #(
+                #[sea_orm(string_value = #values)]
+                #variants,
+            )*
+        }
+    }
+}
+
+fn create_join_model_tokens(
+    join: &str,
+    left: &str,
+    right: &str,
+    pks: [PrimaryKey; 2],
+) -> TokenStream {
+    let table_name = syn::LitStr::new(join, proc_macro2::Span::call_site());
+    let references = [left, right]
+        .iter()
+        .map(|model| format!("{}:references", model).parse::<Field>().unwrap())
+        .collect::<Vec<_>>();
+    let columns = references.iter().map(|f| f.ident());
+    let pks = pks.map(|pk| pk.rust_type());
+    let relation_variants = references.iter().map(belongs_to_variant_tokens);
+    let related_impls = [left, right].map(related_impl_tokens);
+
+    quote! {
+        use async_graphql::*;
+        use sea_orm::entity::prelude::*;
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, SimpleObject)]
+        #[sea_orm(table_name = #table_name)]
+        pub struct Model {
+            #(
+                #[sea_orm(primary_key, auto_increment = false)]
+                pub #columns: #pks,
+            )*
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {
+            #(#relation_variants)*
+        }
+
+        #(#related_impls)*
+
+        impl ActiveModelBehavior for ActiveModel {}
     }
 }
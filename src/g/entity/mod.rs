@@ -1,18 +1,115 @@
-use crate::g::entity::{creation::create_entity, registration::register_entity};
+use crate::g::entity::{
+    creation::{create_entity, create_join_entity},
+    registration::register_entity,
+    relation::{is_referenced, link_has_many, link_via, unlink_related},
+};
+use crate::g::{remove_generated_code, Field, ModelOptions, PrimaryKey};
 use crate::style_print::log_error;
+use quote::ToTokens;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use syn::Item;
 
 mod creation;
 mod registration;
+mod relation;
 
-pub(in crate::g) fn process_entity(model: &str, gen_path: &Path) {
+pub(in crate::g) fn entity_file(model: &str, gen_path: &Path) -> PathBuf {
+    gen_path
+        .join("entity")
+        .join("src")
+        .join(format!("{}.rs", model))
+}
+
+pub(in crate::g) fn process_entity(
+    model: &str,
+    fields: &[Field],
+    options: ModelOptions,
+    gen_path: &Path,
+) {
     let entity_src_dir = gen_path.join("entity").join("src");
 
     fs::create_dir_all(entity_src_dir.as_path()).unwrap_or_else(|why| {
         log_error(&format!("! {:?}", why.kind()));
     });
 
-    create_entity(model, &entity_src_dir);
+    let referenced = is_referenced(model, &entity_src_dir);
+    create_entity(model, fields, options, referenced, &entity_src_dir);
+    link_entity(model, fields, gen_path);
     register_entity(model, &entity_src_dir);
 }
+
+/// Adds the `has_many` side of the references of `model` to the referenced
+/// entities that do not have it yet.
+pub(in crate::g) fn link_entity(model: &str, fields: &[Field], gen_path: &Path) {
+    let entity_src_dir = gen_path.join("entity").join("src");
+
+    for parent in fields.iter().filter_map(|f| f.references.as_deref()) {
+        if parent != model {
+            link_has_many(model, parent, &entity_src_dir);
+        }
+    }
+}
+
+pub(in crate::g) fn process_join_entity(left: &str, right: &str, gen_path: &Path) {
+    let entity_src_dir = gen_path.join("entity").join("src");
+    let join = format!("{}_{}", left, right);
+
+    fs::create_dir_all(entity_src_dir.as_path()).unwrap_or_else(|why| {
+        log_error(&format!("! {:?}", why.kind()));
+    });
+
+    create_join_entity(
+        left,
+        right,
+        primary_key(left, gen_path),
+        primary_key(right, gen_path),
+        &entity_src_dir,
+    );
+    link_join_entity(left, right, gen_path);
+    register_entity(&join, &entity_src_dir);
+}
+
+/// Adds the `Related` impls going through the `<left>_<right>` join entity
+/// to both joined entities that do not have them yet.
+pub(in crate::g) fn link_join_entity(left: &str, right: &str, gen_path: &Path) {
+    let entity_src_dir = gen_path.join("entity").join("src");
+    let join = format!("{}_{}", left, right);
+
+    link_via(left, right, &join, &entity_src_dir);
+    link_via(right, left, &join, &entity_src_dir);
+}
+
+/// Primary key of the generated `model` entity, `i32` when there is none.
+pub(in crate::g) fn primary_key(model: &str, gen_path: &Path) -> PrimaryKey {
+    let content = match fs::read_to_string(entity_file(model, gen_path)) {
+        Ok(content) => content,
+        Err(_) => return PrimaryKey::I32,
+    };
+    let syntax_tree = match syn::parse_file(&content) {
+        Ok(syntax_tree) => syntax_tree,
+        Err(_) => return PrimaryKey::I32,
+    };
+
+    let id = syntax_tree.items.iter().find_map(|item| match item {
+        Item::Struct(item_struct) if item_struct.ident == "Model" => item_struct
+            .fields
+            .iter()
+            .find(|field| field.ident.as_ref().is_some_and(|ident| ident == "id")),
+        _ => None,
+    });
+    match id.map(|id| id.ty.to_token_stream().to_string()).as_deref() {
+        Some("i64") => PrimaryKey::I64,
+        Some("Uuid") => PrimaryKey::Uuid,
+        _ => PrimaryKey::I32,
+    }
+}
+
+pub(in crate::g) fn destroy_entity(model: &str, gen_path: &Path) {
+    let entity_src_dir = gen_path.join("entity").join("src");
+
+    if remove_generated_code(&entity_src_dir, &format!("{}.rs", model)) {
+        unlink_related(model, &entity_src_dir);
+        register_entity(model, &entity_src_dir);
+    }
+}
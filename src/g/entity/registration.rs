@@ -1,14 +1,20 @@
-use crate::g::{emit_generated_code, read_dir};
-use crate::style_print::log_success;
+use crate::g::registration::Registration;
+use crate::g::{format_generated_code, read_dir, write_generated_code};
+use crate::style_print::{log_error, log_success};
 
-use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use std::path::Path;
 
 pub(super) fn register_entity(model: &str, entity_src_dir: &Path) {
-    let file_content_tokens = register_entity_tokens(entity_src_dir);
+    let file_content = match register_entity_content(entity_src_dir) {
+        Ok(file_content) => file_content,
+        Err(why) => {
+            log_error(&why);
+            return;
+        }
+    };
 
-    let file_path = emit_generated_code(entity_src_dir, "lib.rs", &file_content_tokens);
+    let file_path = write_generated_code(entity_src_dir, "lib.rs", &file_content);
 
     log_success(&format!(
         "Successfully registered `{}` entity in {}",
@@ -17,23 +23,27 @@ pub(super) fn register_entity(model: &str, entity_src_dir: &Path) {
     ));
 }
 
-fn register_entity_tokens(entity_src_dir: &Path) -> TokenStream {
+fn register_entity_content(entity_src_dir: &Path) -> Result<String, String> {
     let entity_files = read_dir(entity_src_dir).unwrap();
     let mut entity_box = entity_files
         .iter()
-        .cloned()
-        .filter(|i| i != "lib.rs")
+        .filter(|&i| i != "lib.rs")
         .map(|i| i.replace(".rs", ""))
         .collect::<Vec<_>>();
     entity_box.sort();
 
+    if let Some(mut registration) = Registration::parse(entity_src_dir, "lib.rs")? {
+        registration.sync_mods(entity_src_dir, &entity_box, true);
+        return Ok(registration.into_source());
+    }
+
     let modules = entity_box
         .iter()
         .map(|i| format_ident!("{}", i))
         .collect::<Vec<_>>();
 
-    quote! {
+    Ok(format_generated_code(&quote! {
         pub use async_graphql;
         #(pub mod #modules;)*
-    }
+    }))
 }
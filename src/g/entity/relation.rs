@@ -0,0 +1,499 @@
+use crate::g::{emit_generated_code, read_dir, to_upper_camel, Field};
+use crate::style_print::{log_error, log_success};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use std::fs;
+use std::path::Path;
+use syn::{parse_quote, Item, ItemEnum, Meta, NestedMeta};
+
+/// `Relation` variant of a `name:references` field, e.g.
+/// `#[sea_orm(belongs_to = "super::user::Entity", ...)] User`.
+pub(super) fn belongs_to_variant_tokens(field: &Field) -> TokenStream {
+    let parent = field.references.as_ref().unwrap();
+    let variant = format_ident!("{}", to_upper_camel(parent));
+    let belongs_to = format!("super::{}::Entity", parent);
+    let from = format!("Column::{}", to_upper_camel(&field.name));
+    let to = format!("super::{}::Column::Id", parent);
+    let on_delete = if field.nullable { "SetNull" } else { "Cascade" };
+
+    quote! {
+        #[sea_orm(belongs_to = #belongs_to, from = #from, to = #to, on_delete = #on_delete)]
+        #variant,
+    }
+}
+
+/// Resolver of the parent a `name:references` field points at, loaded through
+/// the `IdLoader` of the parent entity.
+pub(super) fn belongs_to_resolver_tokens(field: &Field) -> TokenStream {
+    let parent = field.references.as_ref().unwrap();
+    let parent_mod = format_ident!("{}", parent);
+    let name = field.ident();
+    let load = if field.nullable {
+        quote! {
+            match self.#name {
+                Some(id) => Ok(loader.load_one(id).await?),
+                None => Ok(None),
+            }
+        }
+    } else {
+        quote!(Ok(loader.load_one(self.#name).await?))
+    };
+
+    quote! {
+        async fn #parent_mod(&self, ctx: &Context<'_>) -> Result<Option<super::#parent_mod::Model>> {
+            let loader = ctx.data::<dataloader::DataLoader<super::#parent_mod::IdLoader>>()?;
+            #load
+        }
+    }
+}
+
+/// `IdLoader` batching lookups of an entity by its `pk`, for the relations
+/// pointing at it.
+pub(super) fn id_loader_tokens(pk: &TokenStream, soft_delete: bool) -> TokenStream {
+    let not_deleted = soft_delete.then(|| quote!(.filter(Column::DeletedAt.is_null())));
+
+    quote! {
+        /// Batches lookups by id, for the relations pointing at this entity.
+        pub struct IdLoader {
+            db: DatabaseConnection,
+        }
+
+        impl IdLoader {
+            pub fn new(db: DatabaseConnection) -> Self {
+                IdLoader { db }
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl dataloader::Loader<#pk> for IdLoader {
+            type Value = Model;
+            type Error = Arc<DbErr>;
+
+            async fn load(&self, keys: &[#pk]) -> Result<HashMap<#pk, Model>, Self::Error> {
+                let models = Entity::find()
+                    .filter(Column::Id.is_in(keys.iter().cloned()))
+                    #not_deleted
+                    .all(&self.db)
+                    .await?;
+
+                Ok(models.into_iter().map(|model| (model.id, model)).collect())
+            }
+        }
+    }
+}
+
+/// Whether another generated entity loads its `model` through the `IdLoader`,
+/// join entities belong to `model` without resolving it.
+pub(super) fn is_referenced(model: &str, entity_src_dir: &Path) -> bool {
+    let id_loader = format!("super::{}::IdLoader", model);
+    let mut entity_files = read_dir(entity_src_dir).unwrap_or_default();
+    entity_files.retain(|i| i != "lib.rs" && *i != format!("{}.rs", model));
+
+    entity_files.iter().any(|file_name| {
+        fs::read_to_string(entity_src_dir.join(file_name))
+            .is_ok_and(|content| content.contains(&id_loader))
+    })
+}
+
+fn is_id_loader(item: &Item) -> bool {
+    match item {
+        Item::Struct(item_struct) => item_struct.ident == "IdLoader",
+        Item::Impl(item_impl) => item_impl.self_ty.to_token_stream().to_string() == "IdLoader",
+        _ => false,
+    }
+}
+
+fn loader_imports() -> [Item; 2] {
+    [
+        parse_quote!(
+            use std::collections::HashMap;
+        ),
+        parse_quote!(
+            use std::sync::Arc;
+        ),
+    ]
+}
+
+/// Drops the `IdLoader` of an entity nothing belongs to anymore, and its
+/// imports once no loader is left.
+fn remove_id_loader(syntax_tree: &mut syn::File) {
+    if !syntax_tree.items.iter().any(is_id_loader) {
+        return;
+    }
+    syntax_tree.items.retain(|item| !is_id_loader(item));
+
+    let loaders = syntax_tree.items.iter().any(|item| match item {
+        Item::Impl(item_impl) => item_impl.trait_.as_ref().is_some_and(|(_, path, _)| {
+            path.to_token_stream()
+                .to_string()
+                .starts_with("dataloader :: Loader")
+        }),
+        _ => false,
+    });
+    if !loaders {
+        let imports = loader_imports().map(|i| i.to_token_stream().to_string());
+        syntax_tree
+            .items
+            .retain(|item| !imports.contains(&item.to_token_stream().to_string()));
+    }
+}
+
+/// Adds the `IdLoader` and its imports to a parsed entity file missing it.
+fn add_id_loader(syntax_tree: &mut syn::File) {
+    let has_loader = syntax_tree
+        .items
+        .iter()
+        .any(|item| matches!(item, Item::Struct(s) if s.ident == "IdLoader"));
+    let model = syntax_tree.items.iter().find_map(|item| match item {
+        Item::Struct(item_struct) if item_struct.ident == "Model" => Some(item_struct),
+        _ => None,
+    });
+    let model = match model {
+        Some(model) if !has_loader => model,
+        _ => return,
+    };
+    let field = |name: &str| {
+        model
+            .fields
+            .iter()
+            .find(|f| f.ident.as_ref().is_some_and(|ident| ident == name))
+    };
+    let pk = match field("id") {
+        Some(id) => id.ty.to_token_stream(),
+        None => return,
+    };
+    let soft_delete = field("deleted_at").is_some();
+
+    for import in loader_imports() {
+        let imported = syntax_tree
+            .items
+            .iter()
+            .any(|item| item.to_token_stream().to_string() == import.to_token_stream().to_string());
+        if !imported {
+            let position = syntax_tree
+                .items
+                .iter()
+                .rposition(|item| matches!(item, Item::Use(_)))
+                .map_or(0, |i| i + 1);
+            syntax_tree.items.insert(position, import);
+        }
+    }
+    let loader: syn::File = syn::parse2(id_loader_tokens(&pk, soft_delete)).unwrap();
+    syntax_tree.items.extend(loader.items);
+}
+
+/// Resolver of the `model` rows belonging to the parent, loaded through the
+/// `<Parent>IdLoader` of `model`.
+fn has_many_resolver(model: &str, parent: &str) -> syn::ImplItem {
+    let model_mod = format_ident!("{}", model);
+    let models = format_ident!("{}s", model);
+    let loader = format_ident!("{}IdLoader", to_upper_camel(parent));
+
+    parse_quote! {
+        async fn #models(&self, ctx: &Context<'_>) -> Result<Vec<super::#model_mod::Model>> {
+            let loader = ctx.data::<dataloader::DataLoader<super::#model_mod::#loader>>()?;
+            Ok(loader.load_one(self.id).await?.unwrap_or_default())
+        }
+    }
+}
+
+fn is_complex_object(item_impl: &syn::ItemImpl) -> bool {
+    item_impl.trait_.is_none()
+        && item_impl
+            .attrs
+            .iter()
+            .any(|attr| attr.path.is_ident("ComplexObject"))
+}
+
+/// Adds `resolver` to the `#[ComplexObject] impl Model` of an entity file,
+/// creating it along with `#[graphql(complex)]` on `Model` when missing.
+fn add_resolver(syntax_tree: &mut syn::File, resolver: syn::ImplItem) {
+    let complex_object = syntax_tree.items.iter_mut().find_map(|item| match item {
+        Item::Impl(item_impl) if is_complex_object(item_impl) => Some(item_impl),
+        _ => None,
+    });
+    if let Some(complex_object) = complex_object {
+        complex_object.items.push(resolver);
+        return;
+    }
+
+    for item in syntax_tree.items.iter_mut() {
+        if let Item::Struct(model) = item {
+            if model.ident == "Model" {
+                model.attrs.push(parse_quote!(#[graphql(complex)]));
+            }
+        }
+    }
+    syntax_tree.items.push(parse_quote! {
+        #[ComplexObject]
+        impl Model {
+            #resolver
+        }
+    });
+}
+
+pub(super) fn related_impl_tokens(other: &str) -> TokenStream {
+    let other_mod = format_ident!("{}", other);
+    let variant = format_ident!("{}", to_upper_camel(other));
+
+    quote! {
+        impl Related<super::#other_mod::Entity> for Entity {
+            fn to() -> RelationDef {
+                Relation::#variant.def()
+            }
+        }
+    }
+}
+
+/// Adds the `has_many` side of `model` belonging to `parent` to the already
+/// generated `entity/src/<parent>.rs`.
+pub(super) fn link_has_many(model: &str, parent: &str, entity_src_dir: &Path) {
+    let parent_file = entity_src_dir.join(format!("{}.rs", parent));
+    let content = match fs::read_to_string(&parent_file) {
+        Ok(content) => content,
+        Err(_) => {
+            log_error(&format!(
+                "Entity `{}` not found, add `Related<super::{}::Entity>` to it by hand",
+                parent, model
+            ));
+            return;
+        }
+    };
+    let mut syntax_tree = syn::parse_file(&content).unwrap();
+
+    let variant = format_ident!("{}", to_upper_camel(model));
+    let has_many = format!("super::{}::Entity", model);
+
+    let relation = syntax_tree.items.iter_mut().find_map(|item| match item {
+        Item::Enum(item_enum) if item_enum.ident == "Relation" => Some(item_enum),
+        _ => None,
+    });
+    let relation = match relation {
+        Some(relation) => relation,
+        None => {
+            log_error(&format!("No `Relation` enum in {}", parent_file.display()));
+            return;
+        }
+    };
+    if relation.variants.iter().any(|v| v.ident == variant) {
+        return;
+    }
+    relation.variants.push(parse_quote! {
+        #[sea_orm(has_many = #has_many)]
+        #variant
+    });
+
+    if derive_relation(relation) {
+        // The derive replaces a hand-written `RelationTrait` impl such as the
+        // panicking one emitted by earlier versions of the generator.
+        syntax_tree.items.retain(|item| match item {
+            Item::Impl(item_impl) => match &item_impl.trait_ {
+                Some((_, path, _)) => !path.is_ident("RelationTrait"),
+                None => true,
+            },
+            _ => true,
+        });
+    }
+    syntax_tree
+        .items
+        .push(syn::parse2(related_impl_tokens(model)).unwrap());
+    add_resolver(&mut syntax_tree, has_many_resolver(model, parent));
+    add_id_loader(&mut syntax_tree);
+
+    let file_path = emit_generated_code(
+        entity_src_dir,
+        &format!("{}.rs", parent),
+        &syntax_tree.into_token_stream(),
+    );
+
+    log_success(&format!(
+        "Successfully linked `{}` to `{}` entity in {}",
+        model,
+        parent,
+        file_path.display()
+    ));
+}
+
+/// Adds `Related<other::Entity>` going through the `join` entity to the
+/// already generated `entity/src/<model>.rs`.
+pub(super) fn link_via(model: &str, other: &str, join: &str, entity_src_dir: &Path) {
+    let model_file = entity_src_dir.join(format!("{}.rs", model));
+    let content = match fs::read_to_string(&model_file) {
+        Ok(content) => content,
+        Err(_) => {
+            log_error(&format!(
+                "Entity `{}` not found, add `Related<super::{}::Entity>` to it by hand",
+                model, other
+            ));
+            return;
+        }
+    };
+    let mut syntax_tree = syn::parse_file(&content).unwrap();
+
+    let other_mod = format_ident!("{}", other);
+    let join_mod = format_ident!("{}", join);
+    let model_variant = format_ident!("{}", to_upper_camel(model));
+    let other_variant = format_ident!("{}", to_upper_camel(other));
+    let related: syn::ItemImpl = parse_quote! {
+        impl Related<super::#other_mod::Entity> for Entity {
+            fn to() -> RelationDef {
+                super::#join_mod::Relation::#other_variant.def()
+            }
+
+            fn via() -> Option<RelationDef> {
+                Some(super::#join_mod::Relation::#model_variant.def().rev())
+            }
+        }
+    };
+
+    let trait_path = |item_impl: &syn::ItemImpl| {
+        item_impl
+            .trait_
+            .as_ref()
+            .map(|(_, path, _)| path.to_token_stream().to_string())
+    };
+    let linked = syntax_tree.items.iter().any(|item| match item {
+        Item::Impl(item_impl) => trait_path(item_impl) == trait_path(&related),
+        _ => false,
+    });
+    if linked {
+        return;
+    }
+    syntax_tree.items.push(Item::Impl(related));
+
+    let file_path = emit_generated_code(
+        entity_src_dir,
+        &format!("{}.rs", model),
+        &syntax_tree.into_token_stream(),
+    );
+
+    log_success(&format!(
+        "Successfully linked `{}` to `{}` through `{}` in {}",
+        model,
+        other,
+        join,
+        file_path.display()
+    ));
+}
+
+/// Drops every `Relation` variant, `Related` impl and resolver pointing at
+/// `model` from the remaining entity files, so they still compile once `model`
+/// is gone.
+pub(super) fn unlink_related(model: &str, entity_src_dir: &Path) {
+    let variant_target = format!("\"super::{}::Entity\"", model);
+    let impl_target = format!("super :: {} :: Entity", model);
+    let resolver_target = format!("super :: {} ::", model);
+    let belongs_to = |model: &str| format!("belongs_to = \"super::{}::Entity\"", model);
+
+    let mut entity_files = read_dir(entity_src_dir).unwrap();
+    entity_files.retain(|i| i != "lib.rs");
+    entity_files.sort();
+
+    for file_name in entity_files {
+        let content = fs::read_to_string(entity_src_dir.join(&file_name)).unwrap();
+        let mut syntax_tree = match syn::parse_file(&content) {
+            Ok(syntax_tree) => syntax_tree,
+            Err(_) => continue,
+        };
+        let items = syntax_tree.items.len();
+        let mut variants = 0;
+        let mut resolvers = 0;
+
+        for item in syntax_tree.items.iter_mut() {
+            if let Item::Enum(relation) = item {
+                if relation.ident == "Relation" {
+                    variants = relation.variants.len();
+                    relation.variants = relation
+                        .variants
+                        .iter()
+                        .filter(|v| {
+                            !v.attrs
+                                .iter()
+                                .any(|attr| attr.tokens.to_string().contains(&variant_target))
+                        })
+                        .cloned()
+                        .collect();
+                    variants -= relation.variants.len();
+                }
+            }
+        }
+        for item in syntax_tree.items.iter_mut() {
+            if let Item::Impl(item_impl) = item {
+                if is_complex_object(item_impl) {
+                    resolvers = item_impl.items.len();
+                    item_impl.items.retain(|resolver| {
+                        !resolver
+                            .to_token_stream()
+                            .to_string()
+                            .contains(&resolver_target)
+                    });
+                    resolvers -= item_impl.items.len();
+                }
+            }
+        }
+        let entity = file_name.trim_end_matches(".rs");
+        if !content.contains(&belongs_to(entity)) && !is_referenced(entity, entity_src_dir) {
+            remove_id_loader(&mut syntax_tree);
+        }
+        let mut complex = true;
+        syntax_tree.items.retain(|item| match item {
+            Item::Impl(item_impl) if is_complex_object(item_impl) => {
+                complex = !item_impl.items.is_empty();
+                complex
+            }
+            Item::Impl(item_impl) => match &item_impl.trait_ {
+                Some((_, path, _)) => !path.to_token_stream().to_string().contains(&impl_target),
+                None => true,
+            },
+            _ => true,
+        });
+        if !complex {
+            for item in syntax_tree.items.iter_mut() {
+                if let Item::Struct(item_struct) = item {
+                    item_struct.attrs.retain(|attr| {
+                        !(attr.path.is_ident("graphql")
+                            && attr.tokens.to_string().contains("complex"))
+                    });
+                }
+            }
+        }
+
+        if variants == 0 && resolvers == 0 && items == syntax_tree.items.len() {
+            continue;
+        }
+        let file_path =
+            emit_generated_code(entity_src_dir, &file_name, &syntax_tree.into_token_stream());
+
+        log_success(&format!(
+            "Successfully unlinked `{}` from {}",
+            model,
+            file_path.display()
+        ));
+    }
+}
+
+/// Makes sure `Relation` derives `DeriveRelation`, returns whether it had to be added.
+fn derive_relation(relation: &mut ItemEnum) -> bool {
+    for attr in relation.attrs.iter_mut() {
+        if !attr.path.is_ident("derive") {
+            continue;
+        }
+        if let Ok(Meta::List(list)) = attr.parse_meta() {
+            let mut derives = list.nested.into_iter().collect::<Vec<_>>();
+            let derived = derives.iter().any(|nested| match nested {
+                NestedMeta::Meta(meta) => meta.path().is_ident("DeriveRelation"),
+                _ => false,
+            });
+            if derived {
+                return false;
+            }
+            derives.push(parse_quote!(DeriveRelation));
+            *attr = parse_quote!(#[derive(#(#derives),*)]);
+            return true;
+        }
+    }
+    relation
+        .attrs
+        .push(parse_quote!(#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]));
+    true
+}
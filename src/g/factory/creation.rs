@@ -0,0 +1,302 @@
+use crate::g::field::EMAIL_DOMAIN;
+use crate::g::{emit_generated_code, to_upper_camel, Field, FieldType, Rule};
+use crate::style_print::log_success;
+use proc_macro2::{Ident, Literal, TokenStream};
+use quote::{format_ident, quote};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+pub(super) fn create_factory(model: &str, fields: &[Field], factories_dir: &Path) {
+    let file_content_tokens = create_factory_tokens(model, fields);
+
+    let file_path = emit_generated_code(
+        factories_dir,
+        &format!("{}.rs", model),
+        &file_content_tokens,
+    );
+
+    log_success(&format!(
+        "Successfully created `{}` factory: {}",
+        model,
+        file_path.display()
+    ));
+}
+
+/// What a factory uses of the `fake` crate.
+#[derive(Default)]
+struct Fakers {
+    /// Whether any value calls `Fake::fake`.
+    used: bool,
+    /// Generators by their module under `fake::faker`.
+    modules: BTreeMap<&'static str, BTreeSet<&'static str>>,
+    /// `let` statements typing the fakes that are formatted further.
+    bindings: Vec<TokenStream>,
+}
+
+impl Fakers {
+    fn faker(&mut self, module: &'static str, name: &'static str) -> Ident {
+        self.used = true;
+        self.modules.entry(module).or_default().insert(name);
+        format_ident!("{}", name)
+    }
+
+    fn imports(&self) -> TokenStream {
+        if !self.used {
+            return quote!();
+        }
+        let modules = self.modules.iter().map(|(module, names)| {
+            let path = module.split("::").map(|i| format_ident!("{}", i));
+            let names = names.iter().map(|name| format_ident!("{}", name));
+            quote! { use fake::faker::#(#path)::*::{#(#names),*}; }
+        });
+        quote! {
+            use fake::Fake;
+            #(#modules)*
+        }
+    }
+
+    /// `value` bound to `name` as a `String`, usable in `format!` arguments.
+    fn bind(&mut self, name: &Ident, value: TokenStream) -> Ident {
+        self.bindings.push(quote! { let #name: String = #value; });
+        name.clone()
+    }
+}
+
+/// A fake value of the column passing its rules, e.g. a person's name for
+/// `name` or a few words for `title`, and whether it uses the sequence number
+/// `n`, which keeps emails and uuids apart between rows.
+fn fake_value(field: &Field, model: &Ident, fakers: &mut Fakers) -> (TokenStream, bool) {
+    let len = field.rules.iter().find_map(|r| match r {
+        Rule::Len(min, max) => Some((*min, *max)),
+        _ => None,
+    });
+    let (value, numbered) = match &field.ty {
+        FieldType::String | FieldType::Text if is_email(field) => {
+            let first_name = fakers.faker("name::en", "FirstName");
+            let local = format_ident!("{}_local", field.name);
+            let local = fakers.bind(
+                &local,
+                quote!(#first_name().fake::<String>().to_lowercase() + &n.to_string()),
+            );
+            // The length rule is met by the local part, the domain is fixed.
+            // `Field` refuses a max leaving no room for it.
+            let format = match len {
+                Some((min, max)) => {
+                    let domain = EMAIL_DOMAIN.len() as u32;
+                    let (min, max) = (min.saturating_sub(domain), max - domain);
+                    format!("{{:x<{}.{}}}{}", min, max, EMAIL_DOMAIN)
+                }
+                None => format!("{{}}{}", EMAIL_DOMAIN),
+            };
+            let lit = Literal::string(&format);
+            (quote!(format!(#lit, #local)), true)
+        }
+        FieldType::String | FieldType::Text => {
+            let value = match field.name.as_str() {
+                "first_name" => {
+                    let first_name = fakers.faker("name::en", "FirstName");
+                    quote!(#first_name().fake())
+                }
+                "last_name" => {
+                    let last_name = fakers.faker("name::en", "LastName");
+                    quote!(#last_name().fake())
+                }
+                "username" | "user_name" => {
+                    let username = fakers.faker("internet::en", "Username");
+                    quote!(#username().fake())
+                }
+                name if name == "name" || name.ends_with("_name") => {
+                    let name = fakers.faker("name::en", "Name");
+                    quote!(#name().fake())
+                }
+                _ if field.ty == FieldType::Text => {
+                    let paragraph = fakers.faker("lorem::en", "Paragraph");
+                    quote!(#paragraph(1..3).fake())
+                }
+                _ => {
+                    let words = fakers.faker("lorem::en", "Words");
+                    quote!(#words(1..4).fake::<Vec<String>>().join(" "))
+                }
+            };
+            match len {
+                Some((min, max)) => {
+                    let value = fakers.bind(&field.ident(), value);
+                    let lit = Literal::string(&format!("{{:x<{}.{}}}", min, max));
+                    (quote!(format!(#lit, #value)), false)
+                }
+                None => (value, false),
+            }
+        }
+        // A referenced row has to be given, see the setter of the column.
+        FieldType::I32 | FieldType::I64 if field.references.is_some() => {
+            (field.ty.sample().0, false)
+        }
+        FieldType::I32 | FieldType::I64 => {
+            let (min, max) = field
+                .rules
+                .iter()
+                .find_map(|r| match r {
+                    Rule::Range(min, max) => Some((*min, *max)),
+                    _ => None,
+                })
+                .unwrap_or((1, 1000));
+            let (min, max) = match field.ty {
+                FieldType::I32 => {
+                    let clamp = |i: i64| i.clamp(i32::MIN.into(), i32::MAX.into()) as i32;
+                    (
+                        Literal::i32_suffixed(clamp(min)),
+                        Literal::i32_suffixed(clamp(max)),
+                    )
+                }
+                _ => (Literal::i64_suffixed(min), Literal::i64_suffixed(max)),
+            };
+            fakers.used = true;
+            (quote!((#min..=#max).fake()), false)
+        }
+        FieldType::F32 => {
+            fakers.used = true;
+            (quote!((0.0f32..1000.0).fake()), false)
+        }
+        FieldType::F64 => {
+            fakers.used = true;
+            (quote!((0.0f64..1000.0).fake()), false)
+        }
+        FieldType::Bool => {
+            let boolean = fakers.faker("boolean::en", "Boolean");
+            (quote!(#boolean(50).fake()), false)
+        }
+        FieldType::Uuid if field.references.is_none() => {
+            (quote!(Uuid::from_u128(u128::from(n))), true)
+        }
+        _ => {
+            let (value, _) = field.sample(model);
+            return (value, false);
+        }
+    };
+    (optional(field, value), numbered)
+}
+
+fn is_email(field: &Field) -> bool {
+    field.rules.contains(&Rule::Email) || field.name == "email" || field.name.ends_with("_email")
+}
+
+fn optional(field: &Field, value: TokenStream) -> TokenStream {
+    if field.nullable {
+        quote!(Some(#value))
+    } else {
+        value
+    }
+}
+
+fn create_factory_tokens(model: &str, fields: &[Field]) -> TokenStream {
+    let model_name = format_ident!("{}", model);
+    let factory = format_ident!("{}Factory", to_upper_camel(model));
+    let factory_doc = format!(" Builds `{}` rows with every column filled.", model);
+    let example_doc = fields.first().map(|f| {
+        let doc = " Override columns with their setters, e.g.";
+        let example = format!(" `{}::new().{}(..).insert(&db)`.", factory, f.name);
+        quote! {
+            #[doc = ""]
+            #[doc = #doc]
+            #[doc = #example]
+        }
+    });
+
+    let mut fakers = Fakers::default();
+    let values = fields
+        .iter()
+        .map(|f| (f, fake_value(f, &model_name, &mut fakers)))
+        .collect::<Vec<_>>();
+    let numbered = values.iter().any(|(_, (_, numbered))| *numbered);
+    let columns = values.iter().map(|(f, (value, _))| {
+        let name = f.ident();
+        quote!(#name: Set(#value),)
+    });
+    let setters = fields.iter().map(|f| {
+        let name = f.ident();
+        let ty = f.entity_type();
+        let doc = match &f.references {
+            Some(parent) => format!(" The `{}` the row belongs to.", parent),
+            None => format!(" Overrides the generated `{}`.", f.name),
+        };
+        quote! {
+            #[doc = #doc]
+            pub fn #name(mut self, #name: impl Into<#ty>) -> Self {
+                self.model.#name = Set(#name.into());
+                self
+            }
+        }
+    });
+
+    let enums = fields
+        .iter()
+        .filter_map(|f| f.enum_ident())
+        .collect::<Vec<_>>();
+    let set_import = (!fields.is_empty()).then(|| quote! { use sea_orm::Set; });
+    let fake_imports = fakers.imports();
+    let bindings = &fakers.bindings;
+    let (sequence_import, sequence, next) = if numbered {
+        (
+            quote! { use std::sync::atomic::{AtomicU32, Ordering}; },
+            quote! {
+                /// Numbers the rows built by the factory, keeping their values apart.
+                static SEQUENCE: AtomicU32 = AtomicU32::new(1);
+            },
+            quote! { let n = SEQUENCE.fetch_add(1, Ordering::Relaxed); },
+        )
+    } else {
+        (quote!(), quote!(), quote!())
+    };
+
+    let model_import = if enums.is_empty() {
+        quote! { use crate::#model_name; }
+    } else {
+        quote! { use crate::#model_name::{self, #(#enums),*}; }
+    };
+
+    quote! {
+        #model_import
+        #fake_imports
+        use sea_orm::prelude::*;
+        #set_import
+        #sequence_import
+
+        #sequence
+
+        #[doc = #factory_doc]
+        #example_doc
+        pub struct #factory {
+            model: #model_name::ActiveModel,
+        }
+
+        impl #factory {
+            pub fn new() -> Self {
+                #next
+                #(#bindings)*
+                #factory {
+                    model: #model_name::ActiveModel {
+                        #(#columns)*
+                        ..Default::default()
+                    },
+                }
+            }
+
+            #(#setters)*
+
+            /// The row as it would be inserted.
+            pub fn build(self) -> #model_name::ActiveModel {
+                self.model
+            }
+
+            pub async fn insert(self, db: &DatabaseConnection) -> Result<#model_name::Model, DbErr> {
+                self.model.insert(db).await
+            }
+        }
+
+        impl Default for #factory {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    }
+}
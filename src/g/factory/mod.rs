@@ -0,0 +1,41 @@
+use crate::g::factory::{
+    creation::create_factory,
+    registration::{declare_factories_feature, declare_factories_module, register_factory},
+};
+use crate::g::{remove_generated_code, Field};
+use crate::style_print::log_error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod creation;
+mod registration;
+
+pub(in crate::g) fn factory_file(model: &str, gen_path: &Path) -> PathBuf {
+    gen_path
+        .join("entity")
+        .join("src")
+        .join("factories")
+        .join(format!("{}.rs", model))
+}
+
+pub(in crate::g) fn process_factory(model: &str, fields: &[Field], gen_path: &Path) {
+    let entity_src_dir = gen_path.join("entity").join("src");
+    let factories_dir = entity_src_dir.join("factories");
+
+    fs::create_dir_all(factories_dir.as_path()).unwrap_or_else(|why| {
+        log_error(&format!("! {:?}", why.kind()));
+    });
+
+    create_factory(model, fields, &factories_dir);
+    register_factory(model, &factories_dir);
+    declare_factories_module(&entity_src_dir);
+    declare_factories_feature(&gen_path.join("entity"));
+}
+
+pub(in crate::g) fn destroy_factory(model: &str, gen_path: &Path) {
+    let factories_dir = gen_path.join("entity").join("src").join("factories");
+
+    if remove_generated_code(&factories_dir, &format!("{}.rs", model)) {
+        register_factory(model, &factories_dir);
+    }
+}
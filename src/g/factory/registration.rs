@@ -0,0 +1,165 @@
+use crate::g::registration::Registration;
+use crate::g::{format_generated_code, read_dir, to_upper_camel, write_generated_code};
+use crate::style_print::{log_error, log_success};
+use proc_macro2::Ident;
+use quote::{format_ident, quote};
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+
+pub(super) fn register_factory(model: &str, factories_dir: &Path) {
+    let file_content = match register_factory_content(factories_dir) {
+        Ok(file_content) => file_content,
+        Err(why) => {
+            log_error(&why);
+            return;
+        }
+    };
+
+    let file_path = write_generated_code(factories_dir, "mod.rs", &file_content);
+
+    log_success(&format!(
+        "Successfully registered `{}` factory in {}",
+        model,
+        file_path.display()
+    ));
+}
+
+/// Declares `factories` next to the entities in the `lib.rs` of the entity
+/// crate, if it is not yet. It is only compiled with the `factories` feature,
+/// so `fake` stays out of the app's build.
+pub(super) fn declare_factories_module(entity_src_dir: &Path) {
+    let mut registration = match Registration::parse(entity_src_dir, "lib.rs") {
+        Ok(Some(registration)) => registration,
+        Ok(None) => return,
+        Err(why) => {
+            log_error(&why);
+            return;
+        }
+    };
+    let (added, _) = registration.sync_mods_as(
+        entity_src_dir,
+        &["factories".to_string()],
+        quote!(#[cfg(any(test, feature = "factories"))] pub),
+    );
+    if added.is_empty() {
+        return;
+    }
+
+    let file_path = write_generated_code(entity_src_dir, "lib.rs", &registration.into_source());
+
+    log_success(&format!(
+        "Successfully declared the factories module in {}",
+        file_path.display()
+    ));
+}
+
+/// Adds the `factories` feature and the optional `fake` dependency it enables
+/// to the `Cargo.toml` of the entity crate, if it has no such feature yet.
+pub(super) fn declare_factories_feature(entity_dir: &Path) {
+    let manifest_file = entity_dir.join("Cargo.toml");
+    let manifest = match fs::read_to_string(&manifest_file) {
+        Ok(manifest) => manifest,
+        Err(why) => {
+            log_error(&format!(
+                "Could not read {}: {:?}, add an optional `fake` dependency and a `factories = [\"fake\"]` feature to it",
+                manifest_file.display(),
+                why.kind()
+            ));
+            return;
+        }
+    };
+    if Regex::new(r"(?m)^factories\s*=")
+        .unwrap()
+        .is_match(&manifest)
+    {
+        return;
+    }
+
+    // A `fake` the app added by hand is kept, the feature then enables nothing.
+    let (manifest, feature) = if Regex::new(r"(?m)^fake\s*=").unwrap().is_match(&manifest) {
+        (manifest, r#"factories = []"#)
+    } else {
+        (
+            add_to_table(
+                &manifest,
+                "dependencies",
+                r#"fake = { version = "2", optional = true }"#,
+            ),
+            r#"factories = ["fake"]"#,
+        )
+    };
+    let manifest = add_to_table(&manifest, "features", feature);
+    fs::write(&manifest_file, manifest).unwrap();
+
+    log_success(&format!(
+        "Successfully added the `factories` feature to {}",
+        manifest_file.display()
+    ));
+}
+
+/// Appends `line` to the `[table]` of `manifest`, which is added at its end if
+/// it is missing.
+fn add_to_table(manifest: &str, table: &str, line: &str) -> String {
+    let header = format!("[{}]", table);
+    let mut lines = manifest.lines().collect::<Vec<_>>();
+    match lines.iter().position(|l| l.trim() == header) {
+        Some(start) => {
+            let mut end = lines[start + 1..]
+                .iter()
+                .position(|l| l.trim_start().starts_with('['))
+                .map_or(lines.len(), |n| start + 1 + n);
+            while end > start + 1 && lines[end - 1].trim().is_empty() {
+                end -= 1;
+            }
+            lines.insert(end, line);
+        }
+        None => {
+            if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+                lines.push("");
+            }
+            lines.push(&header);
+            lines.push(line);
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+fn register_factory_content(factories_dir: &Path) -> Result<String, String> {
+    let factory_files = read_dir(factories_dir).unwrap();
+    let mut factory_box = factory_files
+        .iter()
+        .filter(|&i| i != "mod.rs")
+        .map(|i| i.replace(".rs", ""))
+        .collect::<Vec<_>>();
+    factory_box.sort();
+
+    if let Some(mut registration) = Registration::parse(factories_dir, "mod.rs")? {
+        let (added, stale) = registration.sync_mods(factories_dir, &factory_box, true);
+        let uses = added
+            .iter()
+            .map(|m| (m.clone(), factory_member(m)))
+            .collect::<Vec<_>>();
+        registration.sync_uses(&uses, &stale);
+        return Ok(registration.into_source());
+    }
+
+    let modules = factory_box
+        .iter()
+        .map(|i| format_ident!("{}", i))
+        .collect::<Vec<_>>();
+
+    let members = factory_box
+        .iter()
+        .map(|s| factory_member(s))
+        .collect::<Vec<_>>();
+
+    Ok(format_generated_code(&quote! {
+        #(pub mod #modules;)*
+        #(pub use #modules::#members;)*
+    }))
+}
+
+fn factory_member(module: &str) -> Ident {
+    format_ident!("{}Factory", to_upper_camel(module))
+}
@@ -0,0 +1,736 @@
+use crate::g::to_upper_camel;
+use proc_macro2::{Ident, Literal, TokenStream};
+use quote::{format_ident, quote};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+const RESERVED_FIELDS: [&str; 3] = ["id", "created_at", "updated_at"];
+
+/// Domain of the emails the tests, factories and seeds make up.
+pub(in crate::g) const EMAIL_DOMAIN: &str = "@example.com";
+
+/// Shortest `len` max of an `email` column, room for the domain and a local
+/// part of two characters.
+const MIN_EMAIL_LEN: u32 = EMAIL_DOMAIN.len() as u32 + 2;
+
+/// Enum types are named after their field, these would clash with the items
+/// of the entity file.
+const RESERVED_ENUM_FIELDS: [&str; 6] = [
+    "active_model",
+    "column",
+    "entity",
+    "model",
+    "primary_key",
+    "relation",
+];
+
+/// Column types accepted in `name:type` field specs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FieldType {
+    String,
+    Text,
+    I32,
+    I64,
+    F32,
+    F64,
+    Bool,
+    Date,
+    DateTime,
+    /// `timestamp with time zone`
+    DateTimeTz,
+    Uuid,
+    Json,
+    /// `enum(active,suspended)`, a Postgres enum with the given snake_case
+    /// values.
+    Enum(Vec<String>),
+}
+
+impl FieldType {
+    /// Rust type of the column as seen from a file importing the SeaORM prelude,
+    /// enums are named after their field, see `Field::rust_type`.
+    pub(in crate::g) fn rust_type(&self) -> TokenStream {
+        match self {
+            FieldType::String | FieldType::Text => quote!(String),
+            FieldType::I32 => quote!(i32),
+            FieldType::I64 => quote!(i64),
+            FieldType::F32 => quote!(f32),
+            FieldType::F64 => quote!(f64),
+            FieldType::Bool => quote!(bool),
+            FieldType::Date => quote!(Date),
+            FieldType::DateTime => quote!(DateTime),
+            FieldType::DateTimeTz => quote!(DateTimeWithTimeZone),
+            FieldType::Uuid => quote!(Uuid),
+            FieldType::Json => quote!(Json),
+            FieldType::Enum(_) => unreachable!("enum types are named after their field"),
+        }
+    }
+
+    /// Name to pull in from `sea_orm::prelude` when the type is not a Rust primitive.
+    pub(in crate::g) fn prelude_import(&self) -> Option<Ident> {
+        match self {
+            FieldType::Date => Some(format_ident!("Date")),
+            FieldType::DateTime => Some(format_ident!("DateTime")),
+            FieldType::DateTimeTz => Some(format_ident!("DateTimeWithTimeZone")),
+            FieldType::Uuid => Some(format_ident!("Uuid")),
+            FieldType::Json => Some(format_ident!("Json")),
+            _ => None,
+        }
+    }
+
+    pub(in crate::g) fn is_string(&self) -> bool {
+        matches!(self, FieldType::String | FieldType::Text)
+    }
+
+    /// Whether `default` is a valid default of a column of the type, `None`
+    /// for types that cannot have one.
+    pub(in crate::g) fn accepts_default(&self, default: &str) -> Option<bool> {
+        let valid = match self {
+            FieldType::String | FieldType::Text => true,
+            FieldType::I32 => default.parse::<i32>().is_ok(),
+            FieldType::I64 => default.parse::<i64>().is_ok(),
+            FieldType::F32 => default.parse::<f32>().is_ok_and(f32::is_finite),
+            FieldType::F64 => default.parse::<f64>().is_ok_and(f64::is_finite),
+            FieldType::Bool => default.parse::<bool>().is_ok(),
+            FieldType::Enum(values) => values.iter().any(|value| value == default),
+            _ => return None,
+        };
+        Some(valid)
+    }
+
+    /// `ColumnDef` method giving a column the SQL type SeaORM derives for it.
+    fn column_def_method(&self) -> Ident {
+        let method = match self {
+            FieldType::String => "string",
+            FieldType::Text => "text",
+            FieldType::I32 => "integer",
+            FieldType::I64 => "big_integer",
+            FieldType::F32 => "float",
+            FieldType::F64 => "double",
+            FieldType::Bool => "boolean",
+            FieldType::Date => "date",
+            FieldType::DateTime => "date_time",
+            FieldType::DateTimeTz => "timestamp_with_time_zone",
+            FieldType::Uuid => "uuid",
+            FieldType::Json => "json",
+            FieldType::Enum(_) => unreachable!("enum columns are created with their table"),
+        };
+        format_ident!("{}", method)
+    }
+
+    /// A value of the type as Rust, given the SeaORM prelude, and as a GraphQL
+    /// literal. Enums are left to `Field::sample`.
+    pub(in crate::g) fn sample(&self) -> (TokenStream, String) {
+        let parsed = |value: &str| {
+            let lit = Literal::string(value);
+            (quote!(#lit.parse().unwrap()), format!("{:?}", value))
+        };
+        match self {
+            FieldType::String | FieldType::Text => {
+                (quote!("sample".to_owned()), "\"sample\"".to_string())
+            }
+            FieldType::I32 | FieldType::I64 => (quote!(1), "1".to_string()),
+            FieldType::F32 | FieldType::F64 => (quote!(1.5), "1.5".to_string()),
+            FieldType::Bool => (quote!(true), "true".to_string()),
+            FieldType::Date => parsed("2022-07-16"),
+            FieldType::DateTime => parsed("2022-07-16T23:39:33"),
+            FieldType::DateTimeTz => parsed("2022-07-16T23:39:33+00:00"),
+            FieldType::Uuid => parsed("67e55044-10b1-426f-9247-bb680e5fe0c8"),
+            FieldType::Json => (quote!(Json::Object(Default::default())), "{}".to_string()),
+            FieldType::Enum(_) => unreachable!("enum types are named after their field"),
+        }
+    }
+
+    /// Explicit `column_type` for types SeaORM would otherwise map differently.
+    pub(in crate::g) fn column_type(&self) -> Option<&'static str> {
+        match self {
+            FieldType::Text => Some("Text"),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for FieldType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(values) = s.strip_prefix("enum(").and_then(|s| s.strip_suffix(')')) {
+            let snake_case = Regex::new("^[a-z][a-z0-9_]*$").unwrap();
+            let values = values
+                .split(',')
+                .map(|value| value.trim().to_string())
+                .collect::<Vec<_>>();
+            for (i, value) in values.iter().enumerate() {
+                if !snake_case.is_match(value) {
+                    return Err(format!("enum value `{}` must be snake_case", value));
+                }
+                if !is_ident(&to_upper_camel(value)) {
+                    return Err(format!(
+                        "enum value `{}` would be the Rust keyword `{}`",
+                        value,
+                        to_upper_camel(value)
+                    ));
+                }
+                if values[..i].contains(value) {
+                    return Err(format!("enum value `{}` is listed twice", value));
+                }
+            }
+            return Ok(FieldType::Enum(values));
+        }
+
+        match s {
+            "string" => Ok(FieldType::String),
+            "text" => Ok(FieldType::Text),
+            "i32" | "integer" => Ok(FieldType::I32),
+            "i64" => Ok(FieldType::I64),
+            "f32" => Ok(FieldType::F32),
+            "f64" | "float" => Ok(FieldType::F64),
+            "bool" | "boolean" => Ok(FieldType::Bool),
+            "date" => Ok(FieldType::Date),
+            "datetime" => Ok(FieldType::DateTime),
+            "datetimetz" => Ok(FieldType::DateTimeTz),
+            "uuid" => Ok(FieldType::Uuid),
+            "json" => Ok(FieldType::Json),
+            _ => Err(format!("unknown field type `{}`", s)),
+        }
+    }
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let FieldType::Enum(values) = self {
+            return write!(f, "enum({})", values.join(","));
+        }
+        let name = match self {
+            FieldType::String => "string",
+            FieldType::Text => "text",
+            FieldType::I32 => "i32",
+            FieldType::I64 => "i64",
+            FieldType::F32 => "f32",
+            FieldType::F64 => "f64",
+            FieldType::Bool => "bool",
+            FieldType::Date => "date",
+            FieldType::DateTime => "datetime",
+            FieldType::DateTimeTz => "datetimetz",
+            FieldType::Uuid => "uuid",
+            FieldType::Json => "json",
+            FieldType::Enum(_) => unreachable!(),
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Validation rule of a column, checked by the generated input objects and,
+/// when Postgres can express it, by a `CHECK` constraint.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rule {
+    /// `email`, on string columns. Not checked by the database.
+    Email,
+    /// `len(min,max)`, the number of characters of string columns.
+    Len(u32, u32),
+    /// `range(min,max)`, inclusive bounds of integer columns.
+    Range(i64, i64),
+}
+
+impl Rule {
+    fn applies_to(&self, ty: &FieldType) -> bool {
+        match self {
+            Rule::Email | Rule::Len(..) => ty.is_string(),
+            Rule::Range(min, max) => match ty {
+                FieldType::I32 => i32::try_from(*min).is_ok() && i32::try_from(*max).is_ok(),
+                FieldType::I64 => true,
+                _ => false,
+            },
+        }
+    }
+
+    /// Whether a default value of the column passes the rule.
+    fn accepts(&self, value: &str) -> bool {
+        match self {
+            Rule::Email => Regex::new(r"^[^@\s]+@[^@\s]+\.[^@\s]+$")
+                .unwrap()
+                .is_match(value),
+            Rule::Len(min, max) => (*min..=*max).contains(&(value.chars().count() as u32)),
+            Rule::Range(min, max) => value
+                .parse::<i64>()
+                .is_ok_and(|value| (*min..=*max).contains(&value)),
+        }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "email" {
+            return Ok(Rule::Email);
+        }
+        let bounds = Regex::new(r"^(len|range)\((-?[0-9]+),(-?[0-9]+)\)$").unwrap();
+        let captures = bounds
+            .captures(s)
+            .ok_or_else(|| format!("unknown validation rule `{}`", s))?;
+        let rule = match &captures[1] {
+            "len" => {
+                let parse = |i: usize| captures[i].parse::<u32>();
+                match (parse(2), parse(3)) {
+                    (Ok(min), Ok(max)) if min <= max => Rule::Len(min, max),
+                    _ => return Err(format!("`{}` needs 0 <= min <= max", s)),
+                }
+            }
+            _ => {
+                let parse = |i: usize| captures[i].parse::<i64>();
+                match (parse(2), parse(3)) {
+                    (Ok(min), Ok(max)) if min <= max => Rule::Range(min, max),
+                    _ => return Err(format!("`{}` needs min <= max", s)),
+                }
+            }
+        };
+        Ok(rule)
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rule::Email => write!(f, "email"),
+            Rule::Len(min, max) => write!(f, "len({},{})", min, max),
+            Rule::Range(min, max) => write!(f, "range({},{})", min, max),
+        }
+    }
+}
+
+/// A model column given on the command line as `name:type`, e.g. `age:i32`.
+///
+/// A trailing `?` makes the column nullable (`bio:string?`) and `=value`
+/// gives it a default (`role:string=member`). `user:references` adds a
+/// `user_id` column that belongs to the `user` model and
+/// `status:enum(active,suspended)` an enum column. Validation rules follow
+/// the type, e.g. `name:string:len(1,100)` or `age:i32:range(0,150)?`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Field {
+    pub name: String,
+    pub ty: FieldType,
+    pub nullable: bool,
+    pub default: Option<String>,
+    pub references: Option<String>,
+    pub rules: Vec<Rule>,
+}
+
+impl Field {
+    pub(in crate::g) fn ident(&self) -> Ident {
+        format_ident!("{}", self.name)
+    }
+
+    /// The Rust enum of an enum column, e.g. `Status` for `status`.
+    pub(in crate::g) fn enum_ident(&self) -> Option<Ident> {
+        match self.ty {
+            FieldType::Enum(_) => Some(format_ident!("{}", to_upper_camel(&self.name))),
+            _ => None,
+        }
+    }
+
+    /// Name of the Postgres type of an enum column, e.g. `user_status`.
+    pub(in crate::g) fn enum_name(&self, model: &str) -> String {
+        format!("{}_{}", model, self.name)
+    }
+
+    fn rust_type(&self) -> TokenStream {
+        match self.enum_ident() {
+            Some(ident) => quote!(#ident),
+            None => self.ty.rust_type(),
+        }
+    }
+
+    /// Type of the column in the entity `Model`.
+    pub(in crate::g) fn entity_type(&self) -> TokenStream {
+        let ty = self.rust_type();
+        if self.nullable {
+            quote!(Option<#ty>)
+        } else {
+            ty
+        }
+    }
+
+    /// Type of the field in the generated `Create<Model>Input`, optional
+    /// whenever the column can be left out.
+    pub(in crate::g) fn input_type(&self) -> TokenStream {
+        let ty = self.rust_type();
+        if self.nullable || self.default.is_some() {
+            quote!(Option<#ty>)
+        } else {
+            ty
+        }
+    }
+
+    /// Type of the field in the generated `Update<Model>Input`, left out
+    /// fields are kept and nullable ones can be set to `null`.
+    pub(in crate::g) fn update_input_type(&self) -> TokenStream {
+        let ty = self.rust_type();
+        if self.nullable {
+            quote!(MaybeUndefined<#ty>)
+        } else {
+            quote!(Option<#ty>)
+        }
+    }
+
+    /// A value of the column passing its rules, as Rust with enums under
+    /// `model` and as a GraphQL literal.
+    pub(in crate::g) fn sample(&self, model: &Ident) -> (TokenStream, String) {
+        let (value, graphql) = match &self.ty {
+            FieldType::String | FieldType::Text => {
+                // The length rule of an email is met by its local part.
+                let domain = if self.rules.contains(&Rule::Email) {
+                    EMAIL_DOMAIN
+                } else {
+                    ""
+                };
+                let mut value = self.name.clone();
+                for rule in &self.rules {
+                    if let Rule::Len(min, max) = rule {
+                        let min = (*min as usize).saturating_sub(domain.len());
+                        let max = (*max as usize).saturating_sub(domain.len());
+                        value.truncate(max);
+                        value.extend(std::iter::repeat_n('x', min.saturating_sub(value.len())));
+                    }
+                }
+                value.push_str(domain);
+                let lit = Literal::string(&value);
+                (quote!(#lit.to_owned()), format!("{:?}", value))
+            }
+            FieldType::I32 | FieldType::I64 => {
+                let mut value = 1;
+                for rule in &self.rules {
+                    if let Rule::Range(min, max) = rule {
+                        value = value.clamp(*min, *max);
+                    }
+                }
+                let lit = Literal::i64_unsuffixed(value);
+                (quote!(#lit), value.to_string())
+            }
+            FieldType::Enum(values) => {
+                let ident = self.enum_ident().unwrap();
+                let variant = format_ident!("{}", to_upper_camel(&values[0]));
+                (quote!(#model::#ident::#variant), values[0].to_uppercase())
+            }
+            ty => ty.sample(),
+        };
+        if self.nullable {
+            (quote!(Some(#value)), graphql)
+        } else {
+            (value, graphql)
+        }
+    }
+
+    /// Statement applying `input.<field>` of an `Update<Model>Input` to the
+    /// `model` `ActiveModel`, if it was given.
+    pub(in crate::g) fn update_from_input(&self, model: &Ident) -> TokenStream {
+        let name = self.ident();
+        if self.nullable {
+            quote! {
+                match input.#name {
+                    MaybeUndefined::Value(#name) => #model.#name = Set(Some(#name)),
+                    MaybeUndefined::Null => #model.#name = Set(None),
+                    MaybeUndefined::Undefined => {}
+                }
+            }
+        } else {
+            quote! {
+                if let Some(#name) = input.#name {
+                    #model.#name = Set(#name);
+                }
+            }
+        }
+    }
+
+    /// `#[graphql(validator(...))]` of the field in the generated input objects.
+    pub(in crate::g) fn validator_attr(&self) -> Option<TokenStream> {
+        let validators = self
+            .rules
+            .iter()
+            .map(|rule| match rule {
+                Rule::Email => quote!(email),
+                Rule::Len(min, max) => {
+                    let (min, max) = (Literal::u32_unsuffixed(*min), Literal::u32_unsuffixed(*max));
+                    quote!(min_length = #min, max_length = #max)
+                }
+                Rule::Range(min, max) => {
+                    let (min, max) = (Literal::i64_unsuffixed(*min), Literal::i64_unsuffixed(*max));
+                    quote!(minimum = #min, maximum = #max)
+                }
+            })
+            .collect::<Vec<_>>();
+        (!validators.is_empty()).then(|| quote!(#[graphql(validator(#(#validators),*))]))
+    }
+
+    /// SQL of the `CHECK` constraints enforcing the rules of the column.
+    pub(in crate::g) fn checks(&self) -> Vec<String> {
+        self.rules
+            .iter()
+            .filter_map(|rule| match rule {
+                Rule::Email => None,
+                Rule::Len(min, max) => Some(format!(
+                    "char_length(\"{}\") BETWEEN {} AND {}",
+                    self.name, min, max
+                )),
+                Rule::Range(min, max) => {
+                    Some(format!("\"{}\" BETWEEN {} AND {}", self.name, min, max))
+                }
+            })
+            .collect()
+    }
+
+    /// The default as a literal usable both in `#[sea_orm(default_value = ...)]`
+    /// and in generated Rust code.
+    pub(in crate::g) fn default_lit(&self) -> Option<TokenStream> {
+        let default = self.default.as_ref()?;
+        let lit = match self.ty {
+            FieldType::String | FieldType::Text => {
+                let lit = Literal::string(default);
+                quote!(#lit)
+            }
+            FieldType::I32 | FieldType::I64 => {
+                let lit = Literal::i64_unsuffixed(default.parse().unwrap());
+                quote!(#lit)
+            }
+            FieldType::F32 | FieldType::F64 => {
+                let lit = Literal::f64_unsuffixed(default.parse().unwrap());
+                quote!(#lit)
+            }
+            FieldType::Bool => {
+                let lit = format_ident!("{}", default);
+                quote!(#lit)
+            }
+            FieldType::Enum(_) => {
+                let lit = Literal::string(default);
+                quote!(#lit)
+            }
+            _ => unreachable!("defaults are validated when parsing the field"),
+        };
+        Some(lit)
+    }
+
+    /// `ColumnDef` of the column for migrations that alter an existing table.
+    pub(in crate::g) fn column_def(&self) -> TokenStream {
+        let name = syn::LitStr::new(&self.name, proc_macro2::Span::call_site());
+        let method = self.ty.column_def_method();
+        let null = if self.nullable {
+            quote!(.null())
+        } else {
+            quote!(.not_null())
+        };
+        let default = self.default_lit().map(|lit| quote!(.default(#lit)));
+        quote!(ColumnDef::new(Alias::new(#name)).#method()#null #default)
+    }
+
+    /// Expression assigning `input.<field>` to the matching `ActiveModel` column,
+    /// falling back to the default when the input leaves it out.
+    pub(in crate::g) fn set_from_input(&self) -> TokenStream {
+        let name = self.ident();
+        let default = match (self.enum_ident(), &self.default) {
+            (Some(ident), Some(default)) => {
+                let variant = format_ident!("{}", to_upper_camel(default));
+                Some(quote!(#ident::#variant))
+            }
+            _ => self.default_lit(),
+        };
+        match default {
+            Some(lit) if self.ty.is_string() => {
+                if self.nullable {
+                    quote!(Set(input.#name.or_else(|| Some(#lit.to_owned()))))
+                } else {
+                    quote!(Set(input.#name.unwrap_or_else(|| #lit.to_owned())))
+                }
+            }
+            Some(lit) => {
+                if self.nullable {
+                    quote!(Set(input.#name.or(Some(#lit))))
+                } else {
+                    quote!(Set(input.#name.unwrap_or(#lit)))
+                }
+            }
+            None => quote!(Set(input.#name)),
+        }
+    }
+}
+
+impl FromStr for Field {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, spec) = s
+            .split_once(':')
+            .ok_or_else(|| format!("field `{}` must be given as `name:type`", s))?;
+        let (spec, default) = match spec.split_once('=') {
+            Some((spec, default)) => (spec, Some(default.to_string())),
+            None => (spec, None),
+        };
+        let (spec, nullable) = match spec.strip_suffix('?') {
+            Some(spec) => (spec, true),
+            None => (spec, false),
+        };
+        let mut parts = spec.split(':');
+        let ty = parts.next().unwrap();
+        let (ty, nullable) = match ty.strip_suffix('?') {
+            Some(ty) => (ty, true),
+            None => (ty, nullable),
+        };
+        let rules = parts
+            .map(|rule| rule.parse::<Rule>())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let snake_case = Regex::new("^[a-z][a-z0-9_]*$").unwrap();
+        if !snake_case.is_match(name) {
+            return Err(format!("field name `{}` must be snake_case", name));
+        }
+        if !is_ident(name) {
+            return Err(format!(
+                "field name `{}` is a Rust keyword, pick another one",
+                name
+            ));
+        }
+
+        let (name, ty, references) = if ty == "references" {
+            if default.is_some() {
+                return Err(format!("reference `{}` cannot have a default", name));
+            }
+            if !rules.is_empty() {
+                return Err(format!("reference `{}` cannot have validation rules", name));
+            }
+            (
+                format!("{}_id", name),
+                FieldType::I32,
+                Some(name.to_string()),
+            )
+        } else {
+            (name.to_string(), ty.parse::<FieldType>()?, None)
+        };
+
+        if RESERVED_FIELDS.contains(&name.as_str()) {
+            return Err(format!("field `{}` is generated automatically", name));
+        }
+        if matches!(ty, FieldType::Enum(_)) && RESERVED_ENUM_FIELDS.contains(&name.as_str()) {
+            return Err(format!("enum field `{}` needs another name", name));
+        }
+
+        if let Some(rule) = rules.iter().find(|rule| !rule.applies_to(&ty)) {
+            return Err(format!(
+                "validation rule `{}` does not apply to field `{}` of type {}",
+                rule, name, ty
+            ));
+        }
+
+        if rules.contains(&Rule::Email) {
+            if let Some(Rule::Len(_, max)) = rules.iter().find(|r| matches!(r, Rule::Len(..))) {
+                if *max < MIN_EMAIL_LEN {
+                    return Err(format!(
+                        "email field `{}` needs a max length of at least {}",
+                        name, MIN_EMAIL_LEN
+                    ));
+                }
+            }
+        }
+
+        if let Some(default) = &default {
+            let valid = match ty.accepts_default(default) {
+                Some(valid) => valid,
+                None => {
+                    return Err(format!(
+                        "field `{}` of type {:?} cannot have a default",
+                        name, ty
+                    ))
+                }
+            };
+            if !valid {
+                return Err(format!(
+                    "`{}` is not a valid default for field `{}`",
+                    default, name
+                ));
+            }
+            if let Some(rule) = rules.iter().find(|rule| !rule.accepts(default)) {
+                return Err(format!(
+                    "default `{}` of field `{}` does not pass `{}`",
+                    default, name, rule
+                ));
+            }
+        }
+
+        Ok(Field {
+            name,
+            ty,
+            nullable,
+            default,
+            references,
+            rules,
+        })
+    }
+}
+
+/// Whether `name` can be a Rust identifier, i.e. is not a keyword.
+fn is_ident(name: &str) -> bool {
+    syn::parse_str::<Ident>(name).is_ok()
+}
+
+/// The `name:type` spec the field parses from, references are written out
+/// as the plain `<model>_id:i32` column.
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.name, self.ty)?;
+        for rule in &self.rules {
+            write!(f, ":{}", rule)?;
+        }
+        if self.nullable {
+            write!(f, "?")?;
+        }
+        if let Some(default) = &self.default {
+            write!(f, "={}", default)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Field> for String {
+    fn from(field: Field) -> Self {
+        field.to_string()
+    }
+}
+
+impl TryFrom<String> for Field {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// `use entity::<model>::{...};` for the enum types among `fields`.
+pub(in crate::g) fn enum_imports(model: &str, fields: &[Field]) -> TokenStream {
+    let model = format_ident!("{}", model);
+    let enums = fields
+        .iter()
+        .filter_map(|f| f.enum_ident())
+        .collect::<Vec<_>>();
+
+    if enums.is_empty() {
+        quote!()
+    } else {
+        quote!(use entity::#model::{#(#enums),*};)
+    }
+}
+
+/// `use sea_orm::prelude::{...};` for the non-primitive types among `fields`.
+pub(in crate::g) fn prelude_imports(fields: &[Field]) -> TokenStream {
+    let mut imports = fields
+        .iter()
+        .filter_map(|f| f.ty.prelude_import())
+        .collect::<Vec<_>>();
+    imports.sort();
+    imports.dedup();
+
+    if imports.is_empty() {
+        quote!()
+    } else {
+        quote!(use sea_orm::prelude::{#(#imports),*};)
+    }
+}
@@ -1,12 +1,19 @@
-use crate::g::emit_generated_code;
+use crate::g::field::{enum_imports, prelude_imports};
+use crate::g::graphql::subscription::event_idents;
+use crate::g::{emit_generated_code, Field, FieldType, ModelOptions, PrimaryKey, Timestamps};
 use crate::{g::to_upper_camel, style_print::*};
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
 use std::path::Path;
 use std::str;
 
-pub(super) fn create_mutation(model: &str, mutation_dir: &Path) {
-    let file_content_tokens = create_mutation_tokens(model);
+pub(super) fn create_mutation(
+    model: &str,
+    fields: &[Field],
+    options: ModelOptions,
+    mutation_dir: &Path,
+) {
+    let file_content_tokens = create_mutation_tokens(model, fields, options);
 
     let file_path =
         emit_generated_code(mutation_dir, &format!("{}.rs", model), &file_content_tokens);
@@ -18,26 +25,148 @@ pub(super) fn create_mutation(model: &str, mutation_dir: &Path) {
     ))
 }
 
-fn create_mutation_tokens(model: &str) -> TokenStream {
+fn create_mutation_tokens(model: &str, fields: &[Field], options: ModelOptions) -> TokenStream {
     let model_name = format_ident!("{}", model);
     let create_model_input = format_ident!("Create{}Input", to_upper_camel(model));
+    let update_model_input = format_ident!("Update{}Input", to_upper_camel(model));
     let mutation_struct = format_ident!("{}Mutation", to_upper_camel(model));
     let create_function = format_ident!("create_{}", model);
     let update_function = format_ident!("update_{}", model);
     let delete_function = format_ident!("delete_{}", model);
+    let pk = options.pk.rust_type();
+    let column = |name: &str, ty| Field {
+        name: name.to_string(),
+        ty,
+        nullable: false,
+        default: None,
+        references: None,
+        rules: Vec::new(),
+    };
+    let mut columns = vec![column("id", options.pk.field_type())];
+    columns.extend_from_slice(fields);
+    // `delete_<model>` names the type to convert the current time to.
+    if options.soft_delete && options.timestamps == Timestamps::Tz {
+        columns.push(column("deleted_at", FieldType::DateTimeTz));
+    }
+    let prelude_imports = prelude_imports(&columns);
+    // Uuid keys are generated by the entity, integer ones are given.
+    let (id_input_field, set_id) = match options.pk {
+        PrimaryKey::Uuid => (quote!(), quote!()),
+        PrimaryKey::I32 | PrimaryKey::I64 => (quote!(pub id: #pk,), quote!(id: Set(input.id),)),
+    };
+    let set_import = (options.pk != PrimaryKey::Uuid || options.soft_delete || !fields.is_empty())
+        .then(|| quote!(, Set));
+    let enum_imports = enum_imports(model, fields);
+    let input_fields = fields.iter().map(|f| {
+        let name = f.ident();
+        let ty = f.input_type();
+        let validator = f.validator_attr();
+        quote! {
+            #validator
+            pub #name: #ty,
+        }
+    });
+    let set_fields = fields.iter().map(|f| {
+        let name = f.ident();
+        let value = f.set_from_input();
+        quote!(#name: #value,)
+    });
+    let update_input_fields = fields.iter().map(|f| {
+        let name = f.ident();
+        let ty = f.update_input_type();
+        let validator = f.validator_attr();
+        quote! {
+            #validator
+            pub #name: #ty,
+        }
+    });
+    let update_fields = fields.iter().map(|f| f.update_from_input(&model_name));
+    // GraphQL input objects need at least one field.
+    let (update_input, update_arg) = if fields.is_empty() {
+        (quote!(), quote!())
+    } else {
+        (
+            quote! {
+                #[derive(InputObject)]
+                pub struct #update_model_input {
+                    #(#update_input_fields)*
+                }
+            },
+            quote!(input: #update_model_input,),
+        )
+    };
+    // A model with a uuid key and no fields is created without input.
+    let (create_input, create_arg) = if options.pk == PrimaryKey::Uuid && fields.is_empty() {
+        (quote!(), quote!())
+    } else {
+        (
+            quote! {
+                #[derive(InputObject)]
+                pub struct #create_model_input {
+                    #id_input_field
+                    #(#input_fields)*
+                }
+            },
+            quote!(input: #create_model_input,),
+        )
+    };
+    let maybe_undefined = fields
+        .iter()
+        .any(|f| f.nullable)
+        .then(|| quote!(, MaybeUndefined));
+    let async_graphql_import = if create_input.is_empty() {
+        quote!(
+            use entity::async_graphql;
+        )
+    } else {
+        quote!(use entity::async_graphql::{{self, InputObject #maybe_undefined}};)
+    };
+    let not_found = syn::LitStr::new(
+        &format!("{} {{}} not found", model),
+        proc_macro2::Span::call_site(),
+    );
+    let events = options.subscriptions.then(|| Events::new(model));
+    let (chrono_import, delete_functions) = if options.soft_delete {
+        (
+            quote!(
+                use chrono::Utc;
+            ),
+            soft_delete_functions_tokens(model, &pk, options.timestamps, events.as_ref()),
+        )
+    } else {
+        (
+            quote!(),
+            delete_function_tokens(&delete_function, &model_name, &pk, events.as_ref()),
+        )
+    };
+    let event_imports = events.as_ref().map(Events::imports);
+    let create_return = saved_model_tokens(
+        &model_name,
+        quote!(insert),
+        events.as_ref().map(|e| &e.created),
+    );
+    let publish_updated = events
+        .as_ref()
+        .map(|e| e.publish(&e.updated, quote!(#model_name.clone())));
 
     quote! {
-        use async_graphql::{Context, Object, Result, Error};
-        use entity::async_graphql::{{self, InputObject}};
+        use async_graphql::{Context, Object, Result, Error, ErrorExtensions};
+        #async_graphql_import
         use entity::#model_name;
-        use chrono::Utc;
-        use sea_orm::{ActiveModelTrait, Set};
+        #chrono_import
+        use sea_orm::{ActiveModelTrait #set_import};
+        #prelude_imports
+        #enum_imports
         use crate::graphql::mutation::common::*;
+        #event_imports
         use crate::db::Database;
 
-        #[derive(InputObject)]
-        pub struct #create_model_input {
-            pub id: i32,
+        #create_input
+
+        #update_input
+
+        fn not_found(id: #pk) -> Error {
+            Error::new(format!(#not_found, id)).extend_with(|_, e| e.set("code", "NOT_FOUND"))
         }
 
         #[derive(Default)]
@@ -48,58 +177,181 @@ fn create_mutation_tokens(model: &str) -> TokenStream {
             pub async fn #create_function(
                 &self,
                 ctx: &Context<'_>,
-                input: #create_model_input,
+                #create_arg
             ) -> Result<#model_name::Model> {
                 let db = ctx.data::<Database>().unwrap();
-                let naive_date_time = Utc::now().naive_utc();
 
                 // Define schema here
                 let #model_name = #model_name::ActiveModel {
-                    id: Set(input.id),
-                    created_at: Set(naive_date_time),
-                    updated_at: Set(naive_date_time),
+                    #set_id
+                    #(#set_fields)*
                     ..Default::default()
                 };
 
-                Ok(#model_name.insert(db.get_connection()).await?)
+                #create_return
             }
 
             pub async fn #update_function(
                 &self,
                 ctx: &Context<'_>,
-                id: i32,
+                id: #pk,
+                #update_arg
             ) -> Result<#model_name::Model, Error> {
                 let db = ctx.data::<Database>().unwrap();
-                let naive_date_time = Utc::now().naive_utc();
-                let #model_name: Option<#model_name::Model> =
-                    #model_name::Entity::find_by_id(id)
-                        .one(db.get_connection())
-                        .await?;
-                let mut #model_name: #model_name::ActiveModel = #model_name.unwrap().into();
-                #model_name.updated_at = Set(naive_date_time);
+                let #model_name: #model_name::Model = #model_name::Entity::find_by_id(id)
+                    .one(db.get_connection())
+                    .await?
+                    .ok_or_else(|| not_found(id))?;
+                let mut #model_name: #model_name::ActiveModel = #model_name.into();
+                #(#update_fields)*
                 let #model_name: #model_name::Model =
                     #model_name.update(db.get_connection()).await?;
+                #publish_updated
 
                 Ok(#model_name)
             }
 
-            pub async fn #delete_function(&self, ctx: &Context<'_>, id: i32) -> Result<DeleteResult> {
-                let db = ctx.data::<Database>().unwrap();
+            #delete_functions
+        }
 
-                let res = #model_name::Entity::delete_by_id(id)
-                    .exec(db.get_connection())
-                    .await?;
-
-                if res.rows_affected <= 1 {
-                    Ok(DeleteResult {
-                        success: true,
-                        rows_affected: res.rows_affected,
-                    })
-                } else {
-                    unimplemented!()
-                }
+    }
+}
+
+/// The `<Model>Created`, `<Model>Updated` and `<Model>Deleted` events the
+/// mutations publish for the subscriptions of the model.
+struct Events {
+    model: Ident,
+    created: Ident,
+    updated: Ident,
+    deleted: Ident,
+}
+
+impl Events {
+    fn new(model: &str) -> Self {
+        let [created, updated, deleted] = event_idents(model);
+        Events {
+            model: format_ident!("{}", model),
+            created,
+            updated,
+            deleted,
+        }
+    }
+
+    fn imports(&self) -> TokenStream {
+        let Events {
+            model,
+            created,
+            updated,
+            deleted,
+        } = self;
+        quote! {
+            use crate::graphql::subscription::broker::SimpleBroker;
+            use crate::graphql::subscription::#model::{#created, #updated, #deleted};
+        }
+    }
+
+    /// Statement publishing `event` carrying `value`.
+    fn publish(&self, event: &Ident, value: TokenStream) -> TokenStream {
+        quote!(SimpleBroker::publish(#event(#value));)
+    }
+}
+
+/// `Ok` of the model the `ActiveModel` `method` saves, published as `event`
+/// first if there is one.
+fn saved_model_tokens(
+    model_name: &Ident,
+    method: TokenStream,
+    event: Option<&Ident>,
+) -> TokenStream {
+    match event {
+        Some(event) => quote! {
+            let #model_name = #model_name.#method(db.get_connection()).await?;
+            SimpleBroker::publish(#event(#model_name.clone()));
+
+            Ok(#model_name)
+        },
+        None => quote!(Ok(#model_name.#method(db.get_connection()).await?)),
+    }
+}
+
+fn delete_function_tokens(
+    delete_function: &Ident,
+    model_name: &Ident,
+    pk: &TokenStream,
+    events: Option<&Events>,
+) -> TokenStream {
+    let publish_deleted = events.map(|e| e.publish(&e.deleted, quote!(id)));
+
+    quote! {
+        pub async fn #delete_function(&self, ctx: &Context<'_>, id: #pk) -> Result<DeleteResult> {
+            let db = ctx.data::<Database>().unwrap();
+
+            let res = #model_name::Entity::delete_by_id(id)
+                .exec(db.get_connection())
+                .await?;
+
+            if res.rows_affected == 0 {
+                return Err(not_found(id));
             }
+            #publish_deleted
+
+            Ok(DeleteResult {
+                success: true,
+                rows_affected: res.rows_affected,
+            })
+        }
+    }
+}
+
+/// `delete_<model>` setting `deleted_at`, `restore_<model>` clearing it and
+/// `hard_delete_<model>` removing the row.
+fn soft_delete_functions_tokens(
+    model: &str,
+    pk: &TokenStream,
+    timestamps: Timestamps,
+    events: Option<&Events>,
+) -> TokenStream {
+    let model_name = format_ident!("{}", model);
+    let delete_function = format_ident!("delete_{}", model);
+    let restore_function = format_ident!("restore_{}", model);
+    let hard_delete_function = format_ident!("hard_delete_{}", model);
+    let hard_delete = delete_function_tokens(&hard_delete_function, &model_name, pk, events);
+    let now = timestamps.now();
+    let publish_deleted = events.map(|e| e.publish(&e.deleted, quote!(id)));
+    // A restored row shows up again as an update.
+    let restore_return =
+        saved_model_tokens(&model_name, quote!(update), events.map(|e| &e.updated));
+
+    quote! {
+        pub async fn #delete_function(&self, ctx: &Context<'_>, id: #pk) -> Result<DeleteResult> {
+            let db = ctx.data::<Database>().unwrap();
+            let #model_name: #model_name::Model = #model_name::Entity::find_by_id(id)
+                .one(db.get_connection())
+                .await?
+                .ok_or_else(|| not_found(id))?;
+            let mut #model_name: #model_name::ActiveModel = #model_name.into();
+            #model_name.deleted_at = Set(Some(#now));
+            #model_name.update(db.get_connection()).await?;
+            #publish_deleted
+
+            Ok(DeleteResult {
+                success: true,
+                rows_affected: 1,
+            })
+        }
+
+        pub async fn #restore_function(&self, ctx: &Context<'_>, id: #pk) -> Result<#model_name::Model> {
+            let db = ctx.data::<Database>().unwrap();
+            let #model_name: #model_name::Model = #model_name::Entity::find_deleted_by_id(id)
+                .one(db.get_connection())
+                .await?
+                .ok_or_else(|| not_found(id))?;
+            let mut #model_name: #model_name::ActiveModel = #model_name.into();
+            #model_name.deleted_at = Set(None);
+
+            #restore_return
         }
 
+        #hard_delete
     }
 }
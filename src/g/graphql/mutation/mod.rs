@@ -1,18 +1,40 @@
 use crate::g::graphql::mutation::{creation::create_mutation, registration::register_mutation};
+use crate::g::{remove_generated_code, Field, ModelOptions};
 use crate::style_print::log_error;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 mod creation;
 mod registration;
 
-pub(in crate::g) fn process_graphql_mutation(model: &str, gen_path: &Path) {
+pub(in crate::g) fn mutation_file(model: &str, gen_path: &Path) -> PathBuf {
+    gen_path
+        .join("src")
+        .join("graphql")
+        .join("mutation")
+        .join(format!("{}.rs", model))
+}
+
+pub(in crate::g) fn process_graphql_mutation(
+    model: &str,
+    fields: &[Field],
+    options: ModelOptions,
+    gen_path: &Path,
+) {
     let mutation_dir = gen_path.join("src").join("graphql").join("mutation");
 
     fs::create_dir_all(mutation_dir.as_path()).unwrap_or_else(|why| {
         log_error(&format!("! {:?}", why.kind()));
     });
 
-    create_mutation(model, &mutation_dir);
+    create_mutation(model, fields, options, &mutation_dir);
     register_mutation(model, &mutation_dir);
 }
+
+pub(in crate::g) fn destroy_graphql_mutation(model: &str, gen_path: &Path) {
+    let mutation_dir = gen_path.join("src").join("graphql").join("mutation");
+
+    if remove_generated_code(&mutation_dir, &format!("{}.rs", model)) {
+        register_mutation(model, &mutation_dir);
+    }
+}
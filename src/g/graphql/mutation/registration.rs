@@ -1,13 +1,20 @@
-use crate::g::{emit_generated_code, read_dir, to_upper_camel};
-use crate::style_print::log_success;
-use proc_macro2::TokenStream;
+use crate::g::registration::Registration;
+use crate::g::{format_generated_code, read_dir, to_upper_camel, write_generated_code};
+use crate::style_print::{log_error, log_success};
+use proc_macro2::Ident;
 use quote::{format_ident, quote};
 use std::path::Path;
 
 pub(super) fn register_mutation(model: &str, mutation_dir: &Path) {
-    let file_content_tokens = register_mutation_tokens(&mutation_dir);
+    let file_content = match register_mutation_content(mutation_dir) {
+        Ok(file_content) => file_content,
+        Err(why) => {
+            log_error(&why);
+            return;
+        }
+    };
 
-    let file_path = emit_generated_code(mutation_dir, "mod.rs", &file_content_tokens);
+    let file_path = write_generated_code(mutation_dir, "mod.rs", &file_content);
 
     log_success(&format!(
         "Successfully registered GraphQL mutation for `{}` in {}",
@@ -16,17 +23,30 @@ pub(super) fn register_mutation(model: &str, mutation_dir: &Path) {
     ))
 }
 
-fn register_mutation_tokens(mutation_dir: &Path) -> TokenStream {
+fn register_mutation_content(mutation_dir: &Path) -> Result<String, String> {
     let mutation_files = read_dir(mutation_dir).unwrap();
     let mut mutation_box = mutation_files
         .iter()
-        .cloned()
-        .filter(|i| i != "mod.rs")
-        .filter(|i| i != "common.rs")
+        .filter(|&i| i != "mod.rs")
+        .filter(|&i| i != "common.rs")
         .map(|i| i.replace(".rs", ""))
         .collect::<Vec<_>>();
     mutation_box.sort();
 
+    if let Some(mut registration) = Registration::parse(mutation_dir, "mod.rs")? {
+        let modules = [vec!["common".to_string()], mutation_box].concat();
+        let (added, stale) = registration.sync_mods(mutation_dir, &modules, true);
+        let uses = added
+            .iter()
+            .filter(|&m| m != "common")
+            .map(|m| (m.clone(), mutation_member(m)))
+            .collect::<Vec<_>>();
+        let stale_members = stale.iter().map(|m| mutation_member(m)).collect::<Vec<_>>();
+        registration.sync_uses(&uses, &stale);
+        registration.sync_struct_members("Mutation", &uses, &stale_members);
+        return Ok(registration.into_source());
+    }
+
     let modules = mutation_box
         .iter()
         .map(|i| format_ident!("{}", i))
@@ -34,18 +54,22 @@ fn register_mutation_tokens(mutation_dir: &Path) -> TokenStream {
 
     let members = mutation_box
         .iter()
-        .map(|s| (format_ident!("{}Mutation", to_upper_camel(s))))
+        .map(|s| mutation_member(s))
         .collect::<Vec<_>>();
 
-    quote! {
+    Ok(format_generated_code(&quote! {
         use entity::async_graphql;
 
         pub mod common;
-        #(pub mod #modules)*;
-        #(pub use #modules::#members)*;
+        #(pub mod #modules;)*
+        #(pub use #modules::#members;)*
 
         #[derive(async_graphql::MergedObject, Default)]
         pub struct Mutation(#(#members),*);
 
-    }
+    }))
+}
+
+fn mutation_member(module: &str) -> Ident {
+    format_ident!("{}Mutation", to_upper_camel(module))
 }
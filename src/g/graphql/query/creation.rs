@@ -1,11 +1,16 @@
-use crate::g::{emit_generated_code, to_upper_camel};
-use crate::style_print::log_success;
-use proc_macro2::TokenStream;
+use crate::g::field::enum_imports;
+use crate::g::{
+    emit_generated_code, format_generated_code, to_upper_camel, write_generated_code, Field,
+    FieldType, ModelOptions, PrimaryKey,
+};
+use crate::style_print::{log_error, log_success};
+use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
+use std::fs;
 use std::path::Path;
 
-pub(super) fn create_query(model: &str, query_dir: &Path) {
-    let file_content_tokens = create_query_tokens(model);
+pub(super) fn create_query(model: &str, fields: &[Field], options: ModelOptions, query_dir: &Path) {
+    let file_content_tokens = create_query_tokens(model, fields, options);
 
     let file_path = emit_generated_code(query_dir, &format!("{}.rs", model), &file_content_tokens);
 
@@ -16,33 +21,596 @@ pub(super) fn create_query(model: &str, query_dir: &Path) {
     ));
 }
 
-fn create_query_tokens(model_str: &str) -> TokenStream {
+pub(super) fn create_filter(query_dir: &Path) {
+    let file_content_tokens = create_filter_tokens();
+
+    let file_path = emit_generated_code(query_dir, "filter.rs", &file_content_tokens);
+
+    log_success(&format!(
+        "Successfully created GraphQL filter file: {}",
+        file_path.display()
+    ));
+}
+
+/// `left_pk` and `right_pk` are the primary keys of the joined models.
+pub(super) fn create_join_query(
+    left: &str,
+    right: &str,
+    left_pk: PrimaryKey,
+    right_pk: PrimaryKey,
+    query_dir: &Path,
+) {
+    let join = format!("{}_{}", left, right);
+    let file_content_tokens = create_join_query_tokens(&join, left, right, [left_pk, right_pk]);
+
+    let file_path = emit_generated_code(query_dir, &format!("{}.rs", join), &file_content_tokens);
+
+    log_success(&format!(
+        "Successfully created `{}` GraphQL query file: {}",
+        join,
+        file_path.display()
+    ));
+}
+
+/// Adds the `UuidFilter` to a `filter.rs` generated before it was there.
+pub(super) fn add_uuid_filter(query_dir: &Path) {
+    let file_path = query_dir.join("filter.rs");
+    let content = match fs::read_to_string(&file_path) {
+        Ok(content) => content,
+        Err(why) => {
+            log_error(&format!("! {:?}", why.kind()));
+            return;
+        }
+    };
+    let uuid_filter = uuid_filter_tokens();
+    let addition = format_generated_code(&quote! {
+        use sea_orm::prelude::Uuid;
+
+        #uuid_filter
+    });
+    write_generated_code(query_dir, "filter.rs", &(content + &addition));
+
+    log_success(&format!(
+        "Successfully added `UuidFilter` to GraphQL filter file: {}",
+        file_path.display()
+    ));
+}
+
+/// Filter of `uuid` columns, which only compare for equality.
+fn uuid_filter_tokens() -> TokenStream {
+    quote! {
+        #[derive(InputObject)]
+        pub struct UuidFilter {
+            pub eq: Option<Uuid>,
+            pub ne: Option<Uuid>,
+            #[graphql(name = "in")]
+            pub is_in: Option<Vec<Uuid>>,
+            pub is_null: Option<bool>,
+        }
+
+        impl UuidFilter {
+            pub fn condition(self, column: impl ColumnTrait) -> Condition {
+                let mut condition = Condition::all();
+                if let Some(eq) = self.eq {
+                    condition = condition.add(column.eq(eq));
+                }
+                if let Some(ne) = self.ne {
+                    condition = condition.add(column.ne(ne));
+                }
+                if let Some(is_in) = self.is_in {
+                    condition = condition.add(column.is_in(is_in));
+                }
+                if let Some(is_null) = self.is_null {
+                    condition = condition.add(if is_null { column.is_null() } else { column.is_not_null() });
+                }
+                condition
+            }
+        }
+    }
+}
+
+/// Filter inputs shared by the list queries of every model.
+fn create_filter_tokens() -> TokenStream {
+    let uuid_filter = uuid_filter_tokens();
+
+    quote! {
+        use async_graphql::{InputObject, InputType};
+        use entity::async_graphql;
+        use sea_orm::prelude::{Date, DateTime, DateTimeWithTimeZone, Uuid};
+        use sea_orm::{ColumnTrait, Condition, Value};
+
+        #[derive(InputObject)]
+        #[graphql(concrete(name = "IntFilter", params(i32)))]
+        #[graphql(concrete(name = "BigIntFilter", params(i64)))]
+        #[graphql(concrete(name = "Float32Filter", params(f32)))]
+        #[graphql(concrete(name = "FloatFilter", params(f64)))]
+        #[graphql(concrete(name = "BooleanFilter", params(bool)))]
+        #[graphql(concrete(name = "DateFilter", params(Date)))]
+        #[graphql(concrete(name = "DateTimeFilter", params(DateTime)))]
+        #[graphql(concrete(name = "DateTimeTzFilter", params(DateTimeWithTimeZone)))]
+        pub struct Filter<T: InputType> {
+            pub eq: Option<T>,
+            pub ne: Option<T>,
+            #[graphql(name = "in")]
+            pub is_in: Option<Vec<T>>,
+            pub gt: Option<T>,
+            pub lt: Option<T>,
+            pub is_null: Option<bool>,
+        }
+
+        impl<T: InputType + Into<Value>> Filter<T> {
+            pub fn condition(self, column: impl ColumnTrait) -> Condition {
+                let mut condition = Condition::all();
+                if let Some(eq) = self.eq {
+                    condition = condition.add(column.eq(eq));
+                }
+                if let Some(ne) = self.ne {
+                    condition = condition.add(column.ne(ne));
+                }
+                if let Some(is_in) = self.is_in {
+                    condition = condition.add(column.is_in(is_in));
+                }
+                if let Some(gt) = self.gt {
+                    condition = condition.add(column.gt(gt));
+                }
+                if let Some(lt) = self.lt {
+                    condition = condition.add(column.lt(lt));
+                }
+                if let Some(is_null) = self.is_null {
+                    condition = condition.add(if is_null { column.is_null() } else { column.is_not_null() });
+                }
+                condition
+            }
+        }
+
+        #[derive(InputObject)]
+        pub struct StringFilter {
+            pub eq: Option<String>,
+            pub ne: Option<String>,
+            #[graphql(name = "in")]
+            pub is_in: Option<Vec<String>>,
+            pub contains: Option<String>,
+            pub gt: Option<String>,
+            pub lt: Option<String>,
+            pub is_null: Option<bool>,
+        }
+
+        impl StringFilter {
+            pub fn condition(self, column: impl ColumnTrait) -> Condition {
+                let mut condition = Condition::all();
+                if let Some(eq) = self.eq {
+                    condition = condition.add(column.eq(eq));
+                }
+                if let Some(ne) = self.ne {
+                    condition = condition.add(column.ne(ne));
+                }
+                if let Some(is_in) = self.is_in {
+                    condition = condition.add(column.is_in(is_in));
+                }
+                if let Some(contains) = self.contains {
+                    condition = condition.add(column.contains(&contains));
+                }
+                if let Some(gt) = self.gt {
+                    condition = condition.add(column.gt(gt));
+                }
+                if let Some(lt) = self.lt {
+                    condition = condition.add(column.lt(lt));
+                }
+                if let Some(is_null) = self.is_null {
+                    condition = condition.add(if is_null { column.is_null() } else { column.is_not_null() });
+                }
+                condition
+            }
+        }
+
+        #uuid_filter
+    }
+}
+
+/// `id`, the model's fields and the timestamps, every column of the table.
+pub(super) fn table_columns(options: ModelOptions, fields: &[Field]) -> Vec<Field> {
+    let column = |name: &str, ty| Field {
+        name: name.to_string(),
+        ty,
+        nullable: false,
+        default: None,
+        references: None,
+        rules: Vec::new(),
+    };
+    let mut columns = vec![column("id", options.pk.field_type())];
+    columns.extend_from_slice(fields);
+    if let Some(ty) = options.timestamps.field_type() {
+        columns.push(column("created_at", ty.clone()));
+        columns.push(column("updated_at", ty));
+    }
+    columns
+}
+
+/// Input filtering `field`, `json` columns cannot be filtered on.
+fn filter_type(model: &str, field: &Field) -> Option<TokenStream> {
+    let ty = match field.ty {
+        FieldType::String | FieldType::Text => return Some(quote!(StringFilter)),
+        FieldType::Uuid => return Some(quote!(UuidFilter)),
+        FieldType::Enum(_) => {
+            let filter = enum_filter(model, field);
+            return Some(quote!(#filter));
+        }
+        FieldType::I32 => quote!(i32),
+        FieldType::I64 => quote!(i64),
+        FieldType::F32 => quote!(f32),
+        FieldType::F64 => quote!(f64),
+        FieldType::Bool => quote!(bool),
+        FieldType::Date => quote!(Date),
+        FieldType::DateTime => quote!(DateTime),
+        FieldType::DateTimeTz => quote!(DateTimeWithTimeZone),
+        FieldType::Json => return None,
+    };
+    Some(quote!(Filter<#ty>))
+}
+
+fn enum_filter(model: &str, field: &Field) -> Ident {
+    format_ident!("{}Filter", to_upper_camel(&field.enum_name(model)))
+}
+
+/// Filter of an enum column, the shared `Filter` cannot be made concrete for
+/// enums of the app.
+fn enum_filter_tokens(model_str: &str, field: &Field) -> TokenStream {
+    let model = format_ident!("{}", model_str);
+    let filter = enum_filter(model_str, field);
+    let ty = field.enum_ident().unwrap();
+
+    quote! {
+        #[derive(InputObject)]
+        pub struct #filter {
+            pub eq: Option<#ty>,
+            pub ne: Option<#ty>,
+            #[graphql(name = "in")]
+            pub is_in: Option<Vec<#ty>>,
+            pub is_null: Option<bool>,
+        }
+
+        impl #filter {
+            pub fn condition(self, column: #model::Column) -> Condition {
+                let mut condition = Condition::all();
+                if let Some(eq) = self.eq {
+                    condition = condition.add(column.eq(eq));
+                }
+                if let Some(ne) = self.ne {
+                    condition = condition.add(column.ne(ne));
+                }
+                if let Some(is_in) = self.is_in {
+                    condition = condition.add(column.is_in(is_in));
+                }
+                if let Some(is_null) = self.is_null {
+                    condition = condition.add(if is_null { column.is_null() } else { column.is_not_null() });
+                }
+                condition
+            }
+        }
+    }
+}
+
+fn create_query_tokens(model_str: &str, fields: &[Field], options: ModelOptions) -> TokenStream {
     let model = format_ident!("{}", model_str);
+    let models = format_ident!("{}s", model_str);
     let model_query = format_ident!("{}Query", to_upper_camel(model_str));
-    let get_models = format_ident!("get_{}", model);
+    let connection_fields = format_ident!("{}ConnectionFields", to_upper_camel(model_str));
+    let model_filter = format_ident!("{}Filter", to_upper_camel(model_str));
+    let model_order_by = format_ident!("{}OrderBy", to_upper_camel(model_str));
+    let get_models = format_ident!("get_{}s", model);
     let get_by_id = format_ident!("get_{}_by_id", model);
+    let enum_imports = enum_imports(model_str, fields);
+
+    let pk = options.pk.rust_type();
+    let columns = table_columns(options, fields);
+    let filtered = columns
+        .iter()
+        .filter_map(|f| Some((f, filter_type(model_str, f)?)))
+        .collect::<Vec<_>>();
+    let filter_fields = filtered.iter().map(|(f, ty)| {
+        let name = f.ident();
+        quote!(pub #name: Option<#ty>,)
+    });
+    let filter_conditions = filtered.iter().map(|(f, _)| {
+        let name = f.ident();
+        let column = format_ident!("{}", to_upper_camel(&f.name));
+        quote! {
+            if let Some(#name) = self.#name {
+                condition = condition.add(#name.condition(#model::Column::#column));
+            }
+        }
+    });
+    let enum_filters = fields
+        .iter()
+        .filter(|f| f.enum_ident().is_some())
+        .map(|f| enum_filter_tokens(model_str, f));
+    let mut prelude_imports = Vec::new();
+    for ty in [FieldType::Date, FieldType::DateTime, FieldType::DateTimeTz] {
+        if filtered.iter().any(|(f, _)| f.ty == ty) {
+            prelude_imports.extend(ty.prelude_import());
+        }
+    }
+    if options.pk == PrimaryKey::Uuid {
+        prelude_imports.push(format_ident!("Uuid"));
+    }
+    let prelude_imports = (!prelude_imports.is_empty())
+        .then(|| quote!(use sea_orm::prelude::{#(#prelude_imports),*};));
+    let mut filter_imports = Vec::new();
+    if filtered
+        .iter()
+        .any(|(f, _)| !f.ty.is_string() && f.ty != FieldType::Uuid && f.enum_ident().is_none())
+    {
+        filter_imports.push(format_ident!("Filter"));
+    }
+    if filtered.iter().any(|(f, _)| f.ty.is_string()) {
+        filter_imports.push(format_ident!("StringFilter"));
+    }
+    if filtered.iter().any(|(f, _)| f.ty == FieldType::Uuid) {
+        filter_imports.push(format_ident!("UuidFilter"));
+    }
+    // `find_by_id` of the entity leaves soft deleted rows out already.
+    let not_deleted = options
+        .soft_delete
+        .then(|| quote!(.filter(#model::Column::DeletedAt.is_null())));
+
+    let ordered = columns
+        .iter()
+        .filter(|f| f.ty != FieldType::Json)
+        .collect::<Vec<_>>();
+    let order_variants = ordered
+        .iter()
+        .flat_map(|f| {
+            let column = to_upper_camel(&f.name);
+            [
+                format_ident!("{}Asc", column),
+                format_ident!("{}Desc", column),
+            ]
+        })
+        .collect::<Vec<_>>();
+    let order_arms = ordered.iter().map(|f| {
+        let column = format_ident!("{}", to_upper_camel(&f.name));
+        let asc = format_ident!("{}Asc", column);
+        let desc = format_ident!("{}Desc", column);
+        quote! {
+            #model_order_by::#asc => (#model::Column::#column, Order::Asc),
+            #model_order_by::#desc => (#model::Column::#column, Order::Desc),
+        }
+    });
+    let anchor_arms = ordered.iter().map(|f| {
+        let name = f.ident();
+        let column = format_ident!("{}", to_upper_camel(&f.name));
+        let asc = format_ident!("{}Asc", column);
+        let desc = format_ident!("{}Desc", column);
+        let value = if f.ty.is_string() {
+            quote!(anchor.#name.clone())
+        } else {
+            quote!(anchor.#name)
+        };
+        let value = if f.nullable {
+            quote!(#value.map(Value::from))
+        } else {
+            quote!(Some(Value::from(#value)))
+        };
+        quote! {
+            #model_order_by::#asc | #model_order_by::#desc => #value,
+        }
+    });
 
     quote! {
-        use async_graphql::{Context, Object, Result};
+        use async_graphql::connection::{query, Connection, Edge};
+        use async_graphql::{Context, Enum, InputObject, Object, Result, SimpleObject};
         use entity::{async_graphql, #model};
-        use sea_orm::EntityTrait;
+        #enum_imports
+        #prelude_imports
+        use sea_orm::{
+            ColumnTrait, Condition, EntityTrait, Order, PaginatorTrait, QueryFilter, QueryOrder,
+            QuerySelect, Select, Value,
+        };
         use crate::db::Database;
+        use crate::graphql::query::filter::{#(#filter_imports),*};
+
+        /// Page size when neither `first` nor `last` is given.
+        const DEFAULT_PAGE_SIZE: usize = 20;
+
+        #[derive(SimpleObject)]
+        pub struct #connection_fields {
+            pub total_count: u64,
+        }
+
+        #[derive(InputObject)]
+        pub struct #model_filter {
+            #(#filter_fields)*
+            pub and: Option<Vec<#model_filter>>,
+            pub or: Option<Vec<#model_filter>>,
+        }
+
+        impl #model_filter {
+            pub fn condition(self) -> Condition {
+                let mut condition = Condition::all();
+                #(#filter_conditions)*
+                for filter in self.and.unwrap_or_default() {
+                    condition = condition.add(filter.condition());
+                }
+                if let Some(or) = self.or {
+                    let mut any = Condition::any();
+                    for filter in or {
+                        any = any.add(filter.condition());
+                    }
+                    condition = condition.add(any);
+                }
+                condition
+            }
+        }
+
+        #(#enum_filters)*
+
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Enum)]
+        pub enum #model_order_by {
+            #(#order_variants,)*
+        }
+
+        impl #model_order_by {
+            fn column(self) -> (#model::Column, Order) {
+                match self {
+                    #(#order_arms)*
+                }
+            }
+
+            /// Sorts by the column, ties by `id`, all reversed when paging
+            /// backwards with `last`.
+            pub fn apply(self, select: Select<#model::Entity>, backward: bool) -> Select<#model::Entity> {
+                let (column, order) = self.column();
+                let (order, id_order) = match (order, backward) {
+                    (order, false) => (order, Order::Asc),
+                    (Order::Asc, true) => (Order::Desc, Order::Desc),
+                    (_, true) => (Order::Asc, Order::Desc),
+                };
+                select.order_by(column, order).order_by(#model::Column::Id, id_order)
+            }
+
+            /// Rows after the `anchor` row of a cursor, or before it.
+            ///
+            /// Postgres sorts nulls above every value.
+            pub fn beyond(self, anchor: &#model::Model, before: bool) -> Condition {
+                let (column, order) = self.column();
+                let value = match self {
+                    #(#anchor_arms)*
+                };
+                let id = #model::Column::Id;
+                let tie = if before { id.lt(anchor.id) } else { id.gt(anchor.id) };
+                let larger = (order == Order::Desc) == before;
+                match value {
+                    Some(value) => {
+                        let past = if larger {
+                            column.gt(value.clone())
+                        } else {
+                            column.lt(value.clone())
+                        };
+                        let mut condition = Condition::any()
+                            .add(past)
+                            .add(Condition::all().add(column.eq(value)).add(tie));
+                        if larger {
+                            condition = condition.add(column.is_null());
+                        }
+                        condition
+                    }
+                    None => {
+                        let mut condition =
+                            Condition::any().add(Condition::all().add(column.is_null()).add(tie));
+                        if !larger {
+                            condition = condition.add(column.is_not_null());
+                        }
+                        condition
+                    }
+                }
+            }
+        }
 
         #[derive(Default)]
         pub struct #model_query;
 
         #[Object]
-        impl model_query {
-            async fn #get_models(&self, ctx: &Context<'_>) -> Result<Vec<#model::Model>> {
-                let db = ctx.data::<Database>().unwrap();
+        impl #model_query {
+            /// Cursors are the `id` of their row, so pages stay put when rows
+            /// are added or removed. Without `order_by` pages follow `id`.
+            #[allow(clippy::too_many_arguments)]
+            async fn #get_models(
+                &self,
+                ctx: &Context<'_>,
+                filter: Option<#model_filter>,
+                order_by: Option<#model_order_by>,
+                after: Option<String>,
+                before: Option<String>,
+                first: Option<i32>,
+                last: Option<i32>,
+            ) -> Result<Connection<String, #model::Model, #connection_fields>> {
+                let db = ctx.data::<Database>().unwrap().get_connection();
 
-                Ok(#model::Entity::find()
-                    .all(db.get_connection())
-                    .await
-                    .map_err(|e| e.to_string())?)
+                let mut select = #model::Entity::find() #not_deleted;
+                if let Some(filter) = filter {
+                    select = select.filter(filter.condition());
+                }
+
+                query(
+                    after,
+                    before,
+                    first,
+                    last,
+                    |after: Option<String>, before: Option<String>, first: Option<usize>, last: Option<usize>| async move {
+                        let total_count = select.clone().count(db).await?;
+                        let after = after.map(|after| after.parse::<#pk>()).transpose()?;
+                        let before = before.map(|before| before.parse::<#pk>()).transpose()?;
+                        let backward = first.is_none() && last.is_some();
+                        let limit = first.or(last).unwrap_or(DEFAULT_PAGE_SIZE);
+                        // One more row than asked for tells whether there is another page.
+                        let fetched = limit as u64 + 1;
+
+                        let mut #models = match order_by {
+                            None => {
+                                let mut cursor = select.cursor_by(#model::Column::Id);
+                                if let Some(after) = after {
+                                    cursor.after(after);
+                                }
+                                if let Some(before) = before {
+                                    cursor.before(before);
+                                }
+                                if backward {
+                                    cursor.last(fetched);
+                                } else {
+                                    cursor.first(fetched);
+                                }
+                                cursor.all(db).await?
+                            }
+                            Some(order_by) => {
+                                for (cursor, before) in [(after, false), (before, true)] {
+                                    if let Some(id) = cursor {
+                                        let anchor = #model::Entity::find_by_id(id)
+                                            .one(db)
+                                            .await?
+                                            .ok_or("the row of the cursor is gone")?;
+                                        select = select.filter(order_by.beyond(&anchor, before));
+                                    }
+                                }
+                                let mut #models = order_by
+                                    .apply(select, backward)
+                                    .limit(fetched)
+                                    .all(db)
+                                    .await?;
+                                if backward {
+                                    #models.reverse();
+                                }
+                                #models
+                            }
+                        };
+
+                        let has_more = #models.len() > limit;
+                        let (has_previous_page, has_next_page) = if backward {
+                            if has_more {
+                                #models.remove(0);
+                            }
+                            (has_more, before.is_some())
+                        } else {
+                            #models.truncate(limit);
+                            (after.is_some(), has_more)
+                        };
+
+                        let mut connection = Connection::with_additional_fields(
+                            has_previous_page,
+                            has_next_page,
+                            #connection_fields { total_count },
+                        );
+                        connection.edges.extend(
+                            #models
+                                .into_iter()
+                                .map(|#model| Edge::new(#model.id.to_string(), #model)),
+                        );
+                        Ok::<_, async_graphql::Error>(connection)
+                    },
+                )
+                .await
             }
 
-            async fn #get_by_id(&self, ctx: &Context<'_>, id: i32) -> Result<Option<#model::Model>> {
+            async fn #get_by_id(&self, ctx: &Context<'_>, id: #pk) -> Result<Option<#model::Model>> {
                 let db = ctx.data::<Database>().unwrap();
 
                 Ok(#model::Entity::find_by_id(id)
@@ -53,3 +621,67 @@ fn create_query_tokens(model_str: &str) -> TokenStream {
         }
     }
 }
+
+fn create_join_query_tokens(
+    join: &str,
+    left_str: &str,
+    right_str: &str,
+    [left_pk, right_pk]: [PrimaryKey; 2],
+) -> TokenStream {
+    let join_query = format_ident!("{}Query", to_upper_camel(join));
+    let left = format_ident!("{}", left_str);
+    let right = format_ident!("{}", right_str);
+    let left_id = format_ident!("{}_id", left_str);
+    let right_id = format_ident!("{}_id", right_str);
+    let get_left_rights = format_ident!("get_{}_{}s", left_str, right_str);
+    let get_right_lefts = format_ident!("get_{}_{}s", right_str, left_str);
+    let left_pk_type = left_pk.rust_type();
+    let right_pk_type = right_pk.rust_type();
+    let uuid_import = if left_pk == PrimaryKey::Uuid || right_pk == PrimaryKey::Uuid {
+        quote!(
+            use sea_orm::prelude::Uuid;
+        )
+    } else {
+        quote!()
+    };
+
+    quote! {
+        use async_graphql::{Context, Object, Result};
+        use entity::{async_graphql, #left, #right};
+        #uuid_import
+        use sea_orm::{EntityTrait, ModelTrait};
+        use crate::db::Database;
+
+        #[derive(Default)]
+        pub struct #join_query;
+
+        #[Object]
+        impl #join_query {
+            async fn #get_left_rights(&self, ctx: &Context<'_>, #left_id: #left_pk_type) -> Result<Vec<#right::Model>> {
+                let db = ctx.data::<Database>().unwrap();
+
+                match #left::Entity::find_by_id(#left_id).one(db.get_connection()).await? {
+                    Some(#left) => Ok(#left
+                        .find_related(#right::Entity)
+                        .all(db.get_connection())
+                        .await
+                        .map_err(|e| e.to_string())?),
+                    None => Ok(Vec::new()),
+                }
+            }
+
+            async fn #get_right_lefts(&self, ctx: &Context<'_>, #right_id: #right_pk_type) -> Result<Vec<#left::Model>> {
+                let db = ctx.data::<Database>().unwrap();
+
+                match #right::Entity::find_by_id(#right_id).one(db.get_connection()).await? {
+                    Some(#right) => Ok(#right
+                        .find_related(#left::Entity)
+                        .all(db.get_connection())
+                        .await
+                        .map_err(|e| e.to_string())?),
+                    None => Ok(Vec::new()),
+                }
+            }
+        }
+    }
+}
@@ -1,18 +1,74 @@
-use crate::g::graphql::query::{creation::create_query, registration::register_query};
+use crate::g::entity::primary_key;
+use crate::g::graphql::query::{
+    creation::{add_uuid_filter, create_filter, create_join_query, create_query, table_columns},
+    registration::register_query,
+};
+use crate::g::{remove_generated_code, Field, FieldType, ModelOptions};
 use crate::style_print::log_error;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 mod creation;
 mod registration;
 
-pub(in crate::g) fn process_graphql_query(model: &str, gen_path: &Path) {
+pub(in crate::g) fn query_file(model: &str, gen_path: &Path) -> PathBuf {
+    gen_path
+        .join("src")
+        .join("graphql")
+        .join("query")
+        .join(format!("{}.rs", model))
+}
+
+pub(in crate::g) fn process_graphql_query(
+    model: &str,
+    fields: &[Field],
+    options: ModelOptions,
+    gen_path: &Path,
+) {
     let query_dir = gen_path.join("src").join("graphql").join("query");
 
     fs::create_dir_all(query_dir.as_path()).unwrap_or_else(|why| {
         log_error(&format!("! {:?}", why.kind()));
     });
 
-    create_query(model, &query_dir);
+    // Shared by every model and left alone once it is there, but for the
+    // `UuidFilter` which older ones lack.
+    let filter_file = query_dir.join("filter.rs");
+    if !filter_file.is_file() {
+        create_filter(&query_dir);
+    } else if table_columns(options, fields)
+        .iter()
+        .any(|f| f.ty == FieldType::Uuid)
+        && !fs::read_to_string(&filter_file).is_ok_and(|content| content.contains("UuidFilter"))
+    {
+        add_uuid_filter(&query_dir);
+    }
+    create_query(model, fields, options, &query_dir);
     register_query(model, &query_dir);
 }
+
+pub(in crate::g) fn process_graphql_join_query(left: &str, right: &str, gen_path: &Path) {
+    let query_dir = gen_path.join("src").join("graphql").join("query");
+    let join = format!("{}_{}", left, right);
+
+    fs::create_dir_all(query_dir.as_path()).unwrap_or_else(|why| {
+        log_error(&format!("! {:?}", why.kind()));
+    });
+
+    create_join_query(
+        left,
+        right,
+        primary_key(left, gen_path),
+        primary_key(right, gen_path),
+        &query_dir,
+    );
+    register_query(&join, &query_dir);
+}
+
+pub(in crate::g) fn destroy_graphql_query(model: &str, gen_path: &Path) {
+    let query_dir = gen_path.join("src").join("graphql").join("query");
+
+    if remove_generated_code(&query_dir, &format!("{}.rs", model)) {
+        register_query(model, &query_dir);
+    }
+}
@@ -1,13 +1,20 @@
-use crate::g::{emit_generated_code, read_dir, to_upper_camel};
-use crate::style_print::log_success;
-use proc_macro2::TokenStream;
+use crate::g::registration::Registration;
+use crate::g::{format_generated_code, read_dir, to_upper_camel, write_generated_code};
+use crate::style_print::{log_error, log_success};
+use proc_macro2::Ident;
 use quote::{format_ident, quote};
 use std::path::Path;
 
 pub(super) fn register_query(model: &str, query_dir: &Path) {
-    let file_content_tokens = register_query_tokens(query_dir);
+    let file_content = match register_query_content(query_dir) {
+        Ok(file_content) => file_content,
+        Err(why) => {
+            log_error(&why);
+            return;
+        }
+    };
 
-    let file_path = emit_generated_code(query_dir, "mod.rs", &file_content_tokens);
+    let file_path = write_generated_code(query_dir, "mod.rs", &file_content);
 
     log_success(&format!(
         "Successfully registered GraphQL query for `{}` in {}",
@@ -16,16 +23,40 @@ pub(super) fn register_query(model: &str, query_dir: &Path) {
     ));
 }
 
-fn register_query_tokens(query_dir: &Path) -> TokenStream {
+fn register_query_content(query_dir: &Path) -> Result<String, String> {
     let files = read_dir(query_dir).unwrap();
     let mut query_box = files
         .iter()
-        .cloned()
-        .filter(|i| i != "mod.rs")
+        .filter(|&i| i != "mod.rs")
+        .filter(|&i| i != "filter.rs")
         .map(|i| i.replace(".rs", ""))
         .collect::<Vec<_>>();
     query_box.sort();
+    // The shared filter inputs, declared first like the mutations' `common`.
+    let filter_box = files
+        .iter()
+        .filter(|&i| i == "filter.rs")
+        .map(|_| "filter".to_string())
+        .collect::<Vec<_>>();
 
+    if let Some(mut registration) = Registration::parse(query_dir, "mod.rs")? {
+        let modules = [filter_box, query_box].concat();
+        let (added, stale) = registration.sync_mods(query_dir, &modules, true);
+        let uses = added
+            .iter()
+            .filter(|&m| m != "filter")
+            .map(|m| (m.clone(), query_member(m)))
+            .collect::<Vec<_>>();
+        let stale_members = stale.iter().map(|m| query_member(m)).collect::<Vec<_>>();
+        registration.sync_uses(&uses, &stale);
+        registration.sync_struct_members("Query", &uses, &stale_members);
+        return Ok(registration.into_source());
+    }
+
+    let filter_modules = filter_box
+        .iter()
+        .map(|i| format_ident!("{}", i))
+        .collect::<Vec<_>>();
     let modules = query_box
         .iter()
         .map(|i| format_ident!("{}", i))
@@ -33,19 +64,21 @@ fn register_query_tokens(query_dir: &Path) -> TokenStream {
 
     let members = query_box
         .iter()
-        .map(|s| {
-            let name = to_upper_camel(s);
-            format_ident!("{}Query", name)
-        })
+        .map(|s| query_member(s))
         .collect::<Vec<_>>();
 
-    quote! {
+    Ok(format_generated_code(&quote! {
         use entity::async_graphql;
 
-        #(pub mod #modules)*;
-        #(pub use #modules::#members)*;
+        #(pub mod #filter_modules;)*
+        #(pub mod #modules;)*
+        #(pub use #modules::#members;)*
 
         #[derive(async_graphql::MergedObject, Default)]
         pub struct Query(#(#members),*);
-    }
+    }))
+}
+
+fn query_member(module: &str) -> Ident {
+    format_ident!("{}Query", to_upper_camel(module))
 }
@@ -0,0 +1,138 @@
+use crate::g::{emit_generated_code, to_upper_camel, PrimaryKey};
+use crate::style_print::log_success;
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use std::path::Path;
+
+pub(super) fn create_subscription(model: &str, pk: PrimaryKey, subscription_dir: &Path) {
+    let file_content_tokens = create_subscription_tokens(model, pk);
+
+    let file_path = emit_generated_code(
+        subscription_dir,
+        &format!("{}.rs", model),
+        &file_content_tokens,
+    );
+
+    log_success(&format!(
+        "Successfully created `{}` GraphQL subscription file: {}",
+        model,
+        file_path.display()
+    ));
+}
+
+pub(super) fn create_broker(subscription_dir: &Path) {
+    let file_content_tokens = create_broker_tokens();
+
+    let file_path = emit_generated_code(subscription_dir, "broker.rs", &file_content_tokens);
+
+    log_success(&format!(
+        "Successfully created GraphQL subscription broker file: {}",
+        file_path.display()
+    ));
+}
+
+/// `<Model>Created`, `<Model>Updated` and `<Model>Deleted`, the events the
+/// mutations of `model` publish.
+pub(in crate::g) fn event_idents(model: &str) -> [Ident; 3] {
+    ["Created", "Updated", "Deleted"].map(|kind| format_ident!("{}{}", to_upper_camel(model), kind))
+}
+
+/// In-process broker the mutations publish model events to, with one
+/// broadcast channel per event type.
+fn create_broker_tokens() -> TokenStream {
+    quote! {
+        use futures_util::stream::{self, Stream};
+        use std::any::{Any, TypeId};
+        use std::collections::HashMap;
+        use std::sync::{Mutex, OnceLock};
+        use tokio::sync::broadcast;
+
+        /// Events kept for slow subscribers, which skip the ones they missed.
+        const CAPACITY: usize = 256;
+
+        static CHANNELS: OnceLock<Mutex<HashMap<TypeId, Box<dyn Any + Send>>>> = OnceLock::new();
+
+        fn sender<T: Clone + Send + 'static>() -> broadcast::Sender<T> {
+            let mut channels = CHANNELS.get_or_init(Default::default).lock().unwrap();
+            channels
+                .entry(TypeId::of::<T>())
+                .or_insert_with(|| Box::new(broadcast::channel::<T>(CAPACITY).0))
+                .downcast_ref::<broadcast::Sender<T>>()
+                .unwrap()
+                .clone()
+        }
+
+        pub struct SimpleBroker;
+
+        impl SimpleBroker {
+            pub fn publish<T: Clone + Send + 'static>(event: T) {
+                // Nobody listening is not an error.
+                let _ = sender::<T>().send(event);
+            }
+
+            pub fn subscribe<T: Clone + Send + 'static>() -> impl Stream<Item = T> {
+                stream::unfold(sender::<T>().subscribe(), |mut receiver| async move {
+                    loop {
+                        match receiver.recv().await {
+                            Ok(event) => return Some((event, receiver)),
+                            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                            Err(broadcast::error::RecvError::Closed) => return None,
+                        }
+                    }
+                })
+            }
+        }
+    }
+}
+
+fn create_subscription_tokens(model_str: &str, pk: PrimaryKey) -> TokenStream {
+    let model = format_ident!("{}", model_str);
+    let model_subscription = format_ident!("{}Subscription", to_upper_camel(model_str));
+    let [created, updated, deleted] = event_idents(model_str);
+    let model_created = format_ident!("{}_created", model_str);
+    let model_updated = format_ident!("{}_updated", model_str);
+    let model_deleted = format_ident!("{}_deleted", model_str);
+    let deleted_doc = format!(" Ids of the deleted {}s.", model_str);
+    let uuid_import = (pk == PrimaryKey::Uuid).then(|| {
+        quote!(
+            use sea_orm::prelude::Uuid;
+        )
+    });
+    let pk = pk.rust_type();
+
+    quote! {
+        use async_graphql::Subscription;
+        use entity::{async_graphql, #model};
+        use futures_util::{Stream, StreamExt};
+        #uuid_import
+        use crate::graphql::subscription::broker::SimpleBroker;
+
+        #[derive(Clone)]
+        pub struct #created(pub #model::Model);
+
+        #[derive(Clone)]
+        pub struct #updated(pub #model::Model);
+
+        #[derive(Clone)]
+        pub struct #deleted(pub #pk);
+
+        #[derive(Default)]
+        pub struct #model_subscription;
+
+        #[Subscription]
+        impl #model_subscription {
+            async fn #model_created(&self) -> impl Stream<Item = #model::Model> {
+                SimpleBroker::subscribe::<#created>().map(|event| event.0)
+            }
+
+            async fn #model_updated(&self) -> impl Stream<Item = #model::Model> {
+                SimpleBroker::subscribe::<#updated>().map(|event| event.0)
+            }
+
+            #[doc = #deleted_doc]
+            async fn #model_deleted(&self) -> impl Stream<Item = #pk> {
+                SimpleBroker::subscribe::<#deleted>().map(|event| event.0)
+            }
+        }
+    }
+}
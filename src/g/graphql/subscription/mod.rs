@@ -0,0 +1,46 @@
+use crate::g::graphql::subscription::{
+    creation::{create_broker, create_subscription},
+    registration::{declare_subscription_module, register_subscription},
+};
+use crate::g::{remove_generated_code, PrimaryKey};
+use crate::style_print::log_error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub(in crate::g) use creation::event_idents;
+
+mod creation;
+mod registration;
+
+pub(in crate::g) fn subscription_file(model: &str, gen_path: &Path) -> PathBuf {
+    gen_path
+        .join("src")
+        .join("graphql")
+        .join("subscription")
+        .join(format!("{}.rs", model))
+}
+
+pub(in crate::g) fn process_graphql_subscription(model: &str, pk: PrimaryKey, gen_path: &Path) {
+    let graphql_dir = gen_path.join("src").join("graphql");
+    let subscription_dir = graphql_dir.join("subscription");
+
+    fs::create_dir_all(subscription_dir.as_path()).unwrap_or_else(|why| {
+        log_error(&format!("! {:?}", why.kind()));
+    });
+
+    // Shared by every model and left alone once it is there.
+    if !subscription_dir.join("broker.rs").is_file() {
+        create_broker(&subscription_dir);
+    }
+    create_subscription(model, pk, &subscription_dir);
+    register_subscription(model, &subscription_dir);
+    declare_subscription_module(&graphql_dir);
+}
+
+pub(in crate::g) fn destroy_graphql_subscription(model: &str, gen_path: &Path) {
+    let subscription_dir = gen_path.join("src").join("graphql").join("subscription");
+
+    if remove_generated_code(&subscription_dir, &format!("{}.rs", model)) {
+        register_subscription(model, &subscription_dir);
+    }
+}
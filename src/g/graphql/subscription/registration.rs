@@ -0,0 +1,101 @@
+use crate::g::registration::Registration;
+use crate::g::{format_generated_code, read_dir, to_upper_camel, write_generated_code};
+use crate::style_print::{log_error, log_success};
+use proc_macro2::Ident;
+use quote::{format_ident, quote};
+use std::path::Path;
+
+pub(super) fn register_subscription(model: &str, subscription_dir: &Path) {
+    let file_content = match register_subscription_content(subscription_dir) {
+        Ok(file_content) => file_content,
+        Err(why) => {
+            log_error(&why);
+            return;
+        }
+    };
+
+    let file_path = write_generated_code(subscription_dir, "mod.rs", &file_content);
+
+    log_success(&format!(
+        "Successfully registered GraphQL subscription for `{}` in {}",
+        model,
+        file_path.display()
+    ));
+}
+
+/// Declares `subscription` next to `query` and `mutation` in the
+/// `graphql/mod.rs` of the app, if it is not yet.
+pub(super) fn declare_subscription_module(graphql_dir: &Path) {
+    let mut registration = match Registration::parse(graphql_dir, "mod.rs") {
+        Ok(Some(registration)) => registration,
+        Ok(None) => return,
+        Err(why) => {
+            log_error(&why);
+            return;
+        }
+    };
+    let (added, _) = registration.sync_mods(graphql_dir, &["subscription".to_string()], true);
+    if added.is_empty() {
+        return;
+    }
+
+    let file_path = write_generated_code(graphql_dir, "mod.rs", &registration.into_source());
+
+    log_success(&format!(
+        "Successfully declared the GraphQL subscription module in {}",
+        file_path.display()
+    ));
+}
+
+fn register_subscription_content(subscription_dir: &Path) -> Result<String, String> {
+    let subscription_files = read_dir(subscription_dir).unwrap();
+    let mut subscription_box = subscription_files
+        .iter()
+        .filter(|&i| i != "mod.rs")
+        .filter(|&i| i != "broker.rs")
+        .map(|i| i.replace(".rs", ""))
+        .collect::<Vec<_>>();
+    subscription_box.sort();
+
+    if let Some(mut registration) = Registration::parse(subscription_dir, "mod.rs")? {
+        let modules = [vec!["broker".to_string()], subscription_box].concat();
+        let (added, stale) = registration.sync_mods(subscription_dir, &modules, true);
+        let uses = added
+            .iter()
+            .filter(|&m| m != "broker")
+            .map(|m| (m.clone(), subscription_member(m)))
+            .collect::<Vec<_>>();
+        let stale_members = stale
+            .iter()
+            .map(|m| subscription_member(m))
+            .collect::<Vec<_>>();
+        registration.sync_uses(&uses, &stale);
+        registration.sync_struct_members("Subscription", &uses, &stale_members);
+        return Ok(registration.into_source());
+    }
+
+    let modules = subscription_box
+        .iter()
+        .map(|i| format_ident!("{}", i))
+        .collect::<Vec<_>>();
+
+    let members = subscription_box
+        .iter()
+        .map(|s| subscription_member(s))
+        .collect::<Vec<_>>();
+
+    Ok(format_generated_code(&quote! {
+        use entity::async_graphql;
+
+        pub mod broker;
+        #(pub mod #modules;)*
+        #(pub use #modules::#members;)*
+
+        #[derive(async_graphql::MergedSubscription, Default)]
+        pub struct Subscription(#(#members),*);
+    }))
+}
+
+fn subscription_member(module: &str) -> Ident {
+    format_ident!("{}Subscription", to_upper_camel(module))
+}
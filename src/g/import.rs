@@ -0,0 +1,645 @@
+use crate::g::{Field, FieldType, Index, PrimaryKey, Schema, SchemaModel, Timestamps};
+use crate::style_print::log_error;
+use regex::Regex;
+
+const GENERATED_COLUMNS: [&str; 3] = ["id", "created_at", "updated_at"];
+
+/// Keywords ending the type of a column definition.
+const COLUMN_CONSTRAINTS: [&str; 10] = [
+    "not",
+    "null",
+    "default",
+    "primary",
+    "unique",
+    "references",
+    "check",
+    "constraint",
+    "collate",
+    "generated",
+];
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    /// Unquoted identifiers and keywords are lowercased.
+    Word(String),
+    Quoted(String),
+    Str(String),
+    Number(String),
+    Punct(char),
+}
+
+impl Token {
+    fn is_word(&self, word: &str) -> bool {
+        matches!(self, Token::Word(w) if w == word)
+    }
+
+    fn name(&self) -> Option<&str> {
+        match self {
+            Token::Word(name) | Token::Quoted(name) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Table {
+    name: String,
+    columns: Vec<Column>,
+    primary_key: Vec<String>,
+    /// `(column, referenced table)`
+    foreign_keys: Vec<(String, String)>,
+    indexes: Vec<Index>,
+}
+
+#[derive(Debug)]
+struct Column {
+    name: String,
+    sql_type: String,
+    nullable: bool,
+    default: Option<String>,
+}
+
+impl Schema {
+    /// Reads the `CREATE TABLE`, `CREATE INDEX` and `ALTER TABLE ... ADD
+    /// CONSTRAINT` statements of a Postgres DDL dump, everything else is
+    /// ignored. Tables and columns zapp cannot generate are left out with an
+    /// error message.
+    pub fn from_sql(sql: &str) -> Result<Self, String> {
+        let tokens = tokenize(sql)?;
+        let mut tables: Vec<Table> = Vec::new();
+
+        for statement in tokens.split(|t| *t == Token::Punct(';')) {
+            let mut cursor = Cursor::new(statement);
+            if cursor.eat_words(&["create", "table"]) {
+                let table = parse_create_table(&mut cursor)?;
+                tables.retain(|t| t.name != table.name);
+                tables.push(table);
+            } else if cursor.eat_word("create") {
+                let unique = cursor.eat_word("unique");
+                if cursor.eat_word("index") {
+                    // Indexes keep their names so the baseline migration
+                    // recognizes them.
+                    let (table, index) = parse_create_index(&mut cursor, unique)?;
+                    if let Some(table) = tables.iter_mut().find(|t| t.name == table) {
+                        table.indexes.push(index);
+                    }
+                }
+            } else if cursor.eat_words(&["alter", "table"]) {
+                cursor.eat_word("only");
+                cursor.eat_words(&["if", "exists"]);
+                cursor.eat_word("only");
+                let name = cursor.qualified_name()?;
+                if cursor.eat_word("add") {
+                    if let Some(table) = tables.iter_mut().find(|t| t.name == name) {
+                        parse_table_constraint(&mut cursor, table)?;
+                    }
+                }
+            }
+        }
+
+        let mut schema = Schema {
+            models: Vec::new(),
+            joins: Vec::new(),
+        };
+        for table in &tables {
+            match join_of(table) {
+                Some(join) => schema.joins.push(join),
+                None => {
+                    if let Some(model) = model_of(table) {
+                        schema.models.push(model);
+                    }
+                }
+            }
+        }
+        Ok(schema)
+    }
+}
+
+struct Cursor<'a> {
+    tokens: &'a [Token],
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(tokens: &'a [Token]) -> Self {
+        Cursor {
+            tokens,
+            position: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<&'a Token> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+        token
+    }
+
+    fn eat_word(&mut self, word: &str) -> bool {
+        match self.peek() {
+            Some(token) if token.is_word(word) => {
+                self.position += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Consumes `words` only if they all follow.
+    fn eat_words(&mut self, words: &[&str]) -> bool {
+        let start = self.position;
+        if words.iter().all(|word| self.eat_word(word)) {
+            true
+        } else {
+            self.position = start;
+            false
+        }
+    }
+
+    fn eat_punct(&mut self, punct: char) -> bool {
+        if self.peek() == Some(&Token::Punct(punct)) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_punct(&mut self, punct: char) -> Result<(), String> {
+        if self.eat_punct(punct) {
+            Ok(())
+        } else {
+            Err(format!("expected `{}` near {:?}", punct, self.peek()))
+        }
+    }
+
+    fn name(&mut self) -> Result<String, String> {
+        match self.next().and_then(Token::name) {
+            Some(name) => Ok(name.to_string()),
+            None => Err(format!(
+                "expected a name near {:?}",
+                self.tokens.get(self.position - 1)
+            )),
+        }
+    }
+
+    /// `schema.name` or `name`, without the schema.
+    fn qualified_name(&mut self) -> Result<String, String> {
+        let mut name = self.name()?;
+        while self.eat_punct('.') {
+            name = self.name()?;
+        }
+        Ok(name)
+    }
+
+    /// `(a, b)` as names.
+    fn name_list(&mut self) -> Result<Vec<String>, String> {
+        self.expect_punct('(')?;
+        let mut names = Vec::new();
+        loop {
+            names.push(self.name()?);
+            // Skips `ASC`, `DESC`, operator classes and the like.
+            while !matches!(self.peek(), None | Some(Token::Punct(',' | ')'))) {
+                self.skip();
+            }
+            if !self.eat_punct(',') {
+                break;
+            }
+        }
+        self.expect_punct(')')?;
+        Ok(names)
+    }
+
+    /// Skips one token, or a whole parenthesized group.
+    fn skip(&mut self) {
+        if self.next() != Some(&Token::Punct('(')) {
+            return;
+        }
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next() {
+                Some(Token::Punct('(')) => depth += 1,
+                Some(Token::Punct(')')) => depth -= 1,
+                Some(_) => {}
+                None => return,
+            }
+        }
+    }
+
+    /// Tokens up to the next top level `,` or the closing `)`.
+    fn item(&mut self) -> &'a [Token] {
+        let start = self.position;
+        while !matches!(self.peek(), None | Some(Token::Punct(',' | ')'))) {
+            self.skip();
+        }
+        &self.tokens[start..self.position]
+    }
+}
+
+fn tokenize(sql: &str) -> Result<Vec<Token>, String> {
+    let chars = sql.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '-' && chars.get(i + 1) == Some(&'-') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i += 2;
+        } else if c == '\'' || c == '"' {
+            let mut value = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => return Err("unterminated quote".to_string()),
+                    // A doubled quote stands for the quote itself.
+                    Some(&q) if q == c && chars.get(i + 1) == Some(&c) => {
+                        value.push(c);
+                        i += 2;
+                    }
+                    Some(&q) if q == c => {
+                        i += 1;
+                        break;
+                    }
+                    Some(&other) => {
+                        value.push(other);
+                        i += 1;
+                    }
+                }
+            }
+            tokens.push(if c == '\'' {
+                Token::Str(value)
+            } else {
+                Token::Quoted(value)
+            });
+        } else if c == '$' {
+            // Dollar quoted function bodies, `$$ ... $$` or `$tag$ ... $tag$`.
+            let end = chars[i + 1..]
+                .iter()
+                .position(|&c| c == '$')
+                .ok_or("unterminated dollar quote")?;
+            let tag = chars[i..i + end + 2].iter().collect::<String>();
+            let rest = chars[i + tag.len()..].iter().collect::<String>();
+            let close = rest.find(&tag).ok_or("unterminated dollar quote")?;
+            tokens.push(Token::Str(rest[..close].to_string()));
+            i += tag.len() + rest[..close].chars().count() + tag.len();
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            tokens.push(Token::Number(chars[start..i].iter().collect()));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word = chars[start..i].iter().collect::<String>();
+            tokens.push(Token::Word(word.to_lowercase()));
+        } else {
+            tokens.push(Token::Punct(c));
+            i += 1;
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_create_table(cursor: &mut Cursor) -> Result<Table, String> {
+    cursor.eat_words(&["if", "not", "exists"]);
+    let mut table = Table {
+        name: cursor.qualified_name()?,
+        ..Table::default()
+    };
+
+    cursor.expect_punct('(')?;
+    loop {
+        let item = cursor.item();
+        let mut item_cursor = Cursor::new(item);
+        if !parse_table_constraint(&mut item_cursor, &mut table)? && !item.is_empty() {
+            parse_column(&mut Cursor::new(item), &mut table)?;
+        }
+        if !cursor.eat_punct(',') {
+            break;
+        }
+    }
+    cursor.expect_punct(')')?;
+    Ok(table)
+}
+
+/// `[CONSTRAINT name] PRIMARY KEY (...) | FOREIGN KEY (...) REFERENCES ... |
+/// UNIQUE (...)`, returns whether `cursor` was at a table constraint.
+fn parse_table_constraint(cursor: &mut Cursor, table: &mut Table) -> Result<bool, String> {
+    let name = if cursor.eat_word("constraint") {
+        Some(cursor.name()?)
+    } else {
+        None
+    };
+
+    if cursor.eat_words(&["primary", "key"]) {
+        table.primary_key = cursor.name_list()?;
+    } else if cursor.eat_words(&["foreign", "key"]) {
+        let columns = cursor.name_list()?;
+        if !cursor.eat_word("references") {
+            return Err(format!("expected REFERENCES in `{}`", table.name));
+        }
+        let parent = cursor.qualified_name()?;
+        if let [column] = columns.as_slice() {
+            table.foreign_keys.push((column.clone(), parent));
+        }
+    } else if cursor.eat_word("unique") {
+        let columns = cursor.name_list()?;
+        let name = name.unwrap_or_else(|| format!("{}_{}_key", table.name, columns.join("_")));
+        table.indexes.push(Index {
+            columns,
+            unique: true,
+            name: Some(name),
+        });
+    } else if cursor.eat_word("check") || cursor.eat_word("exclude") {
+    } else {
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+fn parse_column(cursor: &mut Cursor, table: &mut Table) -> Result<(), String> {
+    let name = cursor.name()?;
+
+    let mut type_words = Vec::new();
+    while let Some(token) = cursor.peek() {
+        match token {
+            Token::Word(word) if !COLUMN_CONSTRAINTS.contains(&word.as_str()) => {
+                type_words.push(word.clone());
+                cursor.next();
+            }
+            Token::Punct('(') => cursor.skip(),
+            Token::Punct('[') => {
+                if let Some(word) = type_words.last_mut() {
+                    word.push_str("[]");
+                }
+                cursor.next();
+                cursor.eat_punct(']');
+            }
+            Token::Punct('.') => {
+                // A schema qualified type.
+                type_words.clear();
+                cursor.next();
+            }
+            _ => break,
+        }
+    }
+
+    let mut column = Column {
+        name,
+        sql_type: type_words.join(" "),
+        nullable: true,
+        default: None,
+    };
+    while let Some(token) = cursor.next() {
+        match token {
+            Token::Word(word) if word == "not" && cursor.eat_word("null") => {
+                column.nullable = false;
+            }
+            Token::Word(word) if word == "default" => {
+                column.default = default_value(cursor);
+            }
+            Token::Word(word) if word == "primary" => {
+                cursor.eat_word("key");
+                column.nullable = false;
+                table.primary_key = vec![column.name.clone()];
+            }
+            Token::Word(word) if word == "unique" => {
+                table.indexes.push(Index {
+                    columns: vec![column.name.clone()],
+                    unique: true,
+                    name: Some(format!("{}_{}_key", table.name, column.name)),
+                });
+            }
+            Token::Word(word) if word == "references" => {
+                let parent = cursor.qualified_name()?;
+                table.foreign_keys.push((column.name.clone(), parent));
+            }
+            Token::Punct('(') => {
+                cursor.position -= 1;
+                cursor.skip();
+            }
+            _ => {}
+        }
+    }
+    table.columns.push(column);
+    Ok(())
+}
+
+/// The literal a `DEFAULT` starts with, casts and expressions are dropped.
+fn default_value(cursor: &mut Cursor) -> Option<String> {
+    let negative = cursor.eat_punct('-');
+    let value = match cursor.peek()? {
+        Token::Str(value) => Some(value.clone()),
+        Token::Number(value) if negative => Some(format!("-{}", value)),
+        Token::Number(value) => Some(value.clone()),
+        Token::Word(word) if word == "true" || word == "false" => Some(word.clone()),
+        _ => None,
+    };
+    if value.is_some() {
+        cursor.next();
+    }
+    value
+}
+
+fn parse_create_index(cursor: &mut Cursor, unique: bool) -> Result<(String, Index), String> {
+    cursor.eat_word("concurrently");
+    cursor.eat_words(&["if", "not", "exists"]);
+    let name = match cursor.peek() {
+        Some(token) if !token.is_word("on") => Some(cursor.qualified_name()?),
+        _ => None,
+    };
+    if !cursor.eat_word("on") {
+        return Err("expected ON in CREATE INDEX".to_string());
+    }
+    cursor.eat_word("only");
+    let table = cursor.qualified_name()?;
+    if cursor.eat_word("using") {
+        cursor.next();
+    }
+    let columns = cursor.name_list()?;
+    Ok((
+        table,
+        Index {
+            columns,
+            unique,
+            name,
+        },
+    ))
+}
+
+fn field_type(sql_type: &str) -> Option<FieldType> {
+    let ty = match sql_type {
+        "character varying" | "varchar" | "character" | "char" | "citext" => FieldType::String,
+        "text" => FieldType::Text,
+        "integer" | "int" | "int4" | "serial" | "serial4" => FieldType::I32,
+        "bigint" | "int8" | "bigserial" | "serial8" => FieldType::I64,
+        "real" | "float4" => FieldType::F32,
+        "double precision" | "float8" | "float" => FieldType::F64,
+        "boolean" | "bool" => FieldType::Bool,
+        "date" => FieldType::Date,
+        "timestamp" | "timestamp without time zone" => FieldType::DateTime,
+        "timestamptz" | "timestamp with time zone" => FieldType::DateTimeTz,
+        "uuid" => FieldType::Uuid,
+        "json" | "jsonb" => FieldType::Json,
+        _ => return None,
+    };
+    Some(ty)
+}
+
+/// `(left, right)` when `table` is nothing but the `<left>_<right>` join of two
+/// other tables.
+fn join_of(table: &Table) -> Option<(String, String)> {
+    let (left_id, right_id) = match table.primary_key.as_slice() {
+        [left_id, right_id] => (left_id, right_id),
+        _ => return None,
+    };
+    let parent = |column: &String| {
+        table
+            .foreign_keys
+            .iter()
+            .find(|(c, _)| c == column)
+            .map(|(_, parent)| parent.clone())
+    };
+    let (left, right) = (parent(left_id)?, parent(right_id)?);
+    let only_keys = table
+        .columns
+        .iter()
+        .all(|c| [left_id, right_id].contains(&&c.name));
+    let named_after_keys = *left_id == format!("{}_id", left)
+        && *right_id == format!("{}_id", right)
+        && table.name == format!("{}_{}", left, right);
+
+    if only_keys && named_after_keys {
+        Some((left, right))
+    } else {
+        None
+    }
+}
+
+fn model_of(table: &Table) -> Option<SchemaModel> {
+    let snake_case = Regex::new("^[a-z][a-z0-9_]*$").unwrap();
+    if !snake_case.is_match(&table.name) {
+        log_error(&format!(
+            "Skipped table `{}`, its name is not snake_case",
+            table.name
+        ));
+        return None;
+    }
+    let id = table.columns.iter().find(|c| c.name == "id");
+    let pk = match id.and_then(|id| field_type(&id.sql_type)) {
+        Some(FieldType::I32) => Some(PrimaryKey::I32),
+        Some(FieldType::I64) => Some(PrimaryKey::I64),
+        Some(FieldType::Uuid) => Some(PrimaryKey::Uuid),
+        _ => None,
+    };
+    let pk = match pk {
+        Some(pk) if table.primary_key == ["id"] => pk,
+        _ => {
+            log_error(&format!(
+                "Skipped table `{}`, only an integer or uuid `id` primary key is supported",
+                table.name
+            ));
+            return None;
+        }
+    };
+    let timestamp_types = ["created_at", "updated_at"].map(|timestamp| {
+        let column = table.columns.iter().find(|c| c.name == timestamp);
+        column.and_then(|c| field_type(&c.sql_type))
+    });
+    let timestamps = match timestamp_types {
+        [None, None] => Timestamps::None,
+        [Some(FieldType::DateTimeTz), Some(FieldType::DateTimeTz)] => Timestamps::Tz,
+        [Some(FieldType::DateTime), Some(FieldType::DateTime)] => Timestamps::Naive,
+        _ => {
+            log_error(&format!(
+                "Skipped table `{}`, its `created_at` and `updated_at` columns need to be both there and of the same timestamp type",
+                table.name
+            ));
+            return None;
+        }
+    };
+
+    // A nullable `deleted_at` timestamp is what `soft_delete` generates.
+    let soft_delete = table.columns.iter().any(|c| {
+        c.name == "deleted_at"
+            && c.nullable
+            && field_type(&c.sql_type) == Some(timestamps.deleted_at_type())
+    });
+
+    let fields = table
+        .columns
+        .iter()
+        .filter(|c| !GENERATED_COLUMNS.contains(&c.name.as_str()))
+        .filter(|c| !(soft_delete && c.name == "deleted_at"))
+        .filter_map(|column| {
+            let field = field_of(table, column);
+            if field.is_none() {
+                log_error(&format!(
+                    "Skipped column `{}` of `{}`, `{}` columns are not supported",
+                    column.name, table.name, column.sql_type
+                ));
+            }
+            field
+        })
+        .collect::<Vec<_>>();
+
+    let known = |column: &String| {
+        GENERATED_COLUMNS.contains(&column.as_str()) || fields.iter().any(|f| &f.name == column)
+    };
+    let mut indexes = Vec::new();
+    for index in &table.indexes {
+        if index.columns.iter().all(known) && !indexes.contains(index) {
+            indexes.push(index.clone());
+        }
+    }
+
+    Some(SchemaModel {
+        name: table.name.clone(),
+        fields,
+        indexes,
+        soft_delete,
+        pk,
+        timestamps,
+        subscriptions: false,
+    })
+}
+
+fn field_of(table: &Table, column: &Column) -> Option<Field> {
+    let ty = field_type(&column.sql_type)?;
+    let nullable = if column.nullable { "?" } else { "" };
+
+    let parent = table
+        .foreign_keys
+        .iter()
+        .find(|(c, _)| *c == column.name)
+        .map(|(_, parent)| parent);
+    if let Some(parent) = parent {
+        let key = matches!(ty, FieldType::I32 | FieldType::I64 | FieldType::Uuid);
+        if column.name == format!("{}_id", parent) && key {
+            let field = format!("{}:references{}", parent, nullable).parse::<Field>();
+            return field.ok().map(|field| Field { ty, ..field });
+        }
+    }
+
+    let spec = format!("{}:{}{}", column.name, ty, nullable);
+    let with_default = column
+        .default
+        .as_ref()
+        .and_then(|default| format!("{}={}", spec, default).parse().ok());
+    with_default.or_else(|| spec.parse().ok())
+}
@@ -0,0 +1,385 @@
+use crate::g::{emit_generated_code, Field, Index};
+use crate::style_print::log_success;
+use proc_macro2::TokenStream;
+use quote::quote;
+use regex::Regex;
+use std::path::Path;
+
+/// Change to an existing table, read from the name of the migration the way
+/// `add_age_to_user age:i32` reads.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Alteration {
+    /// `add_<anything>_to_<model> name:type...`
+    AddColumns { model: String, fields: Vec<Field> },
+    /// `remove_<anything>_from_<model> name:type...`, the types are needed to
+    /// add the columns back on rollback.
+    RemoveColumns { model: String, fields: Vec<Field> },
+    /// `rename_<from>_to_<to>_in_<model>`
+    RenameColumn {
+        model: String,
+        from: String,
+        to: String,
+    },
+    /// `add_index_to_<model> column...`
+    AddIndex { model: String, index: Index },
+}
+
+impl Alteration {
+    /// `tables` are the models already generated, they settle names that
+    /// `_to_`, `_in_` or `_from_` split more than one way, like
+    /// `add_due_to_to_do`.
+    pub fn parse(
+        name: &str,
+        args: &[String],
+        unique: bool,
+        tables: &[String],
+    ) -> Result<Self, String> {
+        let add_index = Regex::new("^add_index_to_([a-z][a-z0-9_]*)$").unwrap();
+        let column = Regex::new("^[a-z][a-z0-9_]*$").unwrap();
+        let columns = Regex::new("^[a-z0-9_]+$").unwrap();
+        let unknown = format!(
+            "cannot tell what `{}` alters, name it `add_<columns>_to_<model>`, `remove_<columns>_from_<model>`, `rename_<from>_to_<to>_in_<model>` or `add_index_to_<model>`",
+            name
+        );
+
+        if unique && !add_index.is_match(name) {
+            return Err("--unique only applies to `add_index_to_<model>`".to_string());
+        }
+
+        let alteration = if let Some(captures) = add_index.captures(name) {
+            if let Some(arg) = args.iter().find(|arg| !column.is_match(arg)) {
+                return Err(format!("index column `{}` must be snake_case", arg));
+            }
+            Alteration::AddIndex {
+                model: captures[1].to_string(),
+                index: Index {
+                    columns: args.to_vec(),
+                    unique,
+                    name: None,
+                },
+            }
+        } else if let Some(rest) = name.strip_prefix("rename_") {
+            let readings = splits(rest, "_in_")
+                .into_iter()
+                .flat_map(|(names, model)| {
+                    splits(names, "_to_")
+                        .into_iter()
+                        .map(move |(from, to)| (from, to, model))
+                })
+                .filter(|(from, to, model)| {
+                    [from, to, model].iter().all(|name| column.is_match(name))
+                })
+                .collect::<Vec<_>>();
+            let (from, to, model) = pick(name, readings, tables, &unknown, |(from, to, model)| {
+                (model, format!("`{}` to `{}` in `{}`", from, to, model))
+            })?;
+            if !args.is_empty() {
+                return Err(format!("`{}` takes no fields", name));
+            }
+            Alteration::RenameColumn {
+                model: model.to_string(),
+                from: from.to_string(),
+                to: to.to_string(),
+            }
+        } else if let Some(rest) = name.strip_prefix("add_") {
+            Alteration::AddColumns {
+                model: pick_model(name, rest, "_to_", tables, &unknown, &columns, &column)?,
+                fields: parse_fields(args)?,
+            }
+        } else if let Some(rest) = name.strip_prefix("remove_") {
+            Alteration::RemoveColumns {
+                model: pick_model(name, rest, "_from_", tables, &unknown, &columns, &column)?,
+                fields: parse_fields(args)?,
+            }
+        } else {
+            return Err(unknown);
+        };
+
+        let empty = match &alteration {
+            Alteration::AddColumns { fields, .. } | Alteration::RemoveColumns { fields, .. } => {
+                fields.is_empty()
+            }
+            Alteration::AddIndex { index, .. } => index.columns.is_empty(),
+            Alteration::RenameColumn { .. } => false,
+        };
+        if empty {
+            return Err(format!("`{}` needs at least one column", name));
+        }
+        Ok(alteration)
+    }
+
+    pub fn model(&self) -> &str {
+        match self {
+            Alteration::AddColumns { model, .. }
+            | Alteration::RemoveColumns { model, .. }
+            | Alteration::RenameColumn { model, .. }
+            | Alteration::AddIndex { model, .. } => model,
+        }
+    }
+}
+
+/// Every way to cut `s` around one `sep`.
+fn splits<'a>(s: &'a str, sep: &str) -> Vec<(&'a str, &'a str)> {
+    s.char_indices()
+        .filter(|(i, _)| s[*i..].starts_with(sep))
+        .map(|(i, _)| (&s[..i], &s[i + sep.len()..]))
+        .collect()
+}
+
+/// The model of `<columns><sep><model>`.
+fn pick_model(
+    name: &str,
+    rest: &str,
+    sep: &str,
+    tables: &[String],
+    unknown: &str,
+    columns: &Regex,
+    column: &Regex,
+) -> Result<String, String> {
+    let readings = splits(rest, sep)
+        .into_iter()
+        .filter(|(names, model)| columns.is_match(names) && column.is_match(model))
+        .map(|(_, model)| model)
+        .collect::<Vec<_>>();
+    let model = pick(name, readings, tables, unknown, |model| {
+        (model, format!("`{}`", model))
+    })?;
+    Ok(model.to_string())
+}
+
+/// The only reading of `name`, or else the only one altering a table in
+/// `tables`. `describe` gives the model of a reading and how to show it.
+fn pick<'a, T: Copy>(
+    name: &str,
+    readings: Vec<T>,
+    tables: &[String],
+    unknown: &str,
+    describe: impl Fn(T) -> (&'a str, String),
+) -> Result<T, String> {
+    let known = readings
+        .iter()
+        .copied()
+        .filter(|&reading| tables.iter().any(|table| table == describe(reading).0))
+        .collect::<Vec<_>>();
+    match (readings.as_slice(), known.as_slice()) {
+        ([], _) => Err(unknown.to_string()),
+        ([reading], _) | (_, [reading]) => Ok(*reading),
+        _ => Err(format!(
+            "`{}` can be read as {}, rename the migration so it reads one way",
+            name,
+            readings
+                .iter()
+                .map(|&reading| describe(reading).1)
+                .collect::<Vec<_>>()
+                .join(" or ")
+        )),
+    }
+}
+
+fn parse_fields(args: &[String]) -> Result<Vec<Field>, String> {
+    let fields = args
+        .iter()
+        .map(|arg| arg.parse::<Field>())
+        .collect::<Result<Vec<_>, _>>()?;
+    // The Postgres type of an enum is created along with its table.
+    if let Some(field) = fields.iter().find(|f| f.enum_ident().is_some()) {
+        return Err(format!(
+            "enum field `{}` can only be added with `zapp g model`",
+            field.name
+        ));
+    }
+    // So are the `CHECK` constraints of validation rules.
+    if let Some(field) = fields.iter().find(|f| !f.rules.is_empty()) {
+        return Err(format!(
+            "validation rules of `{}` can only be given with `zapp g model`",
+            field.name
+        ));
+    }
+    Ok(fields)
+}
+
+pub(super) fn create_alter_migration(
+    alteration: &Alteration,
+    migration_name: &str,
+    migration_src_dir: &Path,
+) {
+    let file_content_tokens = create_alter_migration_tokens(alteration, migration_name);
+
+    let file_path = emit_generated_code(
+        migration_src_dir,
+        &format!("{}.rs", migration_name),
+        &file_content_tokens,
+    );
+
+    log_success(&format!(
+        "Successfully created migration file for model `{}`: {}",
+        alteration.model(),
+        file_path.display()
+    ));
+}
+
+fn create_alter_migration_tokens(alteration: &Alteration, migration_name: &str) -> TokenStream {
+    let migration_name_lit = lit(migration_name);
+    let table = lit(alteration.model());
+
+    let (up, down): (Vec<_>, Vec<_>) = match alteration {
+        Alteration::AddColumns { model, fields } => (
+            fields.iter().map(|f| add_column_tokens(model, f)).collect(),
+            fields
+                .iter()
+                .rev()
+                .map(|f| drop_column_tokens(model, f))
+                .collect(),
+        ),
+        Alteration::RemoveColumns { model, fields } => (
+            fields
+                .iter()
+                .map(|f| drop_column_tokens(model, f))
+                .collect(),
+            fields
+                .iter()
+                .rev()
+                .map(|f| add_column_tokens(model, f))
+                .collect(),
+        ),
+        Alteration::RenameColumn { model, from, to } => (
+            vec![rename_column_tokens(model, from, to)],
+            vec![rename_column_tokens(model, to, from)],
+        ),
+        Alteration::AddIndex { model, index } => {
+            let name = lit(&index.name(model));
+            let columns = index.columns.iter().map(|c| lit(c));
+            let unique = if index.unique {
+                quote!(.unique())
+            } else {
+                quote!()
+            };
+            (
+                vec![quote! {
+                    manager
+                        .create_index(
+                            Index::create()
+                                .name(#name)
+                                .table(Alias::new(#table))
+                                #(.col(Alias::new(#columns)))*
+                                #unique
+                                .to_owned(),
+                        )
+                        .await?;
+                }],
+                vec![quote! {
+                    manager
+                        .drop_index(
+                            Index::drop()
+                                .name(#name)
+                                .table(Alias::new(#table))
+                                .to_owned(),
+                        )
+                        .await?;
+                }],
+            )
+        }
+    };
+
+    quote! {
+        use sea_orm_migration::prelude::*;
+
+        pub struct Migration;
+
+        impl MigrationName for Migration {
+            fn name(&self) -> &str {
+                #migration_name_lit
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl MigrationTrait for Migration {
+            async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+                #(#up)*
+
+                Ok(())
+            }
+
+            async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+                #(#down)*
+
+                Ok(())
+            }
+        }
+    }
+}
+
+pub(super) fn lit(s: &str) -> syn::LitStr {
+    syn::LitStr::new(s, proc_macro2::Span::call_site())
+}
+
+pub(super) fn alter_table_tokens(table: &syn::LitStr, changes: &TokenStream) -> TokenStream {
+    quote! {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new(#table))
+                    #changes
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+// The create migrations build tables from the current entities, so on a fresh
+// database the columns are already in their final shape. Every column change
+// is guarded to keep replaying every migration working.
+
+pub(super) fn add_column_tokens(table: &str, field: &Field) -> TokenStream {
+    let (table_lit, column) = (lit(table), lit(&field.name));
+    let column_def = field.column_def();
+    let foreign_key = field.references.as_ref().map(|parent| {
+        let name = lit(&format!("fk_{}_{}", table, field.name));
+        let parent = lit(parent);
+        let on_delete = if field.nullable {
+            quote!(SetNull)
+        } else {
+            quote!(Cascade)
+        };
+        quote! {
+            .add_foreign_key(
+                TableForeignKey::new()
+                    .name(#name)
+                    .from_tbl(Alias::new(#table_lit))
+                    .from_col(Alias::new(#column))
+                    .to_tbl(Alias::new(#parent))
+                    .to_col(Alias::new("id"))
+                    .on_delete(ForeignKeyAction::#on_delete),
+            )
+        }
+    });
+    let alter = alter_table_tokens(&table_lit, &quote!(.add_column(#column_def) #foreign_key));
+    quote! {
+        if !manager.has_column(#table_lit, #column).await? {
+            #alter?;
+        }
+    }
+}
+
+pub(super) fn drop_column_tokens(table: &str, field: &Field) -> TokenStream {
+    let (table, column) = (lit(table), lit(&field.name));
+    let alter = alter_table_tokens(&table, &quote!(.drop_column(Alias::new(#column))));
+    quote! {
+        if manager.has_column(#table, #column).await? {
+            #alter?;
+        }
+    }
+}
+
+fn rename_column_tokens(table: &str, from: &str, to: &str) -> TokenStream {
+    let (table, from, to) = (lit(table), lit(from), lit(to));
+    let alter = alter_table_tokens(
+        &table,
+        &quote!(.rename_column(Alias::new(#from), Alias::new(#to))),
+    );
+    quote! {
+        if manager.has_column(#table, #from).await? {
+            #alter?;
+        }
+    }
+}
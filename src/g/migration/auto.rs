@@ -0,0 +1,305 @@
+use crate::g::migration::alteration::{
+    add_column_tokens, alter_table_tokens, drop_column_tokens, lit,
+};
+use crate::g::{emit_generated_code, read_dir, Field, FieldType};
+use crate::style_print::{log_error, log_success};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use syn::{Item, Lit, Meta, NestedMeta};
+
+/// Columns of every table as they were when the last migration was generated,
+/// keyed by table name. `id`, `created_at` and `updated_at` are left out.
+pub(super) type Snapshot = BTreeMap<String, Vec<Field>>;
+
+const SNAPSHOT_FILE: &str = "entity_snapshot.json";
+
+const GENERATED_COLUMNS: [&str; 3] = ["id", "created_at", "updated_at"];
+
+/// Difference of one column between the snapshot and the entity.
+pub(super) enum ColumnChange {
+    Added(Field),
+    Removed(Field),
+    Changed { from: Field, to: Field },
+}
+
+/// The snapshot in `migration_dir`, `None` if none was recorded yet.
+pub(super) fn load_snapshot(migration_dir: &Path) -> Result<Option<Snapshot>, String> {
+    let content = match fs::read_to_string(migration_dir.join(SNAPSHOT_FILE)) {
+        Ok(content) => content,
+        Err(_) => return Ok(None),
+    };
+    serde_json::from_str(&content).map(Some).map_err(|why| {
+        format!(
+            "migration/{} is not a valid snapshot ({}), fix or delete it to record the entities again",
+            SNAPSHOT_FILE, why
+        )
+    })
+}
+
+pub(super) fn save_snapshot(migration_dir: &Path, snapshot: &Snapshot) {
+    fs::create_dir_all(migration_dir).unwrap();
+    let content = serde_json::to_string_pretty(snapshot).unwrap();
+    fs::write(migration_dir.join(SNAPSHOT_FILE), content + "\n").unwrap();
+}
+
+/// Columns of the `Model` of every entity in `entity_src_dir`.
+pub(super) fn read_entities(entity_src_dir: &Path) -> Result<Snapshot, String> {
+    let mut entity_files = read_dir(entity_src_dir).unwrap_or_default();
+    entity_files.retain(|i| i != "lib.rs" && i.ends_with(".rs"));
+
+    let mut snapshot = Snapshot::new();
+    for file_name in entity_files {
+        let content = fs::read_to_string(entity_src_dir.join(&file_name))
+            .map_err(|why| format!("Could not read entity/src/{}: {:?}", file_name, why.kind()))?;
+        let syntax_tree = syn::parse_file(&content)
+            .map_err(|why| format!("Could not parse entity/src/{}: {}", file_name, why))?;
+        let model = syntax_tree.items.iter().find_map(|item| match item {
+            Item::Struct(item_struct) if item_struct.ident == "Model" => Some(item_struct),
+            _ => None,
+        });
+        let model = match model {
+            Some(model) => model,
+            None => continue,
+        };
+
+        let table = sea_orm_args(&model.attrs)
+            .into_iter()
+            .find_map(|(key, value)| match (key.as_str(), value) {
+                ("table_name", Some(table)) => Some(table),
+                _ => None,
+            })
+            .unwrap_or_else(|| file_name.trim_end_matches(".rs").to_string());
+
+        let columns = model
+            .fields
+            .iter()
+            .filter_map(|field| {
+                let name = field.ident.as_ref()?.to_string();
+                if GENERATED_COLUMNS.contains(&name.as_str()) {
+                    return None;
+                }
+                let column = entity_column(&name, field);
+                if column.is_none() {
+                    log_error(&format!(
+                        "Skipped column `{}` of `{}`, its type is not supported by --auto",
+                        name, table
+                    ));
+                }
+                column
+            })
+            .collect();
+        snapshot.insert(table, columns);
+    }
+    Ok(snapshot)
+}
+
+/// Column of an entity `Model` field, `None` for types without a `FieldType`.
+pub(in crate::g) fn entity_column(name: &str, field: &syn::Field) -> Option<Field> {
+    let (ty, nullable) = match option_inner(&field.ty) {
+        Some(inner) => (inner, true),
+        None => (field.ty.to_token_stream().to_string(), false),
+    };
+    let args = sea_orm_args(&field.attrs);
+    let text = args
+        .iter()
+        .any(|(key, value)| key == "column_type" && value.as_deref() == Some("Text"));
+    let ty = match ty.as_str() {
+        "String" if text => FieldType::Text,
+        "String" => FieldType::String,
+        "i32" => FieldType::I32,
+        "i64" => FieldType::I64,
+        "f32" => FieldType::F32,
+        "f64" => FieldType::F64,
+        "bool" => FieldType::Bool,
+        "Date" => FieldType::Date,
+        "DateTime" => FieldType::DateTime,
+        "DateTimeWithTimeZone" => FieldType::DateTimeTz,
+        "Uuid" => FieldType::Uuid,
+        "Json" => FieldType::Json,
+        _ => return None,
+    };
+    // Only defaults a `name:type=default` spec accepts, the snapshot stores
+    // its columns as such specs.
+    let default = args
+        .into_iter()
+        .find_map(|(key, value)| if key == "default_value" { value } else { None })
+        .filter(|default| ty.accepts_default(default) == Some(true));
+
+    Some(Field {
+        name: name.to_string(),
+        ty,
+        nullable,
+        default,
+        references: None,
+        rules: Vec::new(),
+    })
+}
+
+/// `T` of an `Option<T>` type.
+fn option_inner(ty: &syn::Type) -> Option<String> {
+    let path = match ty {
+        syn::Type::Path(type_path) => &type_path.path,
+        _ => return None,
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => {
+            Some(args.args.first()?.to_token_stream().to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Keys of the `#[sea_orm(...)]` attributes with their value, if any.
+fn sea_orm_args(attrs: &[syn::Attribute]) -> Vec<(String, Option<String>)> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("sea_orm"))
+        .filter_map(|attr| match attr.parse_meta().ok()? {
+            Meta::List(list) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|nested| match nested {
+            NestedMeta::Meta(Meta::Path(path)) => Some((path.get_ident()?.to_string(), None)),
+            NestedMeta::Meta(Meta::NameValue(name_value)) => {
+                let value = match name_value.lit {
+                    Lit::Str(lit) => lit.value(),
+                    Lit::Int(lit) => lit.base10_digits().to_string(),
+                    Lit::Float(lit) => lit.base10_digits().to_string(),
+                    Lit::Bool(lit) => lit.value.to_string(),
+                    _ => return None,
+                };
+                Some((name_value.path.get_ident()?.to_string(), Some(value)))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Column changes of the tables found in both `snapshot` and `entities`, new
+/// and removed tables are handled by their create migrations.
+pub(super) fn diff(
+    snapshot: &Snapshot,
+    entities: &Snapshot,
+) -> BTreeMap<String, Vec<ColumnChange>> {
+    let mut tables = BTreeMap::new();
+    for (table, columns) in entities {
+        let old_columns = match snapshot.get(table) {
+            Some(old_columns) => old_columns,
+            None => continue,
+        };
+
+        let mut changes = Vec::new();
+        for column in columns {
+            match old_columns.iter().find(|old| old.name == column.name) {
+                None => changes.push(ColumnChange::Added(column.clone())),
+                Some(old) if old != column => changes.push(ColumnChange::Changed {
+                    from: old.clone(),
+                    to: column.clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+        for old in old_columns {
+            if !columns.iter().any(|column| column.name == old.name) {
+                changes.push(ColumnChange::Removed(old.clone()));
+            }
+        }
+
+        if !changes.is_empty() {
+            tables.insert(table.clone(), changes);
+        }
+    }
+    tables
+}
+
+pub(super) fn create_auto_migration(
+    changes: &BTreeMap<String, Vec<ColumnChange>>,
+    migration_name: &str,
+    migration_src_dir: &Path,
+) {
+    let file_content_tokens = create_auto_migration_tokens(changes, migration_name);
+
+    let file_path = emit_generated_code(
+        migration_src_dir,
+        &format!("{}.rs", migration_name),
+        &file_content_tokens,
+    );
+
+    log_success(&format!(
+        "Successfully created migration file for the changed entities: {}",
+        file_path.display()
+    ));
+}
+
+fn create_auto_migration_tokens(
+    changes: &BTreeMap<String, Vec<ColumnChange>>,
+    migration_name: &str,
+) -> TokenStream {
+    let migration_name_lit = lit(migration_name);
+
+    let mut up = Vec::new();
+    let mut down = Vec::new();
+    for (table, columns) in changes {
+        for change in columns {
+            let (forward, backward) = match change {
+                ColumnChange::Added(field) => (
+                    add_column_tokens(table, field),
+                    drop_column_tokens(table, field),
+                ),
+                ColumnChange::Removed(field) => (
+                    drop_column_tokens(table, field),
+                    add_column_tokens(table, field),
+                ),
+                ColumnChange::Changed { from, to } => (
+                    modify_column_tokens(table, to),
+                    modify_column_tokens(table, from),
+                ),
+            };
+            up.push(forward);
+            down.push(backward);
+        }
+    }
+    down.reverse();
+
+    quote! {
+        use sea_orm_migration::prelude::*;
+
+        pub struct Migration;
+
+        impl MigrationName for Migration {
+            fn name(&self) -> &str {
+                #migration_name_lit
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl MigrationTrait for Migration {
+            async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+                #(#up)*
+
+                Ok(())
+            }
+
+            async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+                #(#down)*
+
+                Ok(())
+            }
+        }
+    }
+}
+
+fn modify_column_tokens(table: &str, field: &Field) -> TokenStream {
+    let table = lit(table);
+    let column_def = field.column_def();
+    let alter = alter_table_tokens(&table, &quote!(.modify_column(#column_def)));
+    quote!(#alter?;)
+}
@@ -1,23 +1,39 @@
-use crate::g::{emit_generated_code, read_dir};
+use crate::g::{emit_generated_code, to_upper_camel, Field, Index};
 use crate::style_print::log_success;
 use chrono::NaiveDateTime;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use std::path::Path;
 
-pub(super) fn create_migration(model: &str, dt: NaiveDateTime, migration_src_dir: &Path) {
-    let mirgration_name = format!(
-        "m{}{}{}_{}{}{}_create_{}_table",
+pub(super) fn migration_name(model: &str, dt: NaiveDateTime) -> String {
+    timestamped_name(&format!("create_{}_table", model), dt)
+}
+
+/// `name` prefixed with the `mYYYYMMDD_HHMMSS_` that orders migrations.
+pub(super) fn timestamped_name(name: &str, dt: NaiveDateTime) -> String {
+    format!(
+        "m{}{}{}_{}{}{}_{}",
         dt.format("%Y"),
         dt.format("%m"),
         dt.format("%d"),
         dt.format("%H"),
         dt.format("%M"),
         dt.format("%S"),
-        model
-    );
+        name
+    )
+}
 
-    let file_content_tokens = create_migration_tokens(&model, &mirgration_name);
+pub(super) fn create_migration(
+    model: &str,
+    fields: &[Field],
+    indexes: &[Index],
+    dt: NaiveDateTime,
+    migration_src_dir: &Path,
+) {
+    let mirgration_name = migration_name(model, dt);
+
+    let file_content_tokens =
+        create_migration_tokens(&[(model, fields, indexes)], &mirgration_name);
 
     let file_path = emit_generated_code(
         migration_src_dir,
@@ -32,13 +48,99 @@ pub(super) fn create_migration(model: &str, dt: NaiveDateTime, migration_src_dir
     ));
 }
 
-fn create_migration_tokens(model: &str, mirgration_name_str: &str) -> TokenStream {
-    let model = format_ident!("{}", model);
+/// Creates `baseline`, the migration creating every table of `tables` at once,
+/// in the given order, for an existing database.
+pub(super) fn create_baseline_migration(
+    tables: &[(&str, &[Field], &[Index])],
+    migration_name: &str,
+    migration_src_dir: &Path,
+) {
+    let file_content_tokens = create_migration_tokens(tables, migration_name);
+
+    let file_path = emit_generated_code(
+        migration_src_dir,
+        &format!("{}.rs", migration_name),
+        &file_content_tokens,
+    );
+
+    log_success(&format!(
+        "Successfully created baseline migration file: {}",
+        file_path.display()
+    ));
+}
+
+/// Migration creating the tables of the `(model, fields, indexes)` triples in
+/// order and dropping them in reverse. The Postgres types of enum columns are
+/// created before the tables and dropped after them.
+fn create_migration_tokens(
+    tables: &[(&str, &[Field], &[Index])],
+    mirgration_name_str: &str,
+) -> TokenStream {
+    let enums = tables
+        .iter()
+        .flat_map(|(model, fields, _)| {
+            fields
+                .iter()
+                .filter_map(move |field| Some((*model, field.enum_ident()?, field)))
+        })
+        .collect::<Vec<_>>();
+    let create_types = enums.iter().map(|(model, ident, _)| {
+        let model = format_ident!("{}", model);
+        quote! {
+            manager
+                .create_type(schema.create_enum_from_active_enum::<#model::#ident>())
+                .await?;
+        }
+    });
+    let drop_types = enums.iter().rev().map(|(model, _, field)| {
+        let name = syn::LitStr::new(&field.enum_name(model), proc_macro2::Span::call_site());
+        quote! {
+            manager
+                .drop_type(Type::drop().if_exists().name(Alias::new(#name)).to_owned())
+                .await?;
+        }
+    });
+    let (type_import, schema) = if enums.is_empty() {
+        (quote!(), quote!())
+    } else {
+        (
+            quote!(
+                use sea_orm::sea_query::extension::postgres::Type;
+            ),
+            quote!(let schema = Schema::new(DbBackend::Postgres);),
+        )
+    };
+    let create_indexes = tables
+        .iter()
+        .flat_map(|(model, _, indexes)| {
+            indexes
+                .iter()
+                .map(move |index| create_index_tokens(model, index))
+        })
+        .collect::<Vec<_>>();
+    let models = tables
+        .iter()
+        .map(|(model, _, _)| format_ident!("{}", model))
+        .collect::<Vec<_>>();
+    // Validation rules Postgres can express are enforced by the table too.
+    let create_stmts = tables.iter().zip(&models).map(|((_, fields, _), model)| {
+        let checks = fields
+            .iter()
+            .flat_map(|field| field.checks())
+            .collect::<Vec<_>>();
+        if checks.is_empty() {
+            quote!(get_seaorm_create_stmt(#model::Entity))
+        } else {
+            quote!(get_seaorm_create_stmt(#model::Entity)#(.check(Expr::cust(#checks)))*.to_owned())
+        }
+    });
+    let reversed_models = models.iter().rev();
     let migration_name_lit = syn::LitStr::new(mirgration_name_str, proc_macro2::Span::call_site());
 
     quote! {
-        use entity::#model;
+        #(use entity::#models;)*
         use sea_orm::{DbBackend, EntityTrait, Schema};
+        #type_import
         use sea_orm_migration::prelude::*;
 
         pub struct Migration;
@@ -65,23 +167,57 @@ fn create_migration_tokens(model: &str, mirgration_name_str: &str) -> TokenStrea
         #[async_trait::async_trait]
         impl MigrationTrait for Migration {
             async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
-                let stmts = vec![get_seaorm_create_stmt(#model::Entity)];
+                #schema
+                #(#create_types)*
+                let stmts = vec![#(#create_stmts),*];
 
                 for stmt in stmts {
                     manager.create_table(stmt.to_owned()).await?;
                 }
+                #(#create_indexes)*
 
                 Ok(())
             }
 
             async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
-                let stmts = vec![get_seaorm_drop_stmt(#model::Entity)];
+                let stmts = vec![#(get_seaorm_drop_stmt(#reversed_models::Entity)),*];
 
                 for stmt in stmts {
                     manager.drop_table(stmt.to_owned()).await?;
                 }
+                #(#drop_types)*
+
                 Ok(())
             }
         }
     }
 }
+
+fn create_index_tokens(model: &str, index: &Index) -> TokenStream {
+    let name = syn::LitStr::new(&index.name(model), proc_macro2::Span::call_site());
+    let columns = index
+        .columns
+        .iter()
+        .map(|c| format_ident!("{}", to_upper_camel(c)))
+        .collect::<Vec<_>>();
+    let unique = if index.unique {
+        quote!(.unique())
+    } else {
+        quote!()
+    };
+    let model = format_ident!("{}", model);
+
+    quote! {
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name(#name)
+                    .table(#model::Entity)
+                    #(.col(#model::Column::#columns))*
+                    #unique
+                    .to_owned(),
+            )
+            .await?;
+    }
+}
@@ -1,19 +1,206 @@
-use crate::g::migration::{creation::create_migration, registration::register_migration};
-use crate::style_print::log_error;
+use crate::g::migration::{
+    alteration::create_alter_migration,
+    auto::{create_auto_migration, diff, load_snapshot, read_entities, save_snapshot},
+    creation::{create_baseline_migration, create_migration, migration_name, timestamped_name},
+    registration::register_migration,
+};
+use crate::g::{read_dir, remove_generated_code, Field, Index};
+use crate::style_print::{log_error, log_success, log_white};
 use chrono::NaiveDateTime;
+use regex::Regex;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+pub use alteration::Alteration;
+pub(in crate::g) use auto::entity_column;
+
+mod alteration;
+mod auto;
 mod creation;
 mod registration;
 
-pub(in crate::g) fn process_migration(model: &str, dt: NaiveDateTime, gen_path: &Path) {
+pub(in crate::g) fn migration_file(model: &str, dt: NaiveDateTime, gen_path: &Path) -> PathBuf {
+    gen_path
+        .join("migration")
+        .join("src")
+        .join(format!("{}.rs", migration_name(model, dt)))
+}
+
+/// Timestamp of an existing `create_<model>_table` migration.
+pub(in crate::g) fn find_create_migration(model: &str, gen_path: &Path) -> Option<NaiveDateTime> {
+    let migration_src_dir = gen_path.join("migration").join("src");
+    let create_migration =
+        Regex::new(&format!(r"^m(\d{{8}}_\d{{6}})_create_{}_table\.rs$", model)).unwrap();
+
+    let mut files = read_dir(&migration_src_dir).unwrap_or_default();
+    files.sort();
+    files.iter().find_map(|file_name| {
+        let captures = create_migration.captures(file_name)?;
+        NaiveDateTime::parse_from_str(&captures[1], "%Y%m%d_%H%M%S").ok()
+    })
+}
+
+pub(in crate::g) fn process_migration(
+    model: &str,
+    fields: &[Field],
+    indexes: &[Index],
+    dt: NaiveDateTime,
+    gen_path: &Path,
+) {
+    let migration_src_dir = gen_path.join("migration").join("src");
+
+    fs::create_dir_all(migration_src_dir.as_path()).unwrap_or_else(|why| {
+        log_error(&format!("! {:?}", why.kind()));
+    });
+
+    create_migration(model, fields, indexes, dt, &migration_src_dir);
+    register_migration(&migration_name(model, dt), &migration_src_dir);
+}
+
+/// Adds the `baseline` migration creating the `(model, fields, indexes)` tables
+/// of an imported database, unless there is one already.
+pub(in crate::g) fn process_baseline_migration(
+    tables: &[(&str, &[Field], &[Index])],
+    dt: NaiveDateTime,
+    gen_path: &Path,
+) {
+    let migration_src_dir = gen_path.join("migration").join("src");
+    if let Some(existing) = find_migration("baseline", &migration_src_dir) {
+        log_error(&format!("Migration {} already exists", existing));
+        return;
+    }
+
+    fs::create_dir_all(migration_src_dir.as_path()).unwrap_or_else(|why| {
+        log_error(&format!("! {:?}", why.kind()));
+    });
+
+    let migration = timestamped_name("baseline", dt);
+    create_baseline_migration(tables, &migration, &migration_src_dir);
+    register_migration(&migration, &migration_src_dir);
+}
+
+/// Adds the `<name>` migration applying `alteration`, unless a migration of
+/// that name exists already.
+pub(in crate::g) fn process_alter_migration(
+    name: &str,
+    alteration: &Alteration,
+    dt: NaiveDateTime,
+    gen_path: &Path,
+) {
     let migration_src_dir = gen_path.join("migration").join("src");
+    if let Some(existing) = find_migration(name, &migration_src_dir) {
+        log_error(&format!("Migration {} already exists", existing));
+        return;
+    }
 
     fs::create_dir_all(migration_src_dir.as_path()).unwrap_or_else(|why| {
         log_error(&format!("! {:?}", why.kind()));
     });
 
-    create_migration(model, dt, &migration_src_dir);
-    register_migration(model, &migration_src_dir);
+    let migration = timestamped_name(name, dt);
+    create_alter_migration(alteration, &migration, &migration_src_dir);
+    register_migration(&migration, &migration_src_dir);
+    log_white(&format!(
+        "Update the `Model` in entity/src/{}.rs to match",
+        alteration.model()
+    ));
+}
+
+/// Adds a migration for the columns changed in the entities since the last
+/// run, named `name` or after the changed tables. The first run only records
+/// the entities in the snapshot.
+pub(in crate::g) fn process_auto_migration(name: Option<&str>, dt: NaiveDateTime, gen_path: &Path) {
+    let migration_dir = gen_path.join("migration");
+    let migration_src_dir = migration_dir.join("src");
+    let entities = match read_entities(&gen_path.join("entity").join("src")) {
+        Ok(entities) => entities,
+        Err(why) => {
+            log_error(&why);
+            return;
+        }
+    };
+
+    let snapshot = match load_snapshot(&migration_dir) {
+        Ok(Some(snapshot)) => snapshot,
+        Err(why) => {
+            log_error(&why);
+            return;
+        }
+        Ok(None) => {
+            save_snapshot(&migration_dir, &entities);
+            log_success("Recorded the current entities, later changes to them get a migration");
+            return;
+        }
+    };
+
+    let changes = diff(&snapshot, &entities);
+    if changes.is_empty() {
+        save_snapshot(&migration_dir, &entities);
+        log_white("No column changed since the last migration");
+        return;
+    }
+
+    let tables = changes.keys().cloned().collect::<Vec<_>>();
+    let name = match name {
+        Some(name) => name.to_string(),
+        None => format!("alter_{}_table", tables.join("_")),
+    };
+    if let Some(existing) = find_migration(&name, &migration_src_dir) {
+        log_error(&format!("Migration {} already exists", existing));
+        return;
+    }
+
+    let migration = timestamped_name(&name, dt);
+    create_auto_migration(&changes, &migration, &migration_src_dir);
+    register_migration(&migration, &migration_src_dir);
+    save_snapshot(&migration_dir, &entities);
+}
+
+/// Records the freshly generated entity of `model` in the snapshot used by
+/// `process_auto_migration`, if there is one yet.
+pub(in crate::g) fn snapshot_entity(model: &str, gen_path: &Path) {
+    let migration_dir = gen_path.join("migration");
+    let mut snapshot = match load_snapshot(&migration_dir) {
+        Ok(Some(snapshot)) => snapshot,
+        Ok(None) => return,
+        Err(why) => {
+            log_error(&why);
+            return;
+        }
+    };
+
+    let entities = match read_entities(&gen_path.join("entity").join("src")) {
+        Ok(entities) => entities,
+        Err(why) => {
+            log_error(&why);
+            return;
+        }
+    };
+    if let Some(columns) = entities.get(model) {
+        snapshot.insert(model.to_string(), columns.clone());
+        save_snapshot(&migration_dir, &snapshot);
+    }
+}
+
+/// File name of the migration called `name`, whatever its timestamp.
+fn find_migration(name: &str, migration_src_dir: &Path) -> Option<String> {
+    let same_name = Regex::new(&format!(r"^m\d{{8}}_\d{{6}}_{}\.rs$", name)).unwrap();
+    read_dir(migration_src_dir)
+        .unwrap_or_default()
+        .into_iter()
+        .find(|file_name| same_name.is_match(file_name))
+}
+
+pub(in crate::g) fn destroy_migration(model: &str, gen_path: &Path) {
+    let migration_src_dir = gen_path.join("migration").join("src");
+    let create_migration =
+        Regex::new(&format!(r"^m\d{{8}}_\d{{6}}_create_{}_table\.rs$", model)).unwrap();
+
+    for file_name in read_dir(&migration_src_dir).unwrap_or_default() {
+        if create_migration.is_match(&file_name)
+            && remove_generated_code(&migration_src_dir, &file_name)
+        {
+            register_migration(file_name.trim_end_matches(".rs"), &migration_src_dir);
+        }
+    }
 }
@@ -1,38 +1,51 @@
-use crate::g::{emit_generated_code, read_dir};
-use crate::style_print::log_success;
-use proc_macro2::TokenStream;
+use crate::g::registration::Registration;
+use crate::g::{format_generated_code, read_dir, write_generated_code};
+use crate::style_print::{log_error, log_success};
 use quote::{format_ident, quote};
 use std::path::Path;
 
-pub(super) fn register_migration(model: &str, migration_src_dir: &Path) {
-    let file_content_tokens = register_migration_tokens(&migration_src_dir);
+/// Syncs the `lib.rs` of the migration crate with its files after
+/// `migration`, the name of the added or removed migration, changed.
+pub(super) fn register_migration(migration: &str, migration_src_dir: &Path) {
+    let file_content = match register_migration_content(&migration_src_dir) {
+        Ok(file_content) => file_content,
+        Err(why) => {
+            log_error(&why);
+            return;
+        }
+    };
 
-    let file_path = emit_generated_code(migration_src_dir, "lib.rs", &file_content_tokens);
+    let file_path = write_generated_code(migration_src_dir, "lib.rs", &file_content);
 
     log_success(&format!(
-        "Successfully registered migration file for model `{}` in {}",
-        model,
+        "Successfully registered migration `{}` in {}",
+        migration,
         file_path.display()
     ));
 }
 
-fn register_migration_tokens(migration_src_dir: &&Path) -> TokenStream {
-    let files = read_dir(&migration_src_dir).unwrap();
+fn register_migration_content(migration_src_dir: &&Path) -> Result<String, String> {
+    let files = read_dir(migration_src_dir).unwrap();
     let mut files_box = files
         .iter()
-        .cloned()
-        .filter(|i| i != "lib.rs")
-        .filter(|i| i != "main.rs")
+        .filter(|&i| i != "lib.rs")
+        .filter(|&i| i != "main.rs")
         .map(|i| i.replace(".rs", ""))
         .collect::<Vec<_>>();
     files_box.sort();
 
+    if let Some(mut registration) = Registration::parse(migration_src_dir, "lib.rs")? {
+        let (added, stale) = registration.sync_mods(migration_src_dir, &files_box, false);
+        registration.sync_boxed("migrations", "Migration", &added, &stale);
+        return Ok(registration.into_source());
+    }
+
     let modules = files_box
         .iter()
         .map(|i| format_ident!("{}", i))
         .collect::<Vec<_>>();
 
-    quote! {
+    Ok(format_generated_code(&quote! {
         pub use sea_orm_migration::prelude::*;
 
         pub struct Migrator;
@@ -43,9 +56,9 @@ fn register_migration_tokens(migration_src_dir: &&Path) -> TokenStream {
         impl MigratorTrait for Migrator {
             fn migrations() -> Vec<Box<dyn MigrationTrait>> {
                 vec![
-                    #(Box::new(#modules::Migration))*
+                    #(Box::new(#modules::Migration)),*
                 ]
             }
         }
-    }
+    }))
 }
@@ -1,8 +1,26 @@
-use crate::g::entity::process_entity;
-use crate::g::graphql::mutation::process_graphql_mutation;
-use crate::g::graphql::query::process_graphql_query;
-use crate::g::migration::process_migration;
-use chrono::NaiveDateTime;
+use crate::g::conflict::Conflicts;
+use crate::g::entity::{
+    destroy_entity, entity_file, link_entity, link_join_entity, primary_key, process_entity,
+    process_join_entity,
+};
+use crate::g::factory::{destroy_factory, factory_file, process_factory};
+use crate::g::graphql::mutation::{
+    destroy_graphql_mutation, mutation_file, process_graphql_mutation,
+};
+use crate::g::graphql::query::{
+    destroy_graphql_query, process_graphql_join_query, process_graphql_query, query_file,
+};
+use crate::g::graphql::subscription::{
+    destroy_graphql_subscription, process_graphql_subscription, subscription_file,
+};
+use crate::g::migration::{
+    destroy_migration, find_create_migration, migration_file, process_alter_migration,
+    process_auto_migration, process_baseline_migration, process_migration, snapshot_entity,
+};
+use crate::g::seed::{destroy_seeder, process_seeder, seed_columns, seeder_file, seeder_time};
+use crate::g::test::{destroy_test, process_test, test_file};
+use crate::style_print::{log_error, log_success};
+use chrono::{Duration, NaiveDateTime};
 use convert_case::{Case, Casing};
 use proc_macro2::TokenStream;
 use std::io::Write;
@@ -10,12 +28,28 @@ use std::path::{Path, PathBuf};
 use std::{fs, io};
 use syn::File;
 
+pub use conflict::ConflictPolicy;
+pub use dry_run::dry_run;
+pub use field::{Field, FieldType, Rule};
+pub use migration::Alteration;
+pub use options::{ModelOptions, PrimaryKey, Timestamps};
+pub use schema::{Index, Schema, SchemaModel};
+
+mod conflict;
+mod dry_run;
 mod entity;
+mod factory;
+mod field;
 mod graphql;
-
+mod import;
 mod migration;
+mod options;
+mod registration;
+mod schema;
+mod seed;
+mod test;
 
-pub(self) fn read_dir<P: AsRef<Path>>(path: P) -> io::Result<Vec<String>> {
+fn read_dir<P: AsRef<Path>>(path: P) -> io::Result<Vec<String>> {
     Ok(fs::read_dir(path)?
         .filter_map(|entry| {
             let entry = entry.ok()?;
@@ -28,30 +62,361 @@ pub(self) fn read_dir<P: AsRef<Path>>(path: P) -> io::Result<Vec<String>> {
         .collect())
 }
 
-pub(self) fn to_upper_camel(s: &str) -> String {
+fn to_upper_camel(s: &str) -> String {
     s.to_case(Case::UpperCamel)
 }
 
-pub(self) fn emit_generated_code(
+fn emit_generated_code(
     mutation_dir: &Path,
     file_name: &str,
     file_content_tokens: &TokenStream,
 ) -> PathBuf {
+    let formatted = format_generated_code(file_content_tokens);
+    write_generated_code(mutation_dir, file_name, &formatted)
+}
+
+fn format_generated_code(file_content_tokens: &TokenStream) -> String {
     // formatting and pretty printing
     let syntax_tree = syn::parse_str::<File>(&file_content_tokens.to_string()).unwrap();
-    let formatted = prettyplease::unparse(&syntax_tree);
+    prettyplease::unparse(&syntax_tree)
+}
 
-    let file_path = mutation_dir.join(file_name);
+/// Writes `content` as is, like a registration file edited in place.
+fn write_generated_code(dir: &Path, file_name: &str, content: &str) -> PathBuf {
+    let file_path = dir.join(file_name);
     dbg!(&file_path);
     let mut file = fs::File::create(&file_path).unwrap();
 
-    file.write_all(formatted.as_bytes()).unwrap();
+    file.write_all(content.as_bytes()).unwrap();
     file_path
 }
 
-pub fn process_g(model: &str, dt: NaiveDateTime, gen_path: &Path) {
-    process_entity(model, gen_path);
-    process_migration(model, dt, gen_path);
-    process_graphql_mutation(model, gen_path);
-    process_graphql_query(model, gen_path);
+/// Deletes a generated file, returns whether there was one.
+fn remove_generated_code(dir: &Path, file_name: &str) -> bool {
+    let file_path = dir.join(file_name);
+    if !file_path.is_file() {
+        return false;
+    }
+
+    fs::remove_file(&file_path).unwrap();
+    log_success(&format!("Successfully removed {}", file_path.display()));
+    true
+}
+
+pub fn process_g(
+    model: &str,
+    fields: &[Field],
+    options: ModelOptions,
+    dt: NaiveDateTime,
+    gen_path: &Path,
+    on_conflict: ConflictPolicy,
+) {
+    generate_model(model, fields, options, &[], dt, gen_path, on_conflict);
+}
+
+/// Runs `process_g` for every model and `process_join` for every join of
+/// `schema`. Each new create migration is one second after the previous one
+/// so referenced tables are created first.
+pub fn process_schema(
+    schema: &Schema,
+    dt: NaiveDateTime,
+    gen_path: &Path,
+    on_conflict: ConflictPolicy,
+) {
+    let models = schema.models_in_dependency_order();
+    let mut dt = dt;
+    for model in &models {
+        generate_model(
+            &model.name,
+            &model.fields,
+            model.options(),
+            &model.indexes,
+            dt,
+            gen_path,
+            on_conflict,
+        );
+        dt += Duration::seconds(1);
+    }
+    for (left, right) in &schema.joins {
+        process_join(left, right, dt, gen_path, on_conflict);
+        dt += Duration::seconds(1);
+    }
+
+    // A regenerated entity loses the relations added by models that were
+    // skipped, so link everything once more.
+    for model in &models {
+        link_entity(&model.name, &model.fields, gen_path);
+    }
+    for (left, right) in &schema.joins {
+        link_join_entity(left, right, gen_path);
+    }
+}
+
+fn generate_model(
+    model: &str,
+    fields: &[Field],
+    options: ModelOptions,
+    indexes: &[Index],
+    dt: NaiveDateTime,
+    gen_path: &Path,
+    on_conflict: ConflictPolicy,
+) {
+    // A regenerated migration keeps its timestamp so it stays in order.
+    let dt = find_create_migration(model, gen_path).unwrap_or(dt);
+    let fields = &with_reference_types(model, options.pk, fields, gen_path);
+
+    let mut conflicts = Conflicts::new(on_conflict, gen_path);
+    let entity = conflicts.resolve(entity_file(model, gen_path));
+    let migration = conflicts.resolve(migration_file(model, dt, gen_path));
+    let mutation = conflicts.resolve(mutation_file(model, gen_path));
+    let query = conflicts.resolve(query_file(model, gen_path));
+    let subscription =
+        options.subscriptions && conflicts.resolve(subscription_file(model, gen_path));
+    let test = conflicts.resolve(test_file(model, gen_path));
+    let factory = conflicts.resolve(factory_file(model, gen_path));
+    if conflicts.refused() {
+        conflicts.log_summary();
+        return;
+    }
+
+    if entity {
+        process_entity(model, fields, options, gen_path);
+        snapshot_entity(model, gen_path);
+    }
+    if migration {
+        process_migration(model, fields, indexes, dt, gen_path);
+    }
+    if mutation {
+        process_graphql_mutation(model, fields, options, gen_path);
+    }
+    if query {
+        process_graphql_query(model, fields, options, gen_path);
+    }
+    if subscription {
+        process_graphql_subscription(model, options.pk, gen_path);
+    }
+    if test {
+        process_test(model, fields, options, gen_path);
+    }
+    if factory {
+        process_factory(model, fields, gen_path);
+    }
+    conflicts.log_summary();
+}
+
+pub fn process_join(
+    left: &str,
+    right: &str,
+    dt: NaiveDateTime,
+    gen_path: &Path,
+    on_conflict: ConflictPolicy,
+) {
+    let join = format!("{}_{}", left, right);
+    let dt = find_create_migration(&join, gen_path).unwrap_or(dt);
+
+    let mut conflicts = Conflicts::new(on_conflict, gen_path);
+    let entity = conflicts.resolve(entity_file(&join, gen_path));
+    let migration = conflicts.resolve(migration_file(&join, dt, gen_path));
+    let query = conflicts.resolve(query_file(&join, gen_path));
+    if conflicts.refused() {
+        conflicts.log_summary();
+        return;
+    }
+
+    if entity {
+        process_join_entity(left, right, gen_path);
+        snapshot_entity(&join, gen_path);
+    }
+    if migration {
+        process_migration(&join, &[], &[], dt, gen_path);
+    }
+    if query {
+        process_graphql_join_query(left, right, gen_path);
+    }
+    conflicts.log_summary();
+}
+
+/// Generates the entities, queries and mutations of every model and join of a
+/// `schema` read from an existing database, plus one `baseline` migration
+/// creating all of its tables. Models whose existing files may not be
+/// overwritten are left as they are.
+pub fn process_import(
+    schema: &Schema,
+    dt: NaiveDateTime,
+    gen_path: &Path,
+    on_conflict: ConflictPolicy,
+) {
+    let models = schema.models_in_dependency_order();
+    for model in &models {
+        let mut conflicts = Conflicts::new(on_conflict, gen_path);
+        let entity = conflicts.resolve(entity_file(&model.name, gen_path));
+        let mutation = conflicts.resolve(mutation_file(&model.name, gen_path));
+        let query = conflicts.resolve(query_file(&model.name, gen_path));
+        if conflicts.refused() {
+            conflicts.log_summary();
+            continue;
+        }
+
+        if entity {
+            process_entity(&model.name, &model.fields, model.options(), gen_path);
+            snapshot_entity(&model.name, gen_path);
+        }
+        if mutation {
+            process_graphql_mutation(&model.name, &model.fields, model.options(), gen_path);
+        }
+        if query {
+            process_graphql_query(&model.name, &model.fields, model.options(), gen_path);
+        }
+        conflicts.log_summary();
+    }
+
+    let mut tables = models
+        .iter()
+        .map(|model| {
+            (
+                model.name.clone(),
+                model.fields.as_slice(),
+                model.indexes.as_slice(),
+            )
+        })
+        .collect::<Vec<_>>();
+    for (left, right) in &schema.joins {
+        let join = format!("{}_{}", left, right);
+        tables.push((join.clone(), &[], &[]));
+        let mut conflicts = Conflicts::new(on_conflict, gen_path);
+        let entity = conflicts.resolve(entity_file(&join, gen_path));
+        let query = conflicts.resolve(query_file(&join, gen_path));
+        if conflicts.refused() {
+            conflicts.log_summary();
+            continue;
+        }
+
+        if entity {
+            process_join_entity(left, right, gen_path);
+            snapshot_entity(&join, gen_path);
+        }
+        if query {
+            process_graphql_join_query(left, right, gen_path);
+        }
+        conflicts.log_summary();
+    }
+
+    let tables = tables
+        .iter()
+        .map(|(table, fields, indexes)| (table.as_str(), *fields, *indexes))
+        .collect::<Vec<_>>();
+    process_baseline_migration(&tables, dt, gen_path);
+}
+
+/// `fields` with every reference typed like the primary key it points to,
+/// `pk` being the one of `model` itself.
+fn with_reference_types(
+    model: &str,
+    pk: PrimaryKey,
+    fields: &[Field],
+    gen_path: &Path,
+) -> Vec<Field> {
+    fields
+        .iter()
+        .map(|field| match &field.references {
+            Some(parent) => {
+                let parent_pk = if parent == model {
+                    pk
+                } else {
+                    primary_key(parent, gen_path)
+                };
+                Field {
+                    ty: parent_pk.field_type(),
+                    ..field.clone()
+                }
+            }
+            None => field.clone(),
+        })
+        .collect()
+}
+
+/// Generates the seeder inserting a sample row of an existing `model` and
+/// registers it with the `seed` binary.
+pub fn process_seed(model: &str, dt: NaiveDateTime, gen_path: &Path, on_conflict: ConflictPolicy) {
+    if !entity_file(model, gen_path).is_file() {
+        log_error(&format!(
+            "Entity `{}` not found, generate it with `zapp g model {}` first",
+            model, model
+        ));
+        return;
+    }
+    let fields = match seed_columns(model, gen_path) {
+        Ok(fields) => fields,
+        Err(why) => {
+            log_error(&why);
+            return;
+        }
+    };
+    let dt = seeder_time(model, dt, gen_path);
+
+    let mut conflicts = Conflicts::new(on_conflict, gen_path);
+    let seeder = conflicts.resolve(seeder_file(model, dt, gen_path));
+    if conflicts.refused() {
+        conflicts.log_summary();
+        return;
+    }
+
+    if seeder {
+        process_seeder(model, &fields, dt, gen_path);
+    }
+    conflicts.log_summary();
+}
+
+/// Generates the `<name>` migration altering an existing table.
+pub fn process_alter(name: &str, alteration: &Alteration, dt: NaiveDateTime, gen_path: &Path) {
+    let pk = primary_key(alteration.model(), gen_path);
+    let alteration = match alteration {
+        Alteration::AddColumns { model, fields } => Alteration::AddColumns {
+            model: model.clone(),
+            fields: with_reference_types(model, pk, fields, gen_path),
+        },
+        Alteration::RemoveColumns { model, fields } => Alteration::RemoveColumns {
+            model: model.clone(),
+            fields: with_reference_types(model, pk, fields, gen_path),
+        },
+        _ => alteration.clone(),
+    };
+    process_alter_migration(name, &alteration, dt, gen_path);
+}
+
+/// Models with an entity in `entity/src`, the tables a migration can alter.
+pub fn model_names(gen_path: &Path) -> Vec<String> {
+    let mut entity_files = read_dir(gen_path.join("entity").join("src")).unwrap_or_default();
+    entity_files.retain(|i| i != "lib.rs");
+    entity_files
+        .iter()
+        .filter_map(|i| i.strip_suffix(".rs"))
+        .map(|i| i.to_string())
+        .collect()
+}
+
+/// Generates a migration for the columns changed in the entity files since the
+/// last one.
+pub fn process_auto_alter(name: Option<&str>, dt: NaiveDateTime, gen_path: &Path) {
+    process_auto_migration(name, dt, gen_path);
+}
+
+/// Undoes `process_g` for `model`. The create migration is only removed when
+/// `keep_migration` is false, i.e. it has not been applied yet.
+pub fn process_destroy(model: &str, keep_migration: bool, gen_path: &Path) {
+    destroy_entity(model, gen_path);
+    if keep_migration {
+        log_error(&format!(
+            "Kept the `create_{}_table` migration, roll it back with `zapp db rollback` and remove it by hand",
+            model
+        ));
+    } else {
+        destroy_migration(model, gen_path);
+    }
+    destroy_graphql_mutation(model, gen_path);
+    destroy_graphql_query(model, gen_path);
+    destroy_graphql_subscription(model, gen_path);
+    destroy_test(model, gen_path);
+    destroy_factory(model, gen_path);
+    destroy_seeder(model, gen_path);
 }
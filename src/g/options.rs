@@ -0,0 +1,104 @@
+use crate::g::FieldType;
+use proc_macro2::TokenStream;
+use quote::quote;
+use serde::Deserialize;
+use std::str::FromStr;
+
+/// What to generate for a model besides its fields.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ModelOptions {
+    /// Adds a nullable `deleted_at` column, `delete_<model>` sets it and
+    /// queries leave such rows out.
+    pub soft_delete: bool,
+    pub pk: PrimaryKey,
+    pub timestamps: Timestamps,
+    /// Adds `<model>_created`, `<model>_updated` and `<model>_deleted`
+    /// subscriptions the mutations publish to.
+    pub subscriptions: bool,
+}
+
+/// Type of the `id` primary key.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PrimaryKey {
+    /// Auto incremented.
+    #[default]
+    I32,
+    /// Auto incremented.
+    I64,
+    /// Generated by the entity when a row is inserted, so ids cannot be
+    /// guessed.
+    Uuid,
+}
+
+impl PrimaryKey {
+    pub(in crate::g) fn field_type(&self) -> FieldType {
+        match self {
+            PrimaryKey::I32 => FieldType::I32,
+            PrimaryKey::I64 => FieldType::I64,
+            PrimaryKey::Uuid => FieldType::Uuid,
+        }
+    }
+
+    /// Rust type of the key as seen from a file importing the SeaORM prelude.
+    pub(in crate::g) fn rust_type(&self) -> TokenStream {
+        match self {
+            PrimaryKey::I32 => quote!(i32),
+            PrimaryKey::I64 => quote!(i64),
+            PrimaryKey::Uuid => quote!(Uuid),
+        }
+    }
+}
+
+/// `created_at` and `updated_at` columns, kept up to date by the
+/// `before_save` of the entity.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Timestamps {
+    /// `timestamp` columns.
+    #[default]
+    Naive,
+    /// `timestamp with time zone` columns.
+    Tz,
+    None,
+}
+
+impl Timestamps {
+    pub(in crate::g) fn field_type(&self) -> Option<FieldType> {
+        match self {
+            Timestamps::Naive => Some(FieldType::DateTime),
+            Timestamps::Tz => Some(FieldType::DateTimeTz),
+            Timestamps::None => None,
+        }
+    }
+
+    /// Type of `deleted_at`, which follows the timestamps.
+    pub(in crate::g) fn deleted_at_type(&self) -> FieldType {
+        self.field_type().unwrap_or(FieldType::DateTime)
+    }
+
+    /// Current time as a value of these columns, given `chrono::Utc` and the
+    /// SeaORM prelude are imported.
+    pub(in crate::g) fn now(&self) -> TokenStream {
+        match self {
+            Timestamps::Tz => quote!(DateTimeWithTimeZone::from(Utc::now())),
+            Timestamps::Naive | Timestamps::None => quote!(Utc::now().naive_utc()),
+        }
+    }
+}
+
+impl FromStr for PrimaryKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "i32" => Ok(PrimaryKey::I32),
+            "i64" => Ok(PrimaryKey::I64),
+            "uuid" => Ok(PrimaryKey::Uuid),
+            _ => Err(format!(
+                "unknown primary key type `{}`, use i32, i64 or uuid",
+                s
+            )),
+        }
+    }
+}
@@ -0,0 +1,538 @@
+use convert_case::{Case, Casing};
+use proc_macro2::{Ident, LineColumn, Span, TokenStream};
+use quote::{quote, ToTokens};
+use regex::Regex;
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+use syn::punctuated::{Pair, Punctuated};
+use syn::{parse_quote, Expr, Fields, File, Item, MacroDelimiter, Stmt, Token};
+
+/// An existing `mod.rs`/`lib.rs` registration file the generators only add
+/// their own entries to. Entries are spliced into the source text, so
+/// hand-written items, comments and blank lines in it survive regeneration.
+pub(in crate::g) struct Registration {
+    source: String,
+}
+
+/// A comma separated list in the source, like the members of `Query(..)`.
+struct List {
+    /// Offset right after the opening delimiter.
+    open: usize,
+    /// Offset of the closing delimiter.
+    close: usize,
+    entries: Vec<Entry>,
+    /// Offset right after the trailing comma, if there is one.
+    trailing_comma: Option<usize>,
+}
+
+struct Entry {
+    /// Module the entry belongs to, which orders the list.
+    key: Option<String>,
+    /// What the entry is matched by, the member or module name.
+    name: String,
+    range: Range<usize>,
+}
+
+impl Registration {
+    /// `None` when the file is missing. One that doesn't parse, e.g. while
+    /// it is being edited, is an error so it isn't rebuilt over the
+    /// hand-written items in it.
+    pub(in crate::g) fn parse(dir: &Path, file_name: &str) -> Result<Option<Self>, String> {
+        let file_path = dir.join(file_name);
+        let source = match fs::read_to_string(&file_path) {
+            Ok(source) => source,
+            Err(_) => return Ok(None),
+        };
+        syn::parse_file(&source).map_err(|why| {
+            format!(
+                "Could not parse {}: {}, left it untouched, fix it and run the generator again",
+                file_path.display(),
+                why
+            )
+        })?;
+        Ok(Some(Registration { source }))
+    }
+
+    /// Declares the `modules` that are missing and drops other declarations
+    /// whose source file is gone, returns the added and the dropped module
+    /// names.
+    pub(in crate::g) fn sync_mods(
+        &mut self,
+        dir: &Path,
+        modules: &[String],
+        public: bool,
+    ) -> (Vec<String>, Vec<String>) {
+        let vis = if public { quote!(pub) } else { quote!() };
+        self.sync_mods_as(dir, modules, vis)
+    }
+
+    /// Like `sync_mods`, declaring the missing modules as
+    /// `<head> mod <module>;`, e.g. with a `#[cfg(..)]` in the `head`.
+    pub(in crate::g) fn sync_mods_as(
+        &mut self,
+        dir: &Path,
+        modules: &[String],
+        head: TokenStream,
+    ) -> (Vec<String>, Vec<String>) {
+        let mod_name = |item: &Item| match item {
+            Item::Mod(item_mod) if item_mod.content.is_none() => Some(item_mod.ident.to_string()),
+            _ => None,
+        };
+
+        let stale = self
+            .syntax_tree()
+            .items
+            .iter()
+            .filter_map(mod_name)
+            .filter(|name| !modules.contains(name) && !module_exists(dir, name))
+            .collect::<Vec<_>>();
+        for name in &stale {
+            self.remove_items(|item| mod_name(item).as_ref() == Some(name));
+        }
+
+        let mut added = Vec::new();
+        for module in modules {
+            let items = self.syntax_tree().items;
+            if items.iter().any(|i| mod_name(i).as_ref() == Some(module)) {
+                continue;
+            }
+            added.push(module.clone());
+            let ident = quote::format_ident!("{}", module);
+            let fallback = items.iter().rposition(|item| matches!(item, Item::Use(_)));
+            let place = sorted_place(&items, module, mod_name, fallback);
+            self.insert_item(&items, place, parse_quote!(#head mod #ident;));
+        }
+        (added, stale)
+    }
+
+    /// Adds `pub use <module>::<member>;` for every pair that is missing and
+    /// drops the re-exports of `stale` modules.
+    pub(in crate::g) fn sync_uses(&mut self, uses: &[(String, Ident)], stale: &[String]) {
+        let use_root = |item: &Item| match item {
+            Item::Use(syn::ItemUse {
+                vis: syn::Visibility::Public(_),
+                tree: syn::UseTree::Path(path),
+                ..
+            }) => Some(path.ident.to_string()),
+            _ => None,
+        };
+
+        for name in stale {
+            self.remove_items(|item| use_root(item).as_ref() == Some(name));
+        }
+
+        for (module, member) in uses {
+            let ident = quote::format_ident!("{}", module);
+            let item: Item = parse_quote!(pub use #ident::#member;);
+            let items = self.syntax_tree().items;
+            let exists = items
+                .iter()
+                .any(|i| i.to_token_stream().to_string() == item.to_token_stream().to_string());
+            if exists {
+                continue;
+            }
+            let fallback = items.iter().rposition(|item| matches!(item, Item::Mod(_)));
+            let place = sorted_place(&items, module, use_root, fallback);
+            self.insert_item(&items, place, item);
+        }
+    }
+
+    /// Adds the missing `members` to the tuple struct `name`, e.g. the
+    /// `MergedObject` behind `Query`, and drops the `stale` ones. Members are
+    /// named after their module with `name` as suffix, like `UserQuery`.
+    pub(in crate::g) fn sync_struct_members(
+        &mut self,
+        name: &str,
+        members: &[(String, Ident)],
+        stale: &[Ident],
+    ) {
+        let additions = members
+            .iter()
+            .map(|(module, member)| (module.clone(), member.to_string(), member.to_string()))
+            .collect::<Vec<_>>();
+        let stale = stale.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        self.sync_list(|r| r.struct_members(name), &additions, &stale);
+    }
+
+    /// Adds `Box::new(<module>::<member>)` for the missing `modules` to the
+    /// `vec![...]` returned by the `function` method, like `migrations()` of
+    /// the `Migrator`, and drops the `stale` ones.
+    pub(in crate::g) fn sync_boxed(
+        &mut self,
+        function: &str,
+        member: &str,
+        modules: &[String],
+        stale: &[String],
+    ) {
+        let additions = modules
+            .iter()
+            .map(|module| {
+                let text = format!("Box::new({}::{})", module, member);
+                (module.clone(), module.clone(), text)
+            })
+            .collect::<Vec<_>>();
+        self.sync_list(|r| r.boxed_entries(function, member), &additions, stale);
+    }
+
+    pub(in crate::g) fn into_source(self) -> String {
+        self.source
+    }
+
+    fn syntax_tree(&self) -> File {
+        // Every edit keeps the file parsable, it was checked in `parse`.
+        syn::parse_file(&self.source).unwrap()
+    }
+
+    /// Drops the `stale` entries of the list found by `locate` and adds the
+    /// `(key, name, text)` additions whose name is missing, ordered by key.
+    fn sync_list<F: Fn(&Self) -> Option<List>>(
+        &mut self,
+        locate: F,
+        additions: &[(String, String, String)],
+        stale: &[String],
+    ) {
+        let mut removed = false;
+        while let Some(list) = locate(self) {
+            match list.entries.iter().position(|e| stale.contains(&e.name)) {
+                Some(index) => self.remove_entry(&list, index),
+                None => break,
+            }
+            removed = true;
+        }
+        if let Some(list) = locate(self).filter(|_| removed) {
+            self.unwrap_short_list(&list);
+        }
+
+        for (key, name, text) in additions {
+            let list = match locate(self) {
+                Some(list) => list,
+                None => return,
+            };
+            if list.entries.iter().any(|e| e.name == *name) {
+                continue;
+            }
+            let fallback = list.entries.len().checked_sub(1);
+            let position = match sorted_place(&list.entries, key, |e| e.key.clone(), fallback) {
+                Place::Before(i) => i,
+                Place::After(i) => i + 1,
+            };
+            self.insert_entry(&list, position, text);
+        }
+
+        if let Some(list) = locate(self) {
+            self.wrap_long_list(&list);
+        }
+    }
+
+    fn struct_members(&self, name: &str) -> Option<List> {
+        let syntax_tree = self.syntax_tree();
+        let fields = syntax_tree.items.iter().find_map(|item| match item {
+            Item::Struct(item_struct) if item_struct.ident == name => Some(&item_struct.fields),
+            _ => None,
+        });
+        let fields = match fields {
+            Some(Fields::Unnamed(fields)) => fields,
+            _ => return None,
+        };
+
+        let entries = fields
+            .unnamed
+            .iter()
+            .map(|field| {
+                let member = field.ty.to_token_stream().to_string();
+                Entry {
+                    key: member
+                        .strip_suffix(name)
+                        .map(|module| module.to_case(Case::Snake)),
+                    name: member,
+                    range: self.range(field),
+                }
+            })
+            .collect();
+        Some(List {
+            open: self.offset(fields.paren_token.span.start()) + 1,
+            close: self.offset(fields.paren_token.span.end()) - 1,
+            entries,
+            trailing_comma: self.trailing_comma(&fields.unnamed),
+        })
+    }
+
+    fn boxed_entries(&self, function: &str, member: &str) -> Option<List> {
+        let boxed_module = Regex::new(&format!(r"\(\s*(\w+)\s*::\s*{}\s*\)", member)).unwrap();
+        let module_name = |expr: &Expr| {
+            boxed_module
+                .captures(&expr.to_token_stream().to_string())
+                .map(|c| c[1].to_string())
+        };
+
+        let syntax_tree = self.syntax_tree();
+        let vec_macro = syntax_tree
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Impl(item_impl) => Some(item_impl),
+                _ => None,
+            })
+            .flat_map(|item_impl| item_impl.items.iter())
+            .find_map(|impl_item| match impl_item {
+                syn::ImplItem::Method(method) if method.sig.ident == function => {
+                    method.block.stmts.iter().find_map(|stmt| match stmt {
+                        Stmt::Expr(Expr::Macro(expr_macro)) => Some(&expr_macro.mac),
+                        _ => None,
+                    })
+                }
+                _ => None,
+            });
+        let vec_macro = match vec_macro {
+            Some(vec_macro) if vec_macro.path.is_ident("vec") => vec_macro,
+            _ => return None,
+        };
+        let open = match &vec_macro.delimiter {
+            MacroDelimiter::Paren(d) => d.span,
+            MacroDelimiter::Brace(d) => d.span,
+            MacroDelimiter::Bracket(d) => d.span,
+        };
+        let entries = vec_macro
+            .parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+            .ok()?;
+
+        Some(List {
+            open: self.offset(open.start()) + 1,
+            close: self.offset(open.end()) - 1,
+            entries: entries
+                .iter()
+                .map(|expr| Entry {
+                    key: module_name(expr),
+                    name: module_name(expr).unwrap_or_default(),
+                    range: self.range(expr),
+                })
+                .collect(),
+            trailing_comma: self.trailing_comma(&entries),
+        })
+    }
+
+    /// Removes the items matching `predicate` together with their lines.
+    fn remove_items<F: Fn(&Item) -> bool>(&mut self, predicate: F) {
+        while let Some(item) = self.syntax_tree().items.into_iter().find(|i| predicate(i)) {
+            let range = self.range(&item);
+            let line_start = self.source[..range.start].rfind('\n').map_or(0, |i| i + 1);
+            let start = if self.source[line_start..range.start].trim().is_empty() {
+                line_start
+            } else {
+                range.start
+            };
+            let rest = &self.source[range.end..];
+            let line_end = rest.find('\n').map_or(rest.len(), |i| i + 1);
+            let end = if rest[..line_end].trim().is_empty() {
+                range.end + line_end
+            } else {
+                range.end
+            };
+            self.source.replace_range(start..end, "");
+        }
+    }
+
+    /// Inserts `item` on its own line next to the item given by `place`.
+    fn insert_item(&mut self, items: &[Item], place: Place, item: Item) {
+        let text = prettyplease::unparse(&parse_quote!(#item));
+        let text = text.trim_end();
+        match place {
+            Place::After(i) => {
+                let end = self.range(&items[i]).end;
+                let at = self.source[end..]
+                    .find('\n')
+                    .map_or(self.source.len(), |n| end + n);
+                self.source.insert_str(at, &format!("\n{}", text));
+            }
+            Place::Before(i) if i < items.len() => {
+                let at = self.range(&items[i]).start;
+                let line_start = self.source[..at].rfind('\n').map_or(0, |n| n + 1);
+                let indent = &self.source[line_start..at];
+                let indent = if indent.trim().is_empty() { indent } else { "" };
+                let text = format!("{}\n{}", text, indent);
+                self.source.insert_str(at, &text);
+            }
+            Place::Before(_) => self.source.insert_str(0, &format!("{}\n", text)),
+        }
+    }
+
+    fn remove_entry(&mut self, list: &List, index: usize) {
+        let entries = &list.entries;
+        let range = match (index.checked_sub(1), entries.get(index + 1)) {
+            (_, Some(next)) => entries[index].range.start..next.range.start,
+            (Some(previous), None) => entries[previous].range.end..entries[index].range.end,
+            (None, None) => {
+                entries[index].range.start..list.trailing_comma.unwrap_or(entries[index].range.end)
+            }
+        };
+        self.source.replace_range(range, "");
+    }
+
+    /// Inserts `text` before `list.entries[position]`, or after the last
+    /// entry, separated like the entries already are.
+    fn insert_entry(&mut self, list: &List, position: usize, text: &str) {
+        let entries = &list.entries;
+        if entries.is_empty() {
+            self.source.insert_str(list.open, text);
+            return;
+        }
+        if position < entries.len() {
+            let separator = self.separator(list, position);
+            let at = entries[position].range.start;
+            self.source
+                .insert_str(at, &format!("{},{}", text, separator));
+            return;
+        }
+        let separator = self.separator(list, entries.len() - 1);
+        match list.trailing_comma {
+            Some(at) => self
+                .source
+                .insert_str(at, &format!("{}{},", separator, text)),
+            None => {
+                let at = entries[entries.len() - 1].range.end;
+                self.source
+                    .insert_str(at, &format!(",{}{}", separator, text));
+            }
+        }
+    }
+
+    /// Whitespace in front of `list.entries[index]`: its line break and
+    /// indentation when the list has one entry per line, a space otherwise.
+    fn separator(&self, list: &List, index: usize) -> String {
+        let start = match index.checked_sub(1) {
+            Some(previous) => list.entries[previous].range.end,
+            None => list.open,
+        };
+        let before = &self.source[start..list.entries[index].range.start];
+        match before.rfind('\n') {
+            Some(i) => format!("\n{}", &before[i + 1..]),
+            None => " ".to_string(),
+        }
+    }
+
+    /// Puts every entry of a single line list on its own line once the line
+    /// gets longer than rustfmt allows.
+    fn wrap_long_list(&mut self, list: &List) {
+        let line_start = self.source[..list.open].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = self.source[list.close..]
+            .find('\n')
+            .map_or(self.source.len(), |i| list.close + i);
+        let line = &self.source[line_start..line_end];
+        if line.chars().count() <= 100 || self.source[list.open..list.close].contains('\n') {
+            return;
+        }
+
+        let indent = line.len() - line.trim_start().len();
+        let indent = &line[..indent];
+        let entries = list
+            .entries
+            .iter()
+            .map(|e| format!("{}    {}", indent, &self.source[e.range.clone()]))
+            .collect::<Vec<_>>();
+        let trailing_comma = if list.trailing_comma.is_some() {
+            ","
+        } else {
+            ""
+        };
+        let wrapped = format!("\n{}{}\n{}", entries.join(",\n"), trailing_comma, indent);
+        self.source.replace_range(list.open..list.close, &wrapped);
+    }
+
+    /// Puts a list wrapped by `wrap_long_list` back on one line once it fits
+    /// again. Lists with anything but their entries inside are left as is.
+    fn unwrap_short_list(&mut self, list: &List) {
+        let mut rest = self.source[list.open..list.close].to_string();
+        for entry in list.entries.iter().rev() {
+            rest.replace_range(
+                entry.range.start - list.open..entry.range.end - list.open,
+                "",
+            );
+        }
+        if !rest.contains('\n') || rest.chars().any(|c| c != ',' && !c.is_whitespace()) {
+            return;
+        }
+
+        let entries = list
+            .entries
+            .iter()
+            .map(|e| &self.source[e.range.clone()])
+            .collect::<Vec<_>>()
+            .join(", ");
+        let line_start = self.source[..list.open].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = self.source[list.close..]
+            .find('\n')
+            .map_or(self.source.len(), |i| list.close + i);
+        let length = list.open - line_start + entries.len() + line_end - list.close;
+        if length <= 100 {
+            self.source.replace_range(list.open..list.close, &entries);
+        }
+    }
+
+    fn trailing_comma<T>(&self, punctuated: &Punctuated<T, Token![,]>) -> Option<usize> {
+        match punctuated.pairs().last() {
+            Some(Pair::Punctuated(_, comma)) => Some(self.offset(comma.span.end())),
+            _ => None,
+        }
+    }
+
+    /// Byte range of the parsed `node` in the source.
+    fn range<T: ToTokens>(&self, node: &T) -> Range<usize> {
+        let tokens = node.to_token_stream();
+        let span =
+            |tt: Option<proc_macro2::TokenTree>| tt.map_or_else(Span::call_site, |t| t.span());
+        let first = span(tokens.clone().into_iter().next());
+        let last = span(tokens.into_iter().last());
+        self.offset(first.start())..self.offset(last.end())
+    }
+
+    fn offset(&self, location: LineColumn) -> usize {
+        let line_start = self
+            .source
+            .split_inclusive('\n')
+            .take(location.line - 1)
+            .map(str::len)
+            .sum::<usize>();
+        let line = &self.source[line_start..];
+        line_start
+            + line
+                .char_indices()
+                .nth(location.column)
+                .map_or(line.len(), |(i, _)| i)
+    }
+}
+
+/// Whether `name` is backed by `name.rs` or `name/mod.rs` in `dir`.
+pub(in crate::g) fn module_exists(dir: &Path, name: &str) -> bool {
+    dir.join(format!("{}.rs", name)).is_file() || dir.join(name).join("mod.rs").is_file()
+}
+
+/// Where to insert an entry, relative to the existing ones.
+enum Place {
+    Before(usize),
+    After(usize),
+}
+
+/// Where to insert an entry keyed `key`: before the first entry keyed after
+/// it, after the last keyed entry when there is none, and after `fallback`
+/// when nothing is keyed.
+fn sorted_place<T, F: Fn(&T) -> Option<String>>(
+    items: &[T],
+    key: &str,
+    key_of: F,
+    fallback: Option<usize>,
+) -> Place {
+    let keyed = items
+        .iter()
+        .enumerate()
+        .filter_map(|(i, item)| key_of(item).map(|k| (i, k)))
+        .collect::<Vec<_>>();
+
+    match keyed.iter().find(|(_, k)| k.as_str() > key) {
+        Some((i, _)) => Place::Before(*i),
+        None => match keyed.last().map(|(i, _)| *i).or(fallback) {
+            Some(i) => Place::After(i),
+            None => Place::Before(0),
+        },
+    }
+}
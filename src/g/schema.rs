@@ -0,0 +1,168 @@
+use crate::g::{Field, ModelOptions, PrimaryKey, Timestamps};
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+const GENERATED_COLUMNS: [&str; 3] = ["id", "created_at", "updated_at"];
+
+/// Every model of an app described in one `zapp.schema.yaml`:
+///
+/// ```yaml
+/// models:
+///   - name: user
+///     fields: [name:string, bio:text?]
+///     indexes:
+///       - columns: [name]
+///         unique: true
+///   - name: post
+///     fields: [title:string, user:references]
+///     soft_delete: true
+///     pk: uuid
+///     timestamps: tz
+/// joins:
+///   - [user, post]
+/// ```
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Schema {
+    #[serde(default)]
+    pub models: Vec<SchemaModel>,
+    #[serde(default)]
+    pub joins: Vec<(String, String)>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SchemaModel {
+    pub name: String,
+    #[serde(default)]
+    pub fields: Vec<Field>,
+    #[serde(default)]
+    pub indexes: Vec<Index>,
+    #[serde(default)]
+    pub soft_delete: bool,
+    #[serde(default)]
+    pub pk: PrimaryKey,
+    /// `naive`, `tz` or `none`.
+    #[serde(default)]
+    pub timestamps: Timestamps,
+    #[serde(default)]
+    pub subscriptions: bool,
+}
+
+impl SchemaModel {
+    pub fn options(&self) -> ModelOptions {
+        ModelOptions {
+            soft_delete: self.soft_delete,
+            pk: self.pk,
+            timestamps: self.timestamps,
+            subscriptions: self.subscriptions,
+        }
+    }
+}
+
+/// Index created by the `create_<model>_table` migration.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Index {
+    pub columns: Vec<String>,
+    #[serde(default)]
+    pub unique: bool,
+    /// Defaults to `idx_<model>_<columns>`.
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+impl Index {
+    pub(in crate::g) fn name(&self, model: &str) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("idx_{}_{}", model, self.columns.join("_")),
+        }
+    }
+}
+
+impl Schema {
+    pub fn load(file_path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(file_path)
+            .map_err(|why| format!("Cannot read {}: {}", file_path.display(), why))?;
+        let schema = serde_yaml::from_str::<Schema>(&content)
+            .map_err(|why| format!("Invalid {}: {}", file_path.display(), why))?;
+        schema.validate()?;
+        Ok(schema)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let snake_case = Regex::new("^[a-z][a-z0-9_]*$").unwrap();
+        let mut names = HashSet::new();
+        for model in &self.models {
+            if !snake_case.is_match(&model.name) {
+                return Err(format!("model name `{}` must be snake_case", model.name));
+            }
+            if !names.insert(model.name.as_str()) {
+                return Err(format!("model `{}` is declared twice", model.name));
+            }
+            if model.soft_delete && model.fields.iter().any(|f| f.name == "deleted_at") {
+                return Err(format!(
+                    "`{}` has a `deleted_at` field, it is added by `soft_delete`",
+                    model.name
+                ));
+            }
+            for index in &model.indexes {
+                if index.columns.is_empty() {
+                    return Err(format!("index of `{}` has no columns", model.name));
+                }
+                for column in &index.columns {
+                    let timestamp = column == "created_at" || column == "updated_at";
+                    let generated = GENERATED_COLUMNS.contains(&column.as_str())
+                        && !(timestamp && model.timestamps == Timestamps::None);
+                    let known = generated || model.fields.iter().any(|f| &f.name == column);
+                    if !known {
+                        return Err(format!(
+                            "index column `{}` is not a field of `{}`",
+                            column, model.name
+                        ));
+                    }
+                }
+            }
+        }
+        for (left, right) in &self.joins {
+            for model in [left, right] {
+                if !names.contains(model.as_str()) {
+                    return Err(format!("joined model `{}` is not declared", model));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Models ordered so that every model comes after the ones it references,
+    /// otherwise in the order of the file.
+    pub(in crate::g) fn models_in_dependency_order(&self) -> Vec<&SchemaModel> {
+        fn visit<'a>(
+            model: &'a SchemaModel,
+            schema: &'a Schema,
+            visited: &mut HashSet<&'a str>,
+            ordered: &mut Vec<&'a SchemaModel>,
+        ) {
+            if !visited.insert(&model.name) {
+                return;
+            }
+            for parent in model.fields.iter().filter_map(|f| f.references.as_deref()) {
+                if let Some(parent) = schema.models.iter().find(|m| m.name == parent) {
+                    visit(parent, schema, visited, ordered);
+                }
+            }
+            ordered.push(model);
+        }
+
+        let mut visited = HashSet::new();
+        let mut ordered = Vec::new();
+        for model in &self.models {
+            visit(model, self, &mut visited, &mut ordered);
+        }
+        ordered
+    }
+}
@@ -0,0 +1,96 @@
+use crate::g::{emit_generated_code, Field, FieldType, PrimaryKey};
+use crate::style_print::log_success;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use std::path::Path;
+
+pub(super) fn create_seeder(
+    model: &str,
+    seeder_name: &str,
+    pk: PrimaryKey,
+    fields: &[Field],
+    seed_dir: &Path,
+) {
+    let file_content_tokens = create_seeder_tokens(model, pk, fields);
+
+    let file_path = emit_generated_code(
+        seed_dir,
+        &format!("{}.rs", seeder_name),
+        &file_content_tokens,
+    );
+
+    log_success(&format!(
+        "Successfully created seeder for `{}`: {}",
+        model,
+        file_path.display()
+    ));
+}
+
+fn create_seeder_tokens(model: &str, pk: PrimaryKey, fields: &[Field]) -> TokenStream {
+    let model_name = format_ident!("{}", model);
+    // A uuid is set to the sample the seeders of referencing models point at,
+    // the row is skipped when it is already there so seeding can run again.
+    let uuid = pk == PrimaryKey::Uuid;
+    let existing_check = uuid.then(|| {
+        let (id, _) = pk.field_type().sample();
+        quote! {
+            let id: Uuid = #id;
+            let existing = #model_name::Entity::find()
+                .filter(#model_name::Column::Id.eq(id))
+                .one(db)
+                .await?;
+            if existing.is_some() {
+                return Ok(());
+            }
+        }
+    });
+    let set_id = uuid.then(|| quote!(id: Set(id),));
+    let columns = fields.iter().map(|f| {
+        let name = f.ident();
+        let (value, _) = f.sample(&model_name);
+        quote!(#name: Set(#value),)
+    });
+    let mut imports = vec!["ActiveModelTrait", "DatabaseConnection", "DbErr"];
+    if uuid {
+        imports.extend(["ColumnTrait", "EntityTrait", "QueryFilter"]);
+    }
+    if uuid || !fields.is_empty() {
+        imports.push("Set");
+    }
+    imports.sort_unstable();
+    let imports = imports.into_iter().map(|i| format_ident!("{}", i));
+    let mut prelude_imports = Vec::new();
+    if fields.iter().any(|f| f.ty == FieldType::Json) {
+        prelude_imports.push(quote!(Json));
+    }
+    if uuid {
+        prelude_imports.push(quote!(Uuid));
+    }
+    let prelude_import = (!prelude_imports.is_empty())
+        .then(|| quote! { use sea_orm::prelude::{#(#prelude_imports),*}; });
+
+    quote! {
+        use crate::SeederTrait;
+        use entity::#model_name;
+        #prelude_import
+        use sea_orm::{#(#imports),*};
+
+        pub struct Seeder;
+
+        #[async_trait::async_trait]
+        impl SeederTrait for Seeder {
+            async fn run(&self, db: &DatabaseConnection) -> Result<(), DbErr> {
+                #existing_check
+                #model_name::ActiveModel {
+                    #set_id
+                    #(#columns)*
+                    ..Default::default()
+                }
+                .insert(db)
+                .await?;
+
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use crate::g::entity::{entity_file, primary_key};
+use crate::g::migration::entity_column;
+use crate::g::seed::{creation::create_seeder, registration::register_seeder};
+use crate::g::{read_dir, remove_generated_code, to_upper_camel, Field, FieldType, Rule};
+use crate::style_print::log_error;
+use chrono::{Duration, NaiveDateTime, Timelike};
+use quote::ToTokens;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use syn::Item;
+
+mod creation;
+mod registration;
+
+/// Columns the database or the entity fills in.
+const GENERATED_COLUMNS: [&str; 4] = ["id", "created_at", "updated_at", "deleted_at"];
+
+/// `seed_<model>` prefixed with the `sYYYYMMDD_HHMMSS_` that orders seeders.
+fn seeder_name(model: &str, dt: NaiveDateTime) -> String {
+    format!("s{}_seed_{}", dt.format("%Y%m%d_%H%M%S"), model)
+}
+
+fn seed_dir(gen_path: &Path) -> PathBuf {
+    gen_path.join("src").join("bin").join("seed")
+}
+
+pub(in crate::g) fn seeder_file(model: &str, dt: NaiveDateTime, gen_path: &Path) -> PathBuf {
+    seed_dir(gen_path).join(format!("{}.rs", seeder_name(model, dt)))
+}
+
+/// Timestamps and models of the seeders in `src/bin/seed`, oldest first.
+fn seeders(gen_path: &Path) -> Vec<(NaiveDateTime, String)> {
+    let seeder = Regex::new(r"^s(\d{8}_\d{6})_seed_(\w+)\.rs$").unwrap();
+
+    let mut files = read_dir(seed_dir(gen_path)).unwrap_or_default();
+    files.sort();
+    files
+        .iter()
+        .filter_map(|file_name| {
+            let captures = seeder.captures(file_name)?;
+            let dt = NaiveDateTime::parse_from_str(&captures[1], "%Y%m%d_%H%M%S").ok()?;
+            Some((dt, captures[2].to_string()))
+        })
+        .collect()
+}
+
+/// Timestamp of an existing seeder of `model`.
+fn find_seeder(model: &str, gen_path: &Path) -> Option<NaiveDateTime> {
+    seeders(gen_path)
+        .into_iter()
+        .find_map(|(dt, name)| (name == model).then_some(dt))
+}
+
+/// Timestamp of the existing seeder of `model`, otherwise `dt` moved after
+/// every other seeder so they run in the order they were generated.
+pub(in crate::g) fn seeder_time(model: &str, dt: NaiveDateTime, gen_path: &Path) -> NaiveDateTime {
+    if let Some(existing) = find_seeder(model, gen_path) {
+        return existing;
+    }
+    // Names only keep the seconds.
+    match seeders(gen_path).last() {
+        Some((latest, _)) if *latest >= dt.with_nanosecond(0).unwrap() => {
+            *latest + Duration::seconds(1)
+        }
+        _ => dt,
+    }
+}
+
+pub(in crate::g) fn process_seeder(
+    model: &str,
+    fields: &[Field],
+    dt: NaiveDateTime,
+    gen_path: &Path,
+) {
+    let seed_dir = seed_dir(gen_path);
+
+    fs::create_dir_all(seed_dir.as_path()).unwrap_or_else(|why| {
+        log_error(&format!("! {:?}", why.kind()));
+    });
+
+    let pk = primary_key(model, gen_path);
+    create_seeder(model, &seeder_name(model, dt), pk, fields, &seed_dir);
+    register_seeder(model, &seed_dir);
+}
+
+pub(in crate::g) fn destroy_seeder(model: &str, gen_path: &Path) {
+    let seed_dir = seed_dir(gen_path);
+
+    if let Some(dt) = find_seeder(model, gen_path) {
+        if remove_generated_code(&seed_dir, &format!("{}.rs", seeder_name(model, dt))) {
+            register_seeder(model, &seed_dir);
+        }
+    }
+}
+
+/// Columns of the `model` entity a seeder fills in, along with the values of
+/// its enums and the validation rules of its `Create<Model>Input`.
+pub(in crate::g) fn seed_columns(model: &str, gen_path: &Path) -> Result<Vec<Field>, String> {
+    let content = fs::read_to_string(entity_file(model, gen_path))
+        .map_err(|why| format!("Could not read entity/src/{}.rs: {:?}", model, why.kind()))?;
+    let syntax_tree = syn::parse_file(&content)
+        .map_err(|why| format!("Could not parse entity/src/{}.rs: {}", model, why))?;
+    let enums = syntax_tree
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Enum(item_enum) => Some(item_enum),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let model_struct = syntax_tree.items.iter().find_map(|item| match item {
+        Item::Struct(item_struct) if item_struct.ident == "Model" => Some(item_struct),
+        _ => None,
+    });
+    let model_struct = match model_struct {
+        Some(model_struct) => model_struct,
+        None => return Ok(Vec::new()),
+    };
+    let string_value = Regex::new(r#"string_value\s*=\s*"([^"]*)""#).unwrap();
+    let mut rules = input_rules(model, gen_path);
+
+    let columns = model_struct
+        .fields
+        .iter()
+        .filter_map(|field| {
+            let name = field.ident.as_ref()?.to_string();
+            if GENERATED_COLUMNS.contains(&name.as_str()) {
+                return None;
+            }
+            let ty = field.ty.to_token_stream().to_string();
+            let item_enum = enums.iter().find(|item_enum| {
+                item_enum.ident == ty || ty == format!("Option < {} >", item_enum.ident)
+            });
+            let column = match item_enum {
+                Some(item_enum) => Some(Field {
+                    name: name.clone(),
+                    ty: FieldType::Enum(
+                        item_enum
+                            .variants
+                            .iter()
+                            .flat_map(|v| v.attrs.iter())
+                            .filter_map(|attr| {
+                                let tokens = attr.tokens.to_string();
+                                Some(string_value.captures(&tokens)?[1].to_string())
+                            })
+                            .collect(),
+                    ),
+                    nullable: ty.starts_with("Option"),
+                    default: None,
+                    references: None,
+                    rules: Vec::new(),
+                }),
+                None => entity_column(&name, field),
+            };
+            match column {
+                Some(column) => Some(Field {
+                    rules: rules.remove(&name).unwrap_or_default(),
+                    ..column
+                }),
+                None => {
+                    log_error(&format!(
+                        "Left column `{}` out of the `{}` seeder, its type is not supported",
+                        name, model
+                    ));
+                    None
+                }
+            }
+        })
+        .collect();
+    Ok(columns)
+}
+
+/// Rules of the `#[graphql(validator(...))]` fields of `Create<Model>Input`.
+fn input_rules(model: &str, gen_path: &Path) -> HashMap<String, Vec<Rule>> {
+    let mutation_file = gen_path
+        .join("src")
+        .join("graphql")
+        .join("mutation")
+        .join(format!("{}.rs", model));
+    let syntax_tree = match fs::read_to_string(mutation_file)
+        .ok()
+        .and_then(|content| syn::parse_file(&content).ok())
+    {
+        Some(syntax_tree) => syntax_tree,
+        None => return HashMap::new(),
+    };
+    let input = format!("Create{}Input", to_upper_camel(model));
+    let email = Regex::new(r"\bemail\b").unwrap();
+    let len = Regex::new(r"min_length=(\d+),max_length=(\d+)").unwrap();
+    let range = Regex::new(r"minimum=(-?\d+),maximum=(-?\d+)").unwrap();
+
+    let mut rules = HashMap::new();
+    for item in &syntax_tree.items {
+        let item_struct = match item {
+            Item::Struct(item_struct) if item_struct.ident == input => item_struct,
+            _ => continue,
+        };
+        for field in &item_struct.fields {
+            let validators = field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path.is_ident("graphql"))
+                .map(|attr| attr.tokens.to_string().replace(' ', ""))
+                .collect::<String>();
+            let mut field_rules = Vec::new();
+            if email.is_match(&validators) {
+                field_rules.push(Rule::Email);
+            }
+            if let Some(c) = len.captures(&validators) {
+                field_rules.push(Rule::Len(c[1].parse().unwrap(), c[2].parse().unwrap()));
+            }
+            if let Some(c) = range.captures(&validators) {
+                field_rules.push(Rule::Range(c[1].parse().unwrap(), c[2].parse().unwrap()));
+            }
+            if let Some(name) = &field.ident {
+                rules.insert(name.to_string(), field_rules);
+            }
+        }
+    }
+    rules
+}
@@ -0,0 +1,77 @@
+use crate::g::registration::Registration;
+use crate::g::{format_generated_code, read_dir, write_generated_code};
+use crate::style_print::{log_error, log_success};
+use quote::{format_ident, quote};
+use std::path::Path;
+
+pub(super) fn register_seeder(model: &str, seed_dir: &Path) {
+    let file_content = match register_seeder_content(seed_dir) {
+        Ok(file_content) => file_content,
+        Err(why) => {
+            log_error(&why);
+            return;
+        }
+    };
+
+    let file_path = write_generated_code(seed_dir, "main.rs", &file_content);
+
+    log_success(&format!(
+        "Successfully registered seeder for model `{}` in {}",
+        model,
+        file_path.display()
+    ));
+}
+
+fn register_seeder_content(seed_dir: &Path) -> Result<String, String> {
+    let files = read_dir(seed_dir).unwrap();
+    let mut files_box = files
+        .iter()
+        .filter(|&i| i != "main.rs")
+        .map(|i| i.replace(".rs", ""))
+        .collect::<Vec<_>>();
+    files_box.sort();
+
+    if let Some(mut registration) = Registration::parse(seed_dir, "main.rs")? {
+        let (added, stale) = registration.sync_mods(seed_dir, &files_box, false);
+        registration.sync_boxed("seeders", "Seeder", &added, &stale);
+        return Ok(registration.into_source());
+    }
+
+    let modules = files_box
+        .iter()
+        .map(|i| format_ident!("{}", i))
+        .collect::<Vec<_>>();
+
+    Ok(format_generated_code(&quote! {
+        use sea_orm::{Database, DatabaseConnection, DbErr};
+
+        #(mod #modules;)*
+
+        #[async_trait::async_trait]
+        pub trait SeederTrait {
+            async fn run(&self, db: &DatabaseConnection) -> Result<(), DbErr>;
+        }
+
+        pub struct Seed;
+
+        impl Seed {
+            fn seeders() -> Vec<Box<dyn SeederTrait>> {
+                vec![
+                    #(Box::new(#modules::Seeder)),*
+                ]
+            }
+        }
+
+        /// Runs every seeder, oldest first, against `DATABASE_URL`.
+        #[tokio::main]
+        async fn main() -> Result<(), DbErr> {
+            let url = std::env::var("DATABASE_URL").expect("DATABASE_URL is not set");
+            let db = Database::connect(url).await?;
+            for seeder in Seed::seeders() {
+                seeder.run(&db).await?;
+            }
+
+            Ok(())
+        }
+    }))
+}
@@ -0,0 +1,344 @@
+use crate::g::{emit_generated_code, Field, FieldType, ModelOptions, PrimaryKey, Rule};
+use crate::style_print::log_success;
+use convert_case::{Case, Casing};
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use std::path::Path;
+
+pub(super) fn create_test(
+    model: &str,
+    app: &str,
+    constructor: &Ident,
+    fields: &[Field],
+    options: ModelOptions,
+    tests_dir: &Path,
+) {
+    let file_content_tokens = create_test_tokens(model, app, constructor, fields, options);
+
+    let file_path = emit_generated_code(
+        tests_dir,
+        &format!("{}_test.rs", model),
+        &file_content_tokens,
+    );
+
+    log_success(&format!(
+        "Successfully created tests for `{}`: {}",
+        model,
+        file_path.display()
+    ));
+}
+
+/// GraphQL document as a raw string literal, which keeps its quotes readable.
+fn document(document: String) -> TokenStream {
+    format!("r#\"{}\"#", document).parse().unwrap()
+}
+
+fn camel(s: String) -> String {
+    s.to_case(Case::Camel)
+}
+
+/// The column as the resolvers return it, for `value!`.
+fn output_value(field: &Field, graphql: &str) -> TokenStream {
+    match field.ty {
+        FieldType::Enum(_) => format!("{:?}", graphql),
+        _ => graphql.to_string(),
+    }
+    .parse()
+    .unwrap()
+}
+
+/// How a GraphQL input value shows in the `Debug` of the statements in the
+/// transaction log, `None` if it can't be told apart.
+fn logged_value(field: &Field, graphql: &str) -> Option<String> {
+    let logged = match field.ty {
+        FieldType::String | FieldType::Text => graphql.to_string(),
+        FieldType::Enum(_) => format!("{:?}", graphql.to_lowercase()),
+        FieldType::I32 => format!("Int(Some({}))", graphql),
+        FieldType::I64 => format!("BigInt(Some({}))", graphql),
+        FieldType::F32 => format!("Float(Some({}))", graphql),
+        FieldType::F64 => format!("Double(Some({}))", graphql),
+        FieldType::Bool => format!("Bool(Some({}))", graphql),
+        FieldType::Json => return None,
+        _ => graphql.trim_matches('"').to_string(),
+    };
+    Some(logged)
+}
+
+/// Another value of the column passing its rules, which the update test
+/// sets to tell it apart from the stored `sample()`.
+fn other_sample(field: &Field, graphql: &str) -> Option<String> {
+    let other = match &field.ty {
+        FieldType::String | FieldType::Text => {
+            let value = graphql.trim_matches('"');
+            let (local, domain) = value.split_at(value.find('@').unwrap_or(value.len()));
+            format!("{:?}", local.chars().rev().collect::<String>() + domain)
+        }
+        FieldType::Enum(values) => values.get(1)?.to_uppercase(),
+        FieldType::I32 | FieldType::I64 => {
+            let value = graphql.parse::<i64>().ok()?;
+            let (min, max) = field
+                .rules
+                .iter()
+                .find_map(|rule| match rule {
+                    Rule::Range(min, max) => Some((*min, *max)),
+                    _ => None,
+                })
+                .unwrap_or((value, value + 1));
+            [max, min].into_iter().find(|&v| v != value)?.to_string()
+        }
+        FieldType::Bool => (graphql != "true").to_string(),
+        _ => return None,
+    };
+    (other != graphql).then_some(other)
+}
+
+fn create_test_tokens(
+    model: &str,
+    app: &str,
+    constructor: &Ident,
+    fields: &[Field],
+    options: ModelOptions,
+) -> TokenStream {
+    let model_name = format_ident!("{}", model);
+    let app = format_ident!("{}", app);
+    let create_test = format_ident!("create_{}", model);
+    let get_test = format_ident!("get_{}_by_id", model);
+    let update_test = format_ident!("update_{}", model);
+    let delete_test = format_ident!("delete_{}", model);
+    let delete_missing_test = format_ident!("delete_missing_{}", model);
+    let create_field = camel(format!("create_{}", model));
+    let get_field = camel(format!("get_{}_by_id", model));
+    let update_field = camel(format!("update_{}", model));
+    let delete_field = camel(format!("delete_{}", model));
+    let insert = format!("INSERT INTO \"{}\"", model);
+    let update = format!("UPDATE \"{}\"", model);
+
+    let (id, id_graphql) = options.pk.field_type().sample();
+    let id_value = id_graphql.parse::<TokenStream>().unwrap();
+    let not_found = format!("{} {} not found", model, id_graphql.trim_matches('"'));
+    let samples = fields
+        .iter()
+        .map(|f| (f, f.sample(&model_name)))
+        .collect::<Vec<_>>();
+    let columns = samples.iter().map(|(f, (value, _))| {
+        let name = f.ident();
+        quote!(#name: #value,)
+    });
+    let timestamps = options.timestamps.field_type().map(|ty| {
+        let (now, _) = ty.sample();
+        quote! {
+            created_at: #now,
+            updated_at: #now,
+        }
+    });
+    let deleted_at = options.soft_delete.then(|| quote!(deleted_at: None,));
+    let json_import = fields
+        .iter()
+        .any(|f| f.ty == FieldType::Json)
+        .then(|| quote! { use sea_orm::prelude::Json; });
+
+    let mut create_input = samples
+        .iter()
+        .map(|(f, (_, graphql))| format!("{}: {}", camel(f.name.clone()), graphql))
+        .collect::<Vec<_>>();
+    if options.pk != PrimaryKey::Uuid {
+        create_input.insert(0, format!("id: {}", id_graphql));
+    }
+    let create_args = if create_input.is_empty() {
+        String::new()
+    } else {
+        format!("(input: {{ {} }})", create_input.join(", "))
+    };
+    let selection = ["id".to_string()]
+        .into_iter()
+        .chain(samples.iter().map(|(f, _)| camel(f.name.clone())))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let returned = samples.iter().map(|(f, (_, graphql))| {
+        let name = camel(f.name.clone());
+        let value = output_value(f, graphql);
+        quote!(, #name: #value)
+    });
+    let inserted = samples
+        .iter()
+        .filter_map(|(f, (_, graphql))| logged_value(f, graphql))
+        .collect::<Vec<_>>();
+
+    // The update sets the first column it can give another value.
+    let changed = samples
+        .iter()
+        .find_map(|(f, (_, graphql))| Some((*f, other_sample(f, graphql)?)));
+    let (update_args, updated) = match &changed {
+        Some((f, graphql)) => (
+            format!(
+                "(id: {}, input: {{ {}: {} }})",
+                id_graphql,
+                camel(f.name.clone()),
+                graphql
+            ),
+            logged_value(f, graphql),
+        ),
+        None => (format!("(id: {})", id_graphql), None),
+    };
+    let updated = updated.into_iter();
+
+    let create_document = document(format!(
+        "mutation {{ {}{} {{ {} }} }}",
+        create_field, create_args, selection
+    ));
+    let get_document = document(format!(
+        "query {{ {}(id: {}) {{ {} }} }}",
+        get_field, id_graphql, selection
+    ));
+    let update_document = document(format!(
+        "mutation {{ {}{} {{ id }} }}",
+        update_field, update_args
+    ));
+    let delete_document = document(format!(
+        "mutation {{ {}(id: {}) {{ success rowsAffected }} }}",
+        delete_field, id_graphql
+    ));
+    let returned = returned.collect::<Vec<_>>();
+
+    // A soft delete finds the row and updates it, a delete only executes.
+    let no_rows = options
+        .soft_delete
+        .then(|| quote!(let no_rows: Vec<#model_name::Model> = vec![];));
+    let (exec_import, delete_results, missing_results) = if options.soft_delete {
+        (
+            quote!(),
+            quote!(.append_query_results(vec![vec![sample()], vec![sample()]])),
+            quote!(.append_query_results(vec![no_rows])),
+        )
+    } else {
+        (
+            quote!(MockExecResult,),
+            quote! {
+                .append_exec_results(vec![MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 1,
+                }])
+            },
+            quote! {
+                .append_exec_results(vec![MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 0,
+                }])
+            },
+        )
+    };
+
+    quote! {
+        use entity::async_graphql::{value, EmptySubscription, ErrorExtensionValues, Schema};
+        use entity::#model_name;
+        use #app::db::Database;
+        use #app::graphql::mutation::Mutation;
+        use #app::graphql::query::Query;
+        #json_import
+        use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase, #exec_import};
+
+        fn schema(db: DatabaseConnection) -> Schema<Query, Mutation, EmptySubscription> {
+            Schema::build(Query::default(), Mutation::default(), EmptySubscription)
+                .data(Database::#constructor(db))
+                .finish()
+        }
+
+        /// A second handle on the mocked connection, which keeps the
+        /// transaction log readable once the schema owns the first.
+        fn log_handle(db: &DatabaseConnection) -> DatabaseConnection {
+            match db {
+                DatabaseConnection::MockDatabaseConnection(mock) => {
+                    DatabaseConnection::MockDatabaseConnection(mock.clone())
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        /// The row returned by every mocked query.
+        fn sample() -> #model_name::Model {
+            #model_name::Model {
+                id: #id,
+                #(#columns)*
+                #timestamps
+                #deleted_at
+            }
+        }
+
+        #[tokio::test]
+        async fn #create_test() {
+            let db = MockDatabase::new(DatabaseBackend::Postgres)
+                .append_query_results(vec![vec![sample()]])
+                .into_connection();
+            let log = log_handle(&db);
+
+            let res = schema(db).execute(#create_document).await;
+
+            assert!(res.errors.is_empty(), "{:?}", res.errors);
+            assert_eq!(res.data, value!({ #create_field: { "id": #id_value #(#returned)* } }));
+            let log = format!("{:?}", log.into_transaction_log());
+            for value in [#insert #(, #inserted)*] {
+                assert!(log.contains(value), "{} not in {}", value, log);
+            }
+        }
+
+        #[tokio::test]
+        async fn #get_test() {
+            let db = MockDatabase::new(DatabaseBackend::Postgres)
+                .append_query_results(vec![vec![sample()]])
+                .into_connection();
+
+            let res = schema(db).execute(#get_document).await;
+
+            assert!(res.errors.is_empty(), "{:?}", res.errors);
+            assert_eq!(res.data, value!({ #get_field: { "id": #id_value #(#returned)* } }));
+        }
+
+        #[tokio::test]
+        async fn #update_test() {
+            let db = MockDatabase::new(DatabaseBackend::Postgres)
+                .append_query_results(vec![vec![sample()], vec![sample()]])
+                .into_connection();
+            let log = log_handle(&db);
+
+            let res = schema(db).execute(#update_document).await;
+
+            assert!(res.errors.is_empty(), "{:?}", res.errors);
+            assert_eq!(res.data, value!({ #update_field: { "id": #id_value } }));
+            let log = format!("{:?}", log.into_transaction_log());
+            for value in [#update #(, #updated)*] {
+                assert!(log.contains(value), "{} not in {}", value, log);
+            }
+        }
+
+        #[tokio::test]
+        async fn #delete_test() {
+            let db = MockDatabase::new(DatabaseBackend::Postgres)
+                #delete_results
+                .into_connection();
+
+            let res = schema(db).execute(#delete_document).await;
+
+            assert!(res.errors.is_empty(), "{:?}", res.errors);
+            assert_eq!(
+                res.data,
+                value!({ #delete_field: { "success": true, "rowsAffected": 1 } })
+            );
+        }
+
+        #[tokio::test]
+        async fn #delete_missing_test() {
+            #no_rows
+            let db = MockDatabase::new(DatabaseBackend::Postgres)
+                #missing_results
+                .into_connection();
+
+            let res = schema(db).execute(#delete_document).await;
+
+            let mut not_found = ErrorExtensionValues::default();
+            not_found.set("code", "NOT_FOUND");
+            assert_eq!(res.errors.len(), 1, "{:?}", res.errors);
+            assert_eq!(res.errors[0].message, #not_found);
+            assert_eq!(res.errors[0].extensions, Some(not_found));
+        }
+    }
+}
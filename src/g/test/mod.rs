@@ -0,0 +1,142 @@
+use crate::g::test::creation::create_test;
+use crate::g::{
+    format_generated_code, remove_generated_code, write_generated_code, Field, ModelOptions,
+};
+use crate::style_print::{log_error, log_success};
+use proc_macro2::Ident;
+use quote::{format_ident, quote};
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+use syn::{ImplItem, Item};
+
+mod creation;
+
+pub(in crate::g) fn test_file(model: &str, gen_path: &Path) -> PathBuf {
+    gen_path.join("tests").join(format!("{}_test.rs", model))
+}
+
+pub(in crate::g) fn process_test(
+    model: &str,
+    fields: &[Field],
+    options: ModelOptions,
+    gen_path: &Path,
+) {
+    let tests_dir = gen_path.join("tests");
+
+    fs::create_dir_all(tests_dir.as_path()).unwrap_or_else(|why| {
+        log_error(&format!("! {:?}", why.kind()));
+    });
+
+    let src_dir = gen_path.join("src");
+    if !src_dir.join("lib.rs").is_file() {
+        log_error(&format!(
+            "Skipped tests/{}_test.rs, it needs `pub mod db;` and `pub mod graphql;` in src/lib.rs",
+            model
+        ));
+        return;
+    }
+    let constructor = match database_constructor(&src_dir) {
+        Ok(constructor) => constructor,
+        Err(why) => {
+            log_error(&format!("Skipped tests/{}_test.rs, {}", model, why));
+            return;
+        }
+    };
+    create_test(
+        model,
+        &app_crate(gen_path),
+        &constructor,
+        fields,
+        options,
+        &tests_dir,
+    );
+}
+
+/// The associated function of the app's `Database` wrapping a given
+/// `DatabaseConnection`, which the tests hand their `MockDatabase` to. When
+/// there is none, `from_connection` is added to `src/db.rs`.
+fn database_constructor(src_dir: &Path) -> Result<Ident, String> {
+    let source = fs::read_to_string(src_dir.join("db.rs"))
+        .map_err(|_| "it needs the `Database` of the app in src/db.rs".to_string())?;
+    let db =
+        syn::parse_file(&source).map_err(|why| format!("src/db.rs does not parse: {}", why))?;
+
+    let is_database =
+        |ty: &syn::Type| matches!(ty, syn::Type::Path(path) if path.path.is_ident("Database"));
+    let takes_connection = |sig: &syn::Signature| match sig.inputs.iter().collect::<Vec<_>>()[..] {
+        [syn::FnArg::Typed(arg)] => matches!(
+            &*arg.ty,
+            syn::Type::Path(path)
+                if path.path.segments.last().is_some_and(|s| s.ident == "DatabaseConnection")
+        ),
+        _ => false,
+    };
+    let constructor = db.items.iter().find_map(|item| match item {
+        Item::Impl(item_impl) if item_impl.trait_.is_none() && is_database(&item_impl.self_ty) => {
+            item_impl.items.iter().find_map(|item| match item {
+                ImplItem::Method(method)
+                    if method.sig.asyncness.is_none() && takes_connection(&method.sig) =>
+                {
+                    Some(method.sig.ident.clone())
+                }
+                _ => None,
+            })
+        }
+        _ => None,
+    });
+    if let Some(constructor) = constructor {
+        return Ok(constructor);
+    }
+
+    let field = db.items.iter().find_map(|item| match item {
+        Item::Struct(item_struct) if item_struct.ident == "Database" => match &item_struct.fields {
+            syn::Fields::Named(fields) if fields.named.len() == 1 => fields.named[0].ident.clone(),
+            _ => None,
+        },
+        _ => None,
+    });
+    let field = field.ok_or_else(|| {
+        "src/db.rs needs a `Database` with a single connection field or a function making one from a `DatabaseConnection`".to_string()
+    })?;
+
+    let constructor = quote! {
+        impl Database {
+            pub fn from_connection(#field: sea_orm::DatabaseConnection) -> Self {
+                Database { #field }
+            }
+        }
+    };
+    let file_path = write_generated_code(
+        src_dir,
+        "db.rs",
+        &format!(
+            "{}\n\n{}",
+            source.trim_end(),
+            format_generated_code(&constructor)
+        ),
+    );
+    log_success(&format!(
+        "Successfully added `Database::from_connection` for the tests to {}",
+        file_path.display()
+    ));
+    Ok(format_ident!("from_connection"))
+}
+
+pub(in crate::g) fn destroy_test(model: &str, gen_path: &Path) {
+    remove_generated_code(&gen_path.join("tests"), &format!("{}_test.rs", model));
+}
+
+/// Name the integration tests import the app as, from its `Cargo.toml` or
+/// else its directory, any character not allowed in a crate name replaced.
+fn app_crate(gen_path: &Path) -> String {
+    let package = Regex::new(r#"(?m)^name\s*=\s*"([^"]+)""#).unwrap();
+    let name = fs::read_to_string(gen_path.join("Cargo.toml"))
+        .ok()
+        .and_then(|manifest| Some(package.captures(&manifest)?[1].to_string()))
+        .unwrap_or_else(|| {
+            let dir = gen_path.canonicalize().unwrap();
+            dir.file_name().unwrap().to_string_lossy().into_owned()
+        });
+    name.replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+}
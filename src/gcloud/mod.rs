@@ -1,3 +1,4 @@
 pub mod process;
 
+#[allow(unused_imports)]
 pub use process::*;
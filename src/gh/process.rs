@@ -21,7 +21,7 @@ pub fn process_setup_secret() {
 
 pub fn process_add_env(key: &str, value: &str) {
     let output = Command::new("gh")
-        .args(&["secret", "set", key, "-b", value])
+        .args(["secret", "set", key, "-b", value])
         .output();
 
     match &output {
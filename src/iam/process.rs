@@ -11,7 +11,7 @@ pub fn process_create_service_account(project_id: &str, service_name: &str) {
     let description = String::from("--description='") + service_name + " Service Account'";
     let display_name = String::from("--display-name=") + service_name;
     let output = Command::new("gcloud")
-        .args(&[
+        .args([
             "iam",
             "service-accounts",
             "create",
@@ -49,7 +49,7 @@ pub fn process_create_service_account_key(project_id: &str, service_name: &str)
     let service_account =
         String::from(service_name) + "@" + project_id + ".iam.gserviceaccount.com";
     let output = Command::new("gcloud")
-        .args(&[
+        .args([
             "iam",
             "service-accounts",
             "keys",
@@ -110,7 +110,7 @@ pub fn process_add_service_account_role(project_id: &str, service_name: &str, ro
         + ".iam.gserviceaccount.com";
     let role = String::from("--role=") + role_arg;
     let output = Command::new("gcloud")
-        .args(&[
+        .args([
             "projects",
             "add-iam-policy-binding",
             project_id,
@@ -165,7 +165,7 @@ pub fn process_enable_permissions(project_id: &str) {
     ];
     for service_name in service_urls {
         let output = Command::new("gcloud")
-            .args(&["services", "enable", service_name, "--project", project_id])
+            .args(["services", "enable", service_name, "--project", project_id])
             .output();
 
         match &output {
@@ -192,7 +192,7 @@ pub fn process_enable_permissions(project_id: &str) {
 
 pub fn set_keyfile_to_gh_secret() {
     let output = Command::new("gh")
-        .args(&["secret", "set", "ZAPP_GCP_SA_KEY", "<", "./keyfile.json"])
+        .args(["secret", "set", "ZAPP_GCP_SA_KEY", "<", "./keyfile.json"])
         .output();
 
     println!("{:?}", &output);
@@ -127,7 +127,7 @@ pub fn dl_zapp(app_name: &str) {
         "https://storage.googleapis.com/zapp-bucket/zapp-api-template/{}/zapp-api.tar.gz",
         version_range
     );
-    let output = Command::new("curl").args(&["-OL", &zapp_dl_url]).output();
+    let output = Command::new("curl").args(["-OL", &zapp_dl_url]).output();
 
     match &output {
         Ok(val) => {
@@ -149,7 +149,7 @@ pub fn dl_zapp(app_name: &str) {
 
 pub fn unzip_zapp(app_name: &str) {
     let filename = "zapp-api.tar.gz";
-    let output = Command::new("tar").args(&["-zxvf", &filename]).output();
+    let output = Command::new("tar").args(["-zxvf", filename]).output();
 
     match &output {
         Ok(val) => {
@@ -161,7 +161,7 @@ pub fn unzip_zapp(app_name: &str) {
                 }
                 false => {
                     let _ = fs::rename("zapp-api", app_name);
-                    let _ = fs::remove_file(&filename);
+                    let _ = fs::remove_file(filename);
                 }
             }
         }
@@ -171,7 +171,7 @@ pub fn unzip_zapp(app_name: &str) {
 
 pub fn git_init(app_name: &str) {
     let output = Command::new("cd")
-        .args(&[&app_name, "&&", "git", "init", "--initial-branch=main"])
+        .args([app_name, "&&", "git", "init", "--initial-branch=main"])
         .output();
 
     match &output {
@@ -1,11 +1,11 @@
 use chrono::Local;
 use clap::Parser;
 use std::env::current_dir;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::BufReader;
 use zapp::cli::{
-    Cli, Commands, ComputeCommands, DbCommands, DockerCommands, GCommands, GcloudCommands,
-    GcpConfig, GhCommands, IamCommands, InitCommands, RunCommands, SqlCommands,
+    Cli, Commands, ComputeCommands, DbCommands, DestroyCommands, DockerCommands, GCommands,
+    GcloudCommands, GcpConfig, GhCommands, IamCommands, InitCommands, RunCommands, SqlCommands,
 };
 use zapp::compute::*;
 use zapp::db::*;
@@ -72,10 +72,10 @@ fn main() {
             let run_cmd = run.command.unwrap_or(RunCommands::Help);
             match run_cmd {
                 RunCommands::Build => {
-                    process_gcloud_build(&gcp.project_id, &gcp.service_name, &gcp.gcr_region());
+                    process_gcloud_build(&gcp.project_id, &gcp.service_name, gcp.gcr_region());
                 }
                 RunCommands::Deploy => {
-                    process_deploy(&gcp.project_id, &gcp.service_name, &gcp.gcr_region());
+                    process_deploy(&gcp.project_id, &gcp.service_name, gcp.gcr_region());
                 }
                 _ => {
                     let log = "To see example;\n\n $zapp run --help";
@@ -139,14 +139,14 @@ fn main() {
                 }
                 DockerCommands::Build => {
                     let gcp = get_gcp();
-                    process_docker_build(&gcp.project_id, &gcp.service_name, &gcp.gcr_region());
+                    process_docker_build(&gcp.project_id, &gcp.service_name, gcp.gcr_region());
                 }
                 DockerCommands::Restart => {
                     process_docker_restart();
                 }
                 DockerCommands::Push => {
                     let gcp = get_gcp();
-                    process_docker_push(&gcp.project_id, &gcp.service_name, &gcp.gcr_region());
+                    process_docker_push(&gcp.project_id, &gcp.service_name, gcp.gcr_region());
                 }
                 _ => {
                     let log = "To see example;\n\n $zapp docker --help";
@@ -186,11 +186,208 @@ fn main() {
         Commands::G(g) => {
             let g_cmd = g.command.unwrap_or(GCommands::Help);
             match g_cmd {
-                GCommands::Model { model, path } => {
+                GCommands::Model {
+                    model,
+                    fields,
+                    soft_delete,
+                    pk,
+                    no_timestamps,
+                    timestamptz,
+                    subscriptions,
+                    path,
+                    dry_run: preview,
+                    force,
+                    skip,
+                } => {
+                    if soft_delete && fields.iter().any(|f| f.name == "deleted_at") {
+                        log_error(
+                            "`deleted_at` is added by --soft-delete, leave it out of the fields",
+                        );
+                        return;
+                    }
+                    let timestamps = if no_timestamps {
+                        Timestamps::None
+                    } else if timestamptz {
+                        Timestamps::Tz
+                    } else {
+                        Timestamps::Naive
+                    };
+                    let options = ModelOptions {
+                        soft_delete,
+                        pk,
+                        timestamps,
+                        subscriptions,
+                    };
+                    let on_conflict = conflict_policy(force, skip);
                     let gen_path_buf = path.unwrap_or_else(|| current_dir().unwrap());
                     let gen_path = gen_path_buf.as_path();
                     let date = Local::now();
-                    process_g(&model, date.naive_local(), gen_path);
+                    if preview {
+                        dry_run(gen_path, |path| {
+                            process_g(
+                                &model,
+                                &fields,
+                                options,
+                                date.naive_local(),
+                                path,
+                                on_conflict,
+                            )
+                        });
+                    } else {
+                        process_g(
+                            &model,
+                            &fields,
+                            options,
+                            date.naive_local(),
+                            gen_path,
+                            on_conflict,
+                        );
+                    }
+                }
+                GCommands::Join {
+                    left,
+                    right,
+                    path,
+                    dry_run: preview,
+                    force,
+                    skip,
+                } => {
+                    let on_conflict = conflict_policy(force, skip);
+                    let gen_path_buf = path.unwrap_or_else(|| current_dir().unwrap());
+                    let gen_path = gen_path_buf.as_path();
+                    let date = Local::now();
+                    if preview {
+                        dry_run(gen_path, |path| {
+                            process_join(&left, &right, date.naive_local(), path, on_conflict)
+                        });
+                    } else {
+                        process_join(&left, &right, date.naive_local(), gen_path, on_conflict);
+                    }
+                }
+                GCommands::Migration {
+                    name,
+                    auto: true,
+                    path,
+                    dry_run: preview,
+                    ..
+                } => {
+                    let gen_path_buf = path.unwrap_or_else(|| current_dir().unwrap());
+                    let gen_path = gen_path_buf.as_path();
+                    let date = Local::now();
+                    if preview {
+                        dry_run(gen_path, |path| {
+                            process_auto_alter(name.as_deref(), date.naive_local(), path)
+                        });
+                    } else {
+                        process_auto_alter(name.as_deref(), date.naive_local(), gen_path);
+                    }
+                }
+                GCommands::Migration {
+                    name,
+                    args,
+                    unique,
+                    path,
+                    dry_run: preview,
+                    ..
+                } => {
+                    let name = name.unwrap();
+                    let gen_path_buf = path.unwrap_or_else(|| current_dir().unwrap());
+                    let gen_path = gen_path_buf.as_path();
+                    let tables = model_names(gen_path);
+                    let alteration = match Alteration::parse(&name, &args, unique, &tables) {
+                        Ok(alteration) => alteration,
+                        Err(why) => {
+                            log_error(&why);
+                            return;
+                        }
+                    };
+                    let date = Local::now();
+                    if preview {
+                        dry_run(gen_path, |path| {
+                            process_alter(&name, &alteration, date.naive_local(), path)
+                        });
+                    } else {
+                        process_alter(&name, &alteration, date.naive_local(), gen_path);
+                    }
+                }
+                GCommands::Seed {
+                    model,
+                    path,
+                    dry_run: preview,
+                    force,
+                    skip,
+                } => {
+                    let on_conflict = conflict_policy(force, skip);
+                    let gen_path_buf = path.unwrap_or_else(|| current_dir().unwrap());
+                    let gen_path = gen_path_buf.as_path();
+                    let date = Local::now();
+                    if preview {
+                        dry_run(gen_path, |path| {
+                            process_seed(&model, date.naive_local(), path, on_conflict)
+                        });
+                    } else {
+                        process_seed(&model, date.naive_local(), gen_path, on_conflict);
+                    }
+                }
+                GCommands::Import {
+                    file,
+                    path,
+                    dry_run: preview,
+                    force,
+                    skip,
+                } => {
+                    let on_conflict = conflict_policy(force, skip);
+                    let gen_path_buf = path.unwrap_or_else(|| current_dir().unwrap());
+                    let gen_path = gen_path_buf.as_path();
+                    let sql = match fs::read_to_string(&file) {
+                        Ok(sql) => sql,
+                        Err(why) => {
+                            log_error(&format!("Cannot read {}: {}", file.display(), why));
+                            return;
+                        }
+                    };
+                    let schema = match Schema::from_sql(&sql) {
+                        Ok(schema) => schema,
+                        Err(why) => {
+                            log_error(&format!("Invalid {}: {}", file.display(), why));
+                            return;
+                        }
+                    };
+                    let date = Local::now();
+                    if preview {
+                        dry_run(gen_path, |path| {
+                            process_import(&schema, date.naive_local(), path, on_conflict)
+                        });
+                    } else {
+                        process_import(&schema, date.naive_local(), gen_path, on_conflict);
+                    }
+                }
+                GCommands::Schema {
+                    file,
+                    path,
+                    dry_run: preview,
+                    force,
+                    skip,
+                } => {
+                    let on_conflict = conflict_policy(force, skip);
+                    let gen_path_buf = path.unwrap_or_else(|| current_dir().unwrap());
+                    let gen_path = gen_path_buf.as_path();
+                    let file = file.unwrap_or_else(|| gen_path.join("zapp.schema.yaml"));
+                    let schema = match Schema::load(&file) {
+                        Ok(schema) => schema,
+                        Err(why) => {
+                            log_error(&why);
+                            return;
+                        }
+                    };
+                    let date = Local::now();
+                    if preview {
+                        dry_run(gen_path, |path| {
+                            process_schema(&schema, date.naive_local(), path, on_conflict)
+                        });
+                    } else {
+                        process_schema(&schema, date.naive_local(), gen_path, on_conflict);
+                    }
                 }
                 _ => {
                     let log = "To see example;\n\n $zapp run --help";
@@ -198,6 +395,25 @@ fn main() {
                 }
             }
         }
+        Commands::Destroy(destroy) => {
+            let destroy_cmd = destroy.command.unwrap_or(DestroyCommands::Help);
+            match destroy_cmd {
+                DestroyCommands::Model {
+                    model,
+                    keep_migration,
+                    path,
+                } => {
+                    let gen_path_buf = path.unwrap_or_else(|| current_dir().unwrap());
+                    let gen_path = gen_path_buf.as_path();
+                    let keep_migration = keep_migration || process_db_migration_applied(&model);
+                    process_destroy(&model, keep_migration, gen_path);
+                }
+                _ => {
+                    let log = "To see example;\n\n $zapp destroy --help";
+                    log_error(log);
+                }
+            }
+        }
         Commands::Db(db) => {
             let db_cmd = db.command.unwrap_or(DbCommands::Help);
             match db_cmd {
@@ -214,6 +430,9 @@ fn main() {
                 DbCommands::Rollback => {
                     process_db_rollback();
                 }
+                DbCommands::Seed => {
+                    process_db_seed();
+                }
                 _ => {
                     let log = "To see example;\n\n $zapp db --help";
                     log_error(log);
@@ -223,7 +442,15 @@ fn main() {
     }
 }
 
-
+fn conflict_policy(force: bool, skip: bool) -> ConflictPolicy {
+    if force {
+        ConflictPolicy::Force
+    } else if skip {
+        ConflictPolicy::Skip
+    } else {
+        ConflictPolicy::Prompt
+    }
+}
 
 pub fn get_gcp() -> GcpConfig {
     let file_name = "gcp_config.json";
@@ -262,5 +489,5 @@ pub fn setup_deployment(gcp: GcpConfig) {
     process_connect_vpc_connector(&gcp.project_id, &gcp.service_name);
     process_assign_network(&gcp.project_id, &gcp.service_name);
     // 5. Create Github Actions Workflow
-    build_api_workflow(&gcp.gcr_region());
+    build_api_workflow(gcp.gcr_region());
 }
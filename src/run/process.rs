@@ -3,7 +3,7 @@ use std::process::Command;
 pub fn process_gcloud_build(project_id: &str, service_name: &str, gcr_region: &str) {
     let gcr_url = String::from(gcr_region) + "/" + project_id + "/" + service_name;
     let output = Command::new("gcloud")
-        .args(&[
+        .args([
             "builds",
             "submit",
             "--tag",
@@ -21,7 +21,7 @@ pub fn process_deploy(project_id: &str, service_name: &str, gcr_region: &str) {
     let img_url = String::from(gcr_region) + "/" + project_id + "/" + service_name;
     println!("{}", &img_url);
     let output = Command::new("gcloud")
-        .args(&[
+        .args([
             "run",
             "deploy",
             "--image",
@@ -37,7 +37,7 @@ pub fn process_create_sql(project_id: &str, service_name: &str, region: &str, ne
     let instance_name = String::from(service_name) + "-db";
     let db_version = String::from("--database-version=POSTGRES_14");
     let output = Command::new("gcloud")
-        .args(&[
+        .args([
             "sql",
             "instances",
             "create",
@@ -80,16 +80,16 @@ pub fn process_create_sql(project_id: &str, service_name: &str, region: &str, ne
         + &instance_name
         + "\n";
     let zapp_gcloudsql_instance = String::from("ZAPP_GCLOUDSQL_INSTANCE=\"")
-        + &project_id
+        + project_id
         + ":"
-        + &region
+        + region
         + ":"
         + &instance_name
         + "\"\n";
-    let zapp_gcp_project_id = String::from("ZAPP_GCP_PROJECT_ID=") + &project_id + "\n";
-    let zapp_service_name = String::from("ZAPP_SERVICE_NAME=") + &service_name + "\n";
-    let zapp_gcp_region = String::from("ZAPP_GCP_REGION=") + &region + "\n";
-    let zapp_network_name = String::from("ZAPP_NETWORK_NAME=") + &network + "\n";
+    let zapp_gcp_project_id = String::from("ZAPP_GCP_PROJECT_ID=") + project_id + "\n";
+    let zapp_service_name = String::from("ZAPP_SERVICE_NAME=") + service_name + "\n";
+    let zapp_gcp_region = String::from("ZAPP_GCP_REGION=") + region + "\n";
+    let zapp_network_name = String::from("ZAPP_NETWORK_NAME=") + network + "\n";
     let zapp_env = String::from("ZAPP_ENV=production") + "\n";
     let env_production = EnvProduction {
         database_url,
@@ -128,7 +128,7 @@ pub fn process_patch_sql(project_id: &str, service_name: &str, action: &str) {
 
     log_time("Patching Cloud SQL ...\nThis process takes 5 to 10 min.");
     let output = Command::new("gcloud")
-        .args(&[
+        .args([
             "sql",
             "instances",
             "patch",
@@ -160,7 +160,7 @@ pub fn process_patch_sql(project_id: &str, service_name: &str, action: &str) {
 pub fn process_restart_sql(project_id: &str, service_name: &str) {
     let instance_name = String::from(service_name) + "-db";
     let output = Command::new("gcloud")
-        .args(&[
+        .args([
             "sql",
             "instances",
             "restart",
@@ -189,7 +189,7 @@ pub fn process_create_ip_range(project_id: &str, service_name: &str) {
     let ip_range_name = String::from(service_name) + "-ip-range";
     let network = String::from("--network=") + service_name;
     let output = Command::new("gcloud")
-        .args(&[
+        .args([
             "compute",
             "addresses",
             "create",
@@ -223,7 +223,7 @@ pub fn process_connect_vpc_connector(project_id: &str, service_name: &str) {
     let ip_range_name = String::from(service_name) + "-ip-range";
     let network = String::from("--network=") + service_name;
     let output = Command::new("gcloud")
-        .args(&[
+        .args([
             "services",
             "vpc-peerings",
             "connect",
@@ -256,7 +256,7 @@ pub fn process_assign_network(project_id: &str, service_name: &str) {
     let instance_name = String::from(service_name) + "-db";
     let network = String::from("--network=") + service_name;
     let output = Command::new("gcloud")
-        .args(&[
+        .args([
             "beta",
             "sql",
             "instances",
@@ -289,7 +289,7 @@ pub fn get_instance_ip(project_id: &str, service_name: &str, ip_type: usize) ->
     let instance_name = String::from(service_name) + "-db";
     let mut _internal_ip = String::new();
     let output = Command::new("gcloud")
-        .args(&[
+        .args([
             "sql",
             "instances",
             "describe",
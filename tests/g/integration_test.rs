@@ -1,8 +1,14 @@
 use crate::g::assert_file_equality;
 use chrono::NaiveDate;
+use std::fs;
 use std::path::Path;
+use std::process::Command;
 use tempdir::TempDir;
-use zapp::g::process_g;
+use zapp::g::{
+    dry_run, model_names, process_alter, process_auto_alter, process_destroy, process_g,
+    process_import, process_join, process_schema, process_seed, Alteration, ConflictPolicy, Field,
+    ModelOptions, PrimaryKey, Schema, Timestamps,
+};
 
 #[test]
 fn gen_one_user_model() {
@@ -11,7 +17,14 @@ fn gen_one_user_model() {
 
     let test_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
 
-    process_g("user", test_dt, tmp_dir.path());
+    process_g(
+        "user",
+        &[],
+        ModelOptions::default(),
+        test_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
 
     assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/user.rs");
     assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/lib.rs");
@@ -24,8 +37,1063 @@ fn gen_one_user_model() {
     assert_file_equality(resource_dir, tmp_dir.path(), "migration/src/lib.rs");
 
     assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/query/user.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/query/filter.rs");
     assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/query/mod.rs");
 
     assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/mutation/user.rs");
     assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/mutation/mod.rs");
 }
+
+#[test]
+fn gen_user_model_with_fields() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let resource_dir = Path::new("tests/g/resources/gen_user_model_with_fields");
+
+    let test_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
+    let fields = [
+        "name:string",
+        "bio:text?",
+        "age:i32",
+        "born:date",
+        "role:string=member",
+    ]
+    .iter()
+    .map(|f| f.parse::<Field>().unwrap())
+    .collect::<Vec<_>>();
+
+    process_g(
+        "user",
+        &fields,
+        ModelOptions::default(),
+        test_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/user.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/mutation/user.rs");
+
+    for field in [
+        "age:i32=3000000000",
+        "score:f64=NaN",
+        "score:f64=inf",
+        "score:f32=1e39",
+    ] {
+        assert!(field.parse::<Field>().is_err(), "{}", field);
+    }
+    assert!("age:i64=3000000000".parse::<Field>().is_ok());
+}
+
+#[test]
+fn gen_user_model_with_validation_rules() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let resource_dir = Path::new("tests/g/resources/gen_user_model_with_validation_rules");
+
+    let test_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
+    let fields = [
+        "email:string:email",
+        "name:string:len(1,100)",
+        "age:i32:range(0,150)?",
+    ]
+    .iter()
+    .map(|f| f.parse::<Field>().unwrap())
+    .collect::<Vec<_>>();
+
+    process_g(
+        "user",
+        &fields,
+        ModelOptions::default(),
+        test_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+
+    assert_file_equality(
+        resource_dir,
+        tmp_dir.path(),
+        "migration/src/m20220716_233933_create_user_table.rs",
+    );
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/mutation/user.rs");
+    assert!("age:bool:range(0,150)".parse::<Field>().is_err());
+    assert!("type:string".parse::<Field>().is_err());
+    assert!("struct:i32".parse::<Field>().is_err());
+    assert!("kind:enum(self,other)".parse::<Field>().is_err());
+    assert!("age:i32:range(0,3000000000)".parse::<Field>().is_err());
+    assert!("age:i64:range(0,3000000000)".parse::<Field>().is_ok());
+    assert!("age:i32:range(0,150)=200".parse::<Field>().is_err());
+    assert!("name:string:len(1,4)=member".parse::<Field>().is_err());
+    assert!("email:string:email=member".parse::<Field>().is_err());
+    assert!("email:string:email=member@example.com"
+        .parse::<Field>()
+        .is_ok());
+    assert!(Alteration::parse(
+        "add_email_to_user",
+        &["email:string:email".to_string()],
+        false,
+        &[]
+    )
+    .is_err());
+}
+
+#[test]
+fn gen_post_model_referencing_user() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let resource_dir = Path::new("tests/g/resources/gen_post_model_referencing_user");
+
+    let user_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
+    let post_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 40, 12);
+    let fields = ["title:string", "user:references"]
+        .iter()
+        .map(|f| f.parse::<Field>().unwrap())
+        .collect::<Vec<_>>();
+
+    process_g(
+        "user",
+        &[],
+        ModelOptions::default(),
+        user_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+    process_g(
+        "post",
+        &fields,
+        ModelOptions::default(),
+        post_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/user.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/post.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/lib.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "migration/src/lib.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/query/mod.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/mutation/mod.rs");
+}
+
+#[test]
+fn gen_user_group_join() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let resource_dir = Path::new("tests/g/resources/gen_user_group_join");
+
+    let user_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
+    let group_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 40, 12);
+    let join_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 41, 5);
+
+    process_g(
+        "user",
+        &[],
+        ModelOptions::default(),
+        user_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+    process_g(
+        "group",
+        &[],
+        ModelOptions::default(),
+        group_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+    process_join(
+        "user",
+        "group",
+        join_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/user_group.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/user.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/group.rs");
+    assert_file_equality(
+        resource_dir,
+        tmp_dir.path(),
+        "migration/src/m20220716_234105_create_user_group_table.rs",
+    );
+    assert_file_equality(
+        resource_dir,
+        tmp_dir.path(),
+        "src/graphql/query/user_group.rs",
+    );
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/query/mod.rs");
+}
+
+#[test]
+fn destroy_post_model_referencing_user() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let resource_dir = Path::new("tests/g/resources/gen_one_user_model");
+
+    let user_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
+    let post_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 40, 12);
+    let fields = ["title:string", "user:references"]
+        .iter()
+        .map(|f| f.parse::<Field>().unwrap())
+        .collect::<Vec<_>>();
+
+    process_g(
+        "user",
+        &[],
+        ModelOptions::default(),
+        user_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+    process_g(
+        "post",
+        &fields,
+        ModelOptions::default(),
+        post_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+    process_destroy("post", false, tmp_dir.path());
+
+    assert!(!tmp_dir.path().join("entity/src/post.rs").exists());
+    assert!(!tmp_dir
+        .path()
+        .join("migration/src/m20220716_234012_create_post_table.rs")
+        .exists());
+    assert!(!tmp_dir.path().join("src/graphql/query/post.rs").exists());
+    assert!(!tmp_dir.path().join("src/graphql/mutation/post.rs").exists());
+    assert!(!tmp_dir.path().join("tests/post_test.rs").exists());
+
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/user.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/lib.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "migration/src/lib.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/query/mod.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/mutation/mod.rs");
+}
+
+#[test]
+fn dry_run_writes_nothing() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let resource_dir = Path::new("tests/g/resources/gen_one_user_model");
+
+    let user_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
+    let post_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 40, 12);
+    let fields = ["title:string", "user:references"]
+        .iter()
+        .map(|f| f.parse::<Field>().unwrap())
+        .collect::<Vec<_>>();
+
+    process_g(
+        "user",
+        &[],
+        ModelOptions::default(),
+        user_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+    dry_run(tmp_dir.path(), |path| {
+        process_g(
+            "post",
+            &fields,
+            ModelOptions::default(),
+            post_dt,
+            path,
+            ConflictPolicy::Force,
+        )
+    });
+
+    assert!(!tmp_dir.path().join("entity/src/post.rs").exists());
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/user.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/lib.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "migration/src/lib.rs");
+}
+
+#[test]
+fn regen_user_model_skips_or_overwrites_existing_files() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let resource_dir = Path::new("tests/g/resources/gen_one_user_model");
+
+    let test_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
+    let later_dt = NaiveDate::from_ymd(2022, 7, 17).and_hms(8, 0, 0);
+    let entity_file = tmp_dir.path().join("entity/src/user.rs");
+
+    process_g(
+        "user",
+        &[],
+        ModelOptions::default(),
+        test_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+    fs::write(&entity_file, "// hand-written\n").unwrap();
+
+    process_g(
+        "user",
+        &[],
+        ModelOptions::default(),
+        later_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Skip,
+    );
+    assert_eq!(
+        fs::read_to_string(&entity_file).unwrap(),
+        "// hand-written\n"
+    );
+
+    process_g(
+        "user",
+        &[],
+        ModelOptions::default(),
+        later_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/user.rs");
+    assert_file_equality(
+        resource_dir,
+        tmp_dir.path(),
+        "migration/src/m20220716_233933_create_user_table.rs",
+    );
+    assert_file_equality(resource_dir, tmp_dir.path(), "migration/src/lib.rs");
+}
+
+#[test]
+fn gen_tag_model_without_relations() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let resource_dir = Path::new("tests/g/resources/gen_tag_model_without_relations");
+
+    let test_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
+    let fields = ["name:string".parse::<Field>().unwrap()];
+    let options = ModelOptions {
+        soft_delete: true,
+        ..Default::default()
+    };
+    process_g(
+        "tag",
+        &fields,
+        options,
+        test_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/tag.rs");
+    let tag = fs::read_to_string(tmp_dir.path().join("entity/src/tag.rs")).unwrap();
+    assert!(!tag.contains("dataloader"));
+    assert!(!tag.contains("async_trait"));
+}
+
+#[test]
+fn gen_user_model_keeps_hand_written_registrations() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let resource_dir =
+        Path::new("tests/g/resources/gen_user_model_keeps_hand_written_registrations");
+
+    let hand_written = [
+        "src/graphql/query/mod.rs",
+        "src/graphql/query/health.rs",
+        "migration/src/lib.rs",
+        "migration/src/m20220101_000000_create_extensions.rs",
+    ];
+    for path in hand_written {
+        let target = tmp_dir.path().join(path);
+        fs::create_dir_all(target.parent().unwrap()).unwrap();
+        fs::copy(resource_dir.join("before").join(path), target).unwrap();
+    }
+    // Mid-edit, so it doesn't parse.
+    let unparsable = "pub use async_graphql;\npub mod health\n";
+    let entity_lib = tmp_dir.path().join("entity/src/lib.rs");
+    fs::create_dir_all(entity_lib.parent().unwrap()).unwrap();
+    fs::write(&entity_lib, unparsable).unwrap();
+
+    let test_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
+    process_g(
+        "user",
+        &[],
+        ModelOptions::default(),
+        test_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/query/mod.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "migration/src/lib.rs");
+    assert_eq!(fs::read_to_string(&entity_lib).unwrap(), unparsable);
+}
+
+#[test]
+fn gen_schema() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let resource_dir = Path::new("tests/g/resources/gen_schema");
+
+    let test_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
+    let schema = Schema::load(&resource_dir.join("zapp.schema.yaml")).unwrap();
+    process_schema(&schema, test_dt, tmp_dir.path(), ConflictPolicy::Force);
+
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/user.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/post.rs");
+    assert_file_equality(
+        resource_dir,
+        tmp_dir.path(),
+        "migration/src/m20220716_233933_create_user_table.rs",
+    );
+    assert_file_equality(
+        resource_dir,
+        tmp_dir.path(),
+        "migration/src/m20220716_233934_create_post_table.rs",
+    );
+    assert_file_equality(resource_dir, tmp_dir.path(), "migration/src/lib.rs");
+}
+
+#[test]
+fn gen_add_age_to_user_migration() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let resource_dir = Path::new("tests/g/resources/gen_add_age_to_user_migration");
+
+    let user_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
+    process_g(
+        "user",
+        &[],
+        ModelOptions::default(),
+        user_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+
+    let alter_dt = NaiveDate::from_ymd(2022, 7, 17).and_hms(9, 12, 45);
+    let args = ["age:i32=0".to_string(), "bio:text?".to_string()];
+    let tables = model_names(tmp_dir.path());
+    let alteration = Alteration::parse("add_age_and_bio_to_user", &args, false, &tables).unwrap();
+    process_alter(
+        "add_age_and_bio_to_user",
+        &alteration,
+        alter_dt,
+        tmp_dir.path(),
+    );
+
+    assert_file_equality(
+        resource_dir,
+        tmp_dir.path(),
+        "migration/src/m20220717_091245_add_age_and_bio_to_user.rs",
+    );
+    assert_file_equality(resource_dir, tmp_dir.path(), "migration/src/lib.rs");
+
+    for (name, args, minute) in [
+        ("rename_bio_to_about_in_user", vec![], 13),
+        ("remove_age_from_user", vec!["age:i32=0".to_string()], 14),
+    ] {
+        let alteration = Alteration::parse(name, &args, false, &tables).unwrap();
+        let alter_dt = NaiveDate::from_ymd(2022, 7, 17).and_hms(9, minute, 45);
+        process_alter(name, &alteration, alter_dt, tmp_dir.path());
+    }
+    assert_file_equality(
+        resource_dir,
+        tmp_dir.path(),
+        "migration/src/m20220717_091345_rename_bio_to_about_in_user.rs",
+    );
+    assert_file_equality(
+        resource_dir,
+        tmp_dir.path(),
+        "migration/src/m20220717_091445_remove_age_from_user.rs",
+    );
+
+    let args = ["reply_to:i32?".to_string()];
+    let alteration = Alteration::parse("add_reply_to_to_user", &args, false, &tables).unwrap();
+    assert_eq!(alteration.model(), "user");
+    let ambiguous = Alteration::parse("add_reply_to_to_user", &args, false, &[]).unwrap_err();
+    assert!(ambiguous.contains("`to_user` or `user`"));
+    assert!(Alteration::parse(
+        "rename_due_to_date_to_deadline_in_user",
+        &[],
+        false,
+        &tables
+    )
+    .is_err());
+}
+
+#[test]
+fn gen_auto_migration_from_entity_changes() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let resource_dir = Path::new("tests/g/resources/gen_auto_migration_from_entity_changes");
+
+    let user_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
+    let fields = ["name:string", "age:i32"]
+        .iter()
+        .map(|f| f.parse::<Field>().unwrap())
+        .collect::<Vec<_>>();
+    process_g(
+        "user",
+        &fields,
+        ModelOptions::default(),
+        user_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+
+    let alter_dt = NaiveDate::from_ymd(2022, 7, 17).and_hms(9, 12, 45);
+    process_auto_alter(None, alter_dt, tmp_dir.path());
+    assert!(!tmp_dir
+        .path()
+        .join("migration/src/m20220717_091245_alter_user_table.rs")
+        .exists());
+
+    let entity_file = tmp_dir.path().join("entity/src/user.rs");
+    let entity = fs::read_to_string(&entity_file).unwrap();
+    let entity = entity.replace("pub name: String,", "").replace(
+        "pub age: i32,",
+        "pub age: Option<i64>,\n    pub nickname: String,",
+    );
+    fs::write(&entity_file, entity).unwrap();
+    process_auto_alter(None, alter_dt, tmp_dir.path());
+
+    assert_file_equality(
+        resource_dir,
+        tmp_dir.path(),
+        "migration/src/m20220717_091245_alter_user_table.rs",
+    );
+    assert_file_equality(
+        resource_dir,
+        tmp_dir.path(),
+        "migration/entity_snapshot.json",
+    );
+}
+
+#[test]
+fn gen_auto_migration_with_date_default() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let resource_dir = Path::new("tests/g/resources/gen_auto_migration_with_date_default");
+
+    let user_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
+    let fields = ["name:string", "born:date"]
+        .iter()
+        .map(|f| f.parse::<Field>().unwrap())
+        .collect::<Vec<_>>();
+    process_g(
+        "user",
+        &fields,
+        ModelOptions::default(),
+        user_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+
+    let entity_file = tmp_dir.path().join("entity/src/user.rs");
+    let entity = fs::read_to_string(&entity_file).unwrap();
+    let entity = entity.replace(
+        "pub born: Date,",
+        "#[sea_orm(default_value = \"2000-01-01\")]\n    pub born: Date,",
+    );
+    fs::write(&entity_file, entity).unwrap();
+
+    let alter_dt = NaiveDate::from_ymd(2022, 7, 17).and_hms(9, 12, 45);
+    process_auto_alter(None, alter_dt, tmp_dir.path());
+
+    let entity = fs::read_to_string(&entity_file).unwrap();
+    let entity = entity.replace("pub name: String,", "pub name: Option<String>,");
+    fs::write(&entity_file, entity).unwrap();
+    process_auto_alter(None, alter_dt, tmp_dir.path());
+
+    assert_file_equality(
+        resource_dir,
+        tmp_dir.path(),
+        "migration/src/m20220717_091245_alter_user_table.rs",
+    );
+    assert_file_equality(
+        resource_dir,
+        tmp_dir.path(),
+        "migration/entity_snapshot.json",
+    );
+}
+
+#[test]
+fn gen_import_schema_sql() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let resource_dir = Path::new("tests/g/resources/gen_import_schema_sql");
+
+    let sql = fs::read_to_string(resource_dir.join("schema.sql")).unwrap();
+    let schema = Schema::from_sql(&sql).unwrap();
+    let models = schema
+        .models
+        .iter()
+        .map(|m| m.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(models, ["user", "post"]);
+    assert_eq!(schema.joins, [("user".to_string(), "post".to_string())]);
+
+    let test_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
+    process_import(&schema, test_dt, tmp_dir.path(), ConflictPolicy::Force);
+
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/user.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/post.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/user_post.rs");
+    assert_file_equality(
+        resource_dir,
+        tmp_dir.path(),
+        "migration/src/m20220716_233933_baseline.rs",
+    );
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/mutation/user.rs");
+
+    // A refused model is left alone, the others are still generated.
+    let user_file = tmp_dir.path().join("entity/src/user.rs");
+    let user = fs::read_to_string(&user_file).unwrap() + "// hand-written\n";
+    fs::write(&user_file, &user).unwrap();
+    for path in [
+        "entity/src/post.rs",
+        "src/graphql/query/post.rs",
+        "src/graphql/mutation/post.rs",
+    ] {
+        fs::remove_file(tmp_dir.path().join(path)).unwrap();
+    }
+    process_import(&schema, test_dt, tmp_dir.path(), ConflictPolicy::Prompt);
+
+    assert_eq!(fs::read_to_string(&user_file).unwrap(), user);
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/post.rs");
+}
+
+#[test]
+fn gen_user_model_with_enum_fields() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let resource_dir = Path::new("tests/g/resources/gen_user_model_with_enum_fields");
+
+    let test_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
+    let fields = [
+        "name:string",
+        "status:enum(active,suspended,deleted)=active",
+        "role:enum(member,admin)?",
+    ]
+    .iter()
+    .map(|f| f.parse::<Field>().unwrap())
+    .collect::<Vec<_>>();
+
+    process_g(
+        "user",
+        &fields,
+        ModelOptions::default(),
+        test_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/user.rs");
+    assert_file_equality(
+        resource_dir,
+        tmp_dir.path(),
+        "migration/src/m20220716_233933_create_user_table.rs",
+    );
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/mutation/user.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/query/user.rs");
+}
+
+#[test]
+fn gen_post_model_with_soft_delete() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let resource_dir = Path::new("tests/g/resources/gen_post_model_with_soft_delete");
+
+    let test_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
+    let fields = ["title:string".parse::<Field>().unwrap()];
+    let options = ModelOptions {
+        soft_delete: true,
+        ..Default::default()
+    };
+
+    process_g(
+        "post",
+        &fields,
+        options,
+        test_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/post.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/query/post.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/mutation/post.rs");
+}
+
+#[test]
+fn gen_post_model_tests() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let resource_dir = Path::new("tests/g/resources/gen_post_model_tests");
+    fs::write(
+        tmp_dir.path().join("Cargo.toml"),
+        "[package]\nname = \"my-app\"\n",
+    )
+    .unwrap();
+    process_g(
+        "tag",
+        &[],
+        ModelOptions::default(),
+        NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 32),
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+    assert!(!tmp_dir.path().join("tests/tag_test.rs").exists());
+    for path in ["src/lib.rs", "src/db.rs"] {
+        fs::copy(
+            resource_dir.join("before").join(path),
+            tmp_dir.path().join(path),
+        )
+        .unwrap();
+    }
+
+    let test_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
+    let fields = [
+        "title:string:len(8,100)",
+        "status:enum(draft,published)",
+        "rating:i32:range(1,5)?",
+        "published_on:date?",
+    ]
+    .iter()
+    .map(|f| f.parse::<Field>().unwrap())
+    .collect::<Vec<_>>();
+    let options = ModelOptions {
+        soft_delete: true,
+        pk: PrimaryKey::Uuid,
+        ..Default::default()
+    };
+
+    process_g(
+        "post",
+        &fields,
+        options,
+        test_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+
+    assert_file_equality(resource_dir, tmp_dir.path(), "tests/post_test.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/db.rs");
+}
+
+#[test]
+fn gen_user_and_post_seeders() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let resource_dir = Path::new("tests/g/resources/gen_user_and_post_seeders");
+
+    let test_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
+    let user_fields = [
+        "email:string:len(14,16):email",
+        "name:string:len(8,100)",
+        "role:enum(admin,member)",
+    ]
+    .iter()
+    .map(|f| f.parse::<Field>().unwrap())
+    .collect::<Vec<_>>();
+    let post_fields = ["title:string", "user:references"]
+        .iter()
+        .map(|f| f.parse::<Field>().unwrap())
+        .collect::<Vec<_>>();
+    let user_options = ModelOptions {
+        pk: PrimaryKey::Uuid,
+        ..Default::default()
+    };
+
+    for (model, fields, options) in [
+        ("user", &user_fields, user_options),
+        ("post", &post_fields, ModelOptions::default()),
+    ] {
+        process_g(
+            model,
+            fields,
+            options,
+            test_dt,
+            tmp_dir.path(),
+            ConflictPolicy::Force,
+        );
+        process_seed(model, test_dt, tmp_dir.path(), ConflictPolicy::Force);
+    }
+
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/bin/seed/main.rs");
+    assert_file_equality(
+        resource_dir,
+        tmp_dir.path(),
+        "src/bin/seed/s20220716_233933_seed_user.rs",
+    );
+    assert_file_equality(
+        resource_dir,
+        tmp_dir.path(),
+        "src/bin/seed/s20220716_233934_seed_post.rs",
+    );
+
+    process_destroy("post", false, tmp_dir.path());
+    assert!(!tmp_dir
+        .path()
+        .join("src/bin/seed/s20220716_233934_seed_post.rs")
+        .exists());
+}
+
+#[test]
+fn gen_user_and_post_factories() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let resource_dir = Path::new("tests/g/resources/gen_user_and_post_factories");
+
+    let test_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
+    let user_fields = [
+        "email:string:email",
+        "name:string:len(8,100)",
+        "age:i32:range(18,150)?",
+        "role:enum(admin,member)",
+        "born_on:date?",
+    ]
+    .iter()
+    .map(|f| f.parse::<Field>().unwrap())
+    .collect::<Vec<_>>();
+    let post_fields = [
+        "title:string",
+        "body:text",
+        "score:f32",
+        "published:bool?",
+        "contact:string:email:len(16,40)",
+        "user:references",
+    ]
+    .iter()
+    .map(|f| f.parse::<Field>().unwrap())
+    .collect::<Vec<_>>();
+
+    fs::create_dir_all(tmp_dir.path().join("entity")).unwrap();
+    fs::write(
+        tmp_dir.path().join("entity/Cargo.toml"),
+        "[package]\nname = \"entity\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\nserde = { version = \"1\", features = [\"derive\"] }\n\n[dependencies.sea-orm]\nversion = \"^0.9.0\"\n",
+    )
+    .unwrap();
+    for (model, fields) in [("user", &user_fields), ("post", &post_fields)] {
+        process_g(
+            model,
+            fields,
+            ModelOptions::default(),
+            test_dt,
+            tmp_dir.path(),
+            ConflictPolicy::Force,
+        );
+    }
+
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/Cargo.toml");
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/lib.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/factories/mod.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/factories/user.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/factories/post.rs");
+
+    process_destroy("post", false, tmp_dir.path());
+    assert!(!tmp_dir.path().join("entity/src/factories/post.rs").exists());
+    let factories = fs::read_to_string(tmp_dir.path().join("entity/src/factories/mod.rs")).unwrap();
+    assert!(!factories.contains("post"));
+
+    assert!("contact:string:email:len(5,13)".parse::<Field>().is_err());
+    assert!("contact:string:len(5,14):email".parse::<Field>().is_ok());
+}
+
+#[test]
+fn gen_user_model_with_uuid_pk() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let resource_dir = Path::new("tests/g/resources/gen_user_model_with_uuid_pk");
+
+    let user_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
+    let post_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 40, 12);
+    let user_fields = ["name:string".parse::<Field>().unwrap()];
+    let post_fields = ["user:references".parse::<Field>().unwrap()];
+    let options = ModelOptions {
+        pk: PrimaryKey::Uuid,
+        ..Default::default()
+    };
+
+    // A filter file from before uuid columns could be filtered on.
+    let filter_file = tmp_dir.path().join("src/graphql/query/filter.rs");
+    fs::create_dir_all(filter_file.parent().unwrap()).unwrap();
+    fs::copy(
+        resource_dir.join("before/src/graphql/query/filter.rs"),
+        &filter_file,
+    )
+    .unwrap();
+
+    process_g(
+        "user",
+        &user_fields,
+        options,
+        user_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+    process_g(
+        "post",
+        &post_fields,
+        ModelOptions::default(),
+        post_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/user.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/post.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/query/user.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/query/post.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/query/filter.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/mutation/user.rs");
+}
+
+#[test]
+fn gen_models_with_timestamp_options() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let resource_dir = Path::new("tests/g/resources/gen_models_with_timestamp_options");
+
+    let event_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
+    let tag_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 40, 12);
+    let event_fields = ["starts_at:datetimetz".parse::<Field>().unwrap()];
+    let tag_fields = ["name:string".parse::<Field>().unwrap()];
+    let event_options = ModelOptions {
+        soft_delete: true,
+        timestamps: Timestamps::Tz,
+        ..Default::default()
+    };
+    let tag_options = ModelOptions {
+        timestamps: Timestamps::None,
+        ..Default::default()
+    };
+
+    process_g(
+        "event",
+        &event_fields,
+        event_options,
+        event_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+    process_g(
+        "tag",
+        &tag_fields,
+        tag_options,
+        tag_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/event.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/query/event.rs");
+    assert_file_equality(
+        resource_dir,
+        tmp_dir.path(),
+        "src/graphql/mutation/event.rs",
+    );
+    assert_file_equality(resource_dir, tmp_dir.path(), "entity/src/tag.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/query/tag.rs");
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/mutation/tag.rs");
+}
+
+#[test]
+fn gen_user_model_with_subscriptions() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let resource_dir = Path::new("tests/g/resources/gen_user_model_with_subscriptions");
+
+    let test_dt = NaiveDate::from_ymd(2022, 7, 16).and_hms(23, 39, 33);
+    let fields = ["name:string".parse::<Field>().unwrap()];
+    let options = ModelOptions {
+        subscriptions: true,
+        ..Default::default()
+    };
+    let graphql_dir = tmp_dir.path().join("src").join("graphql");
+    fs::create_dir_all(&graphql_dir).unwrap();
+    fs::write(
+        graphql_dir.join("mod.rs"),
+        "pub mod mutation;\npub mod query;\n",
+    )
+    .unwrap();
+
+    process_g(
+        "user",
+        &fields,
+        options,
+        test_dt,
+        tmp_dir.path(),
+        ConflictPolicy::Force,
+    );
+
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/mod.rs");
+    assert_file_equality(
+        resource_dir,
+        tmp_dir.path(),
+        "src/graphql/subscription/mod.rs",
+    );
+    assert_file_equality(
+        resource_dir,
+        tmp_dir.path(),
+        "src/graphql/subscription/broker.rs",
+    );
+    assert_file_equality(
+        resource_dir,
+        tmp_dir.path(),
+        "src/graphql/subscription/user.rs",
+    );
+    assert_file_equality(resource_dir, tmp_dir.path(), "src/graphql/mutation/user.rs");
+
+    process_destroy("user", false, tmp_dir.path());
+
+    assert!(!graphql_dir.join("subscription").join("user.rs").exists());
+}
+
+/// Scaffolds an app from the downloaded template, generates models into it and
+/// runs their tests against the template's own `Database`. Needs the network
+/// and builds the whole app, run it with `cargo test -- --ignored`.
+#[test]
+#[ignore]
+fn scaffolded_app_passes_generated_tests() {
+    let tmp_dir = TempDir::new("zapp-g-integration-tests").unwrap();
+    let app_dir = tmp_dir.path().join("my_app");
+    let run = |program: &str, args: &[&str], dir: &Path| {
+        let status = Command::new(program)
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        assert!(status.success(), "`{} {}` failed", program, args.join(" "));
+    };
+    let zapp = env!("CARGO_BIN_EXE_zapp");
+
+    run(zapp, &["new", "my_app"], tmp_dir.path());
+    assert!(
+        app_dir.join("Cargo.toml").exists(),
+        "the app template could not be downloaded"
+    );
+    // What the README asks of an app running the generated tests.
+    let lib_file = app_dir.join("src/lib.rs");
+    if !lib_file.exists() {
+        fs::write(&lib_file, "pub mod db;\npub mod graphql;\n").unwrap();
+    }
+    run(
+        zapp,
+        &[
+            "g",
+            "model",
+            "user",
+            "name:string:len(1,100)",
+            "email:string:email",
+        ],
+        &app_dir,
+    );
+    run(
+        zapp,
+        &[
+            "g",
+            "model",
+            "post",
+            "title:string",
+            "status:enum(draft,published)",
+            "user:references",
+            "--soft-delete",
+        ],
+        &app_dir,
+    );
+    run(
+        zapp,
+        &["g", "model", "tag", "name:string", "--pk", "uuid"],
+        &app_dir,
+    );
+
+    run(
+        "cargo",
+        &["add", "--dev", "sea-orm", "--features", "mock"],
+        &app_dir,
+    );
+    run("cargo", &["build"], &app_dir);
+    run(
+        "cargo",
+        &["build", "--features", "factories"],
+        &app_dir.join("entity"),
+    );
+
+    run("cargo", &["test"], &app_dir);
+}
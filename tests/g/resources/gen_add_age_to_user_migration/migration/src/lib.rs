@@ -0,0 +1,13 @@
+pub use sea_orm_migration::prelude::*;
+pub struct Migrator;
+mod m20220716_233933_create_user_table;
+mod m20220717_091245_add_age_and_bio_to_user;
+#[async_trait::async_trait]
+impl MigratorTrait for Migrator {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        vec![
+            Box::new(m20220716_233933_create_user_table::Migration),
+            Box::new(m20220717_091245_add_age_and_bio_to_user::Migration)
+        ]
+    }
+}
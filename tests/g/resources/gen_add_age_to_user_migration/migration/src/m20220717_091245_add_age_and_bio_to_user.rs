@@ -0,0 +1,61 @@
+use sea_orm_migration::prelude::*;
+pub struct Migration;
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20220717_091245_add_age_and_bio_to_user"
+    }
+}
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if !manager.has_column("user", "age").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("user"))
+                        .add_column(
+                            ColumnDef::new(Alias::new("age"))
+                                .integer()
+                                .not_null()
+                                .default(0),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+        if !manager.has_column("user", "bio").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("user"))
+                        .add_column(ColumnDef::new(Alias::new("bio")).text().null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.has_column("user", "bio").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("user"))
+                        .drop_column(Alias::new("bio"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        if manager.has_column("user", "age").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("user"))
+                        .drop_column(Alias::new("age"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use sea_orm_migration::prelude::*;
+pub struct Migration;
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20220717_091345_rename_bio_to_about_in_user"
+    }
+}
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.has_column("user", "bio").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("user"))
+                        .rename_column(Alias::new("bio"), Alias::new("about"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.has_column("user", "about").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("user"))
+                        .rename_column(Alias::new("about"), Alias::new("bio"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
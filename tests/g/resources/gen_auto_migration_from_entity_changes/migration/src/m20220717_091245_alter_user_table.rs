@@ -0,0 +1,80 @@
+use sea_orm_migration::prelude::*;
+pub struct Migration;
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20220717_091245_alter_user_table"
+    }
+}
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("user"))
+                    .modify_column(
+                        ColumnDef::new(Alias::new("age")).big_integer().null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        if !manager.has_column("user", "nickname").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("user"))
+                        .add_column(
+                            ColumnDef::new(Alias::new("nickname")).string().not_null(),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+        if manager.has_column("user", "name").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("user"))
+                        .drop_column(Alias::new("name"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if !manager.has_column("user", "name").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("user"))
+                        .add_column(
+                            ColumnDef::new(Alias::new("name")).string().not_null(),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+        if manager.has_column("user", "nickname").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("user"))
+                        .drop_column(Alias::new("nickname"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("user"))
+                    .modify_column(
+                        ColumnDef::new(Alias::new("age")).integer().not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
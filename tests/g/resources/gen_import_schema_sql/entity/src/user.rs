@@ -0,0 +1,90 @@
+use async_graphql::*;
+use chrono::Utc;
+use sea_orm::{entity::prelude::*, DeleteMany, Set};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    DeriveEntityModel,
+    Serialize,
+    Deserialize,
+    SimpleObject
+)]
+#[sea_orm(table_name = "user")]
+#[graphql(complex)]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    #[serde(skip_deserializing)]
+    pub id: i32,
+    pub name: String,
+    pub email: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub bio: Option<String>,
+    #[sea_orm(default_value = "member")]
+    pub role: String,
+    #[sea_orm(default_value = 0.0)]
+    pub score: f64,
+    #[sea_orm(indexed)]
+    pub created_at: DateTime,
+    #[sea_orm(indexed)]
+    pub updated_at: DateTime,
+}
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::post::Entity")]
+    Post,
+}
+impl ActiveModelBehavior for ActiveModel {
+    fn before_save(mut self, insert: bool) -> Result<Self, DbErr> {
+        let now = Utc::now().naive_utc();
+        if insert && self.created_at.is_not_set() {
+            self.created_at = Set(now);
+        }
+        self.updated_at = Set(now);
+        Ok(self)
+    }
+}
+impl Entity {
+    pub fn find_by_id(id: i32) -> Select<Entity> {
+        Self::find().filter(Column::Id.eq(id))
+    }
+    pub fn delete_by_id(id: i32) -> DeleteMany<Entity> {
+        Self::delete_many().filter(Column::Id.eq(id))
+    }
+}
+impl Related<super::post::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Post.def()
+    }
+}
+#[ComplexObject]
+impl Model {
+    async fn posts(&self, ctx: &Context<'_>) -> Result<Vec<super::post::Model>> {
+        let loader = ctx.data::<dataloader::DataLoader<super::post::UserIdLoader>>()?;
+        Ok(loader.load_one(self.id).await?.unwrap_or_default())
+    }
+}
+/// Batches lookups by id, for the relations pointing at this entity.
+pub struct IdLoader {
+    db: DatabaseConnection,
+}
+impl IdLoader {
+    pub fn new(db: DatabaseConnection) -> Self {
+        IdLoader { db }
+    }
+}
+#[async_trait::async_trait]
+impl dataloader::Loader<i32> for IdLoader {
+    type Value = Model;
+    type Error = Arc<DbErr>;
+    async fn load(&self, keys: &[i32]) -> Result<HashMap<i32, Model>, Self::Error> {
+        let models = Entity::find()
+            .filter(Column::Id.is_in(keys.iter().cloned()))
+            .all(&self.db)
+            .await?;
+        Ok(models.into_iter().map(|model| (model.id, model)).collect())
+    }
+}
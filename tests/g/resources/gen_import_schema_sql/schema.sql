@@ -0,0 +1,69 @@
+--
+-- PostgreSQL database dump
+--
+
+SET statement_timeout = 0;
+SELECT pg_catalog.set_config('search_path', '', false);
+
+CREATE FUNCTION public.touch() RETURNS trigger
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    NEW.updated_at = now(); RETURN NEW;
+END;
+$$;
+
+CREATE TABLE public."user" (
+    id integer NOT NULL,
+    name character varying(255) NOT NULL,
+    email character varying NOT NULL,
+    bio text,
+    role character varying DEFAULT 'member'::character varying NOT NULL,
+    score double precision DEFAULT 0 NOT NULL,
+    rank smallint,
+    balance numeric(10,2),
+    tags text[],
+    created_at timestamp without time zone DEFAULT now() NOT NULL,
+    updated_at timestamp without time zone NOT NULL
+);
+
+CREATE SEQUENCE public.user_id_seq AS integer START WITH 1 INCREMENT BY 1;
+ALTER SEQUENCE public.user_id_seq OWNED BY public."user".id;
+ALTER TABLE ONLY public."user" ALTER COLUMN id SET DEFAULT nextval('public.user_id_seq'::regclass);
+
+CREATE TABLE public.post (
+    id serial PRIMARY KEY,
+    title varchar(120) NOT NULL,
+    published boolean DEFAULT false NOT NULL,
+    user_id integer NOT NULL REFERENCES public."user" (id) ON DELETE CASCADE,
+    created_at timestamp NOT NULL,
+    updated_at timestamp NOT NULL
+);
+
+CREATE TABLE public.audit_log (
+    uuid uuid PRIMARY KEY,
+    payload jsonb
+);
+
+CREATE TABLE public.event (
+    id serial PRIMARY KEY,
+    created_at timestamp with time zone NOT NULL,
+    updated_at timestamp without time zone NOT NULL
+);
+
+CREATE TABLE public.user_post (
+    user_id integer NOT NULL,
+    post_id integer NOT NULL,
+    CONSTRAINT user_post_pkey PRIMARY KEY (user_id, post_id)
+);
+
+ALTER TABLE ONLY public."user"
+    ADD CONSTRAINT user_pkey PRIMARY KEY (id);
+ALTER TABLE ONLY public."user"
+    ADD CONSTRAINT user_email_key UNIQUE (email);
+ALTER TABLE ONLY public.user_post
+    ADD CONSTRAINT user_post_user_id_fkey FOREIGN KEY (user_id) REFERENCES public."user"(id);
+ALTER TABLE ONLY public.user_post
+    ADD CONSTRAINT user_post_post_id_fkey FOREIGN KEY (post_id) REFERENCES public.post(id);
+
+CREATE INDEX post_title_idx ON public.post USING btree (title);
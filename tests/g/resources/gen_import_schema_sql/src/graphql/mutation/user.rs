@@ -0,0 +1,92 @@
+use async_graphql::{Context, Object, Result, Error, ErrorExtensions};
+use entity::async_graphql::{self, InputObject, MaybeUndefined};
+use entity::user;
+use sea_orm::{ActiveModelTrait, Set};
+use crate::graphql::mutation::common::*;
+use crate::db::Database;
+#[derive(InputObject)]
+pub struct CreateUserInput {
+    pub id: i32,
+    pub name: String,
+    pub email: String,
+    pub bio: Option<String>,
+    pub role: Option<String>,
+    pub score: Option<f64>,
+}
+#[derive(InputObject)]
+pub struct UpdateUserInput {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub bio: MaybeUndefined<String>,
+    pub role: Option<String>,
+    pub score: Option<f64>,
+}
+fn not_found(id: i32) -> Error {
+    Error::new(format!("user {} not found", id))
+        .extend_with(|_, e| e.set("code", "NOT_FOUND"))
+}
+#[derive(Default)]
+pub struct UserMutation;
+#[Object]
+impl UserMutation {
+    pub async fn create_user(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateUserInput,
+    ) -> Result<user::Model> {
+        let db = ctx.data::<Database>().unwrap();
+        let user = user::ActiveModel {
+            id: Set(input.id),
+            name: Set(input.name),
+            email: Set(input.email),
+            bio: Set(input.bio),
+            role: Set(input.role.unwrap_or_else(|| "member".to_owned())),
+            score: Set(input.score.unwrap_or(0.0)),
+            ..Default::default()
+        };
+        Ok(user.insert(db.get_connection()).await?)
+    }
+    pub async fn update_user(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+        input: UpdateUserInput,
+    ) -> Result<user::Model, Error> {
+        let db = ctx.data::<Database>().unwrap();
+        let user: user::Model = user::Entity::find_by_id(id)
+            .one(db.get_connection())
+            .await?
+            .ok_or_else(|| not_found(id))?;
+        let mut user: user::ActiveModel = user.into();
+        if let Some(name) = input.name {
+            user.name = Set(name);
+        }
+        if let Some(email) = input.email {
+            user.email = Set(email);
+        }
+        match input.bio {
+            MaybeUndefined::Value(bio) => user.bio = Set(Some(bio)),
+            MaybeUndefined::Null => user.bio = Set(None),
+            MaybeUndefined::Undefined => {}
+        }
+        if let Some(role) = input.role {
+            user.role = Set(role);
+        }
+        if let Some(score) = input.score {
+            user.score = Set(score);
+        }
+        let user: user::Model = user.update(db.get_connection()).await?;
+        Ok(user)
+    }
+    pub async fn delete_user(&self, ctx: &Context<'_>, id: i32) -> Result<DeleteResult> {
+        let db = ctx.data::<Database>().unwrap();
+        let res = user::Entity::delete_by_id(id).exec(db.get_connection()).await?;
+        if res.rows_affected == 0 {
+            return Err(not_found(id));
+        }
+        Ok(DeleteResult {
+            success: true,
+            rows_affected: res.rows_affected,
+        })
+    }
+}
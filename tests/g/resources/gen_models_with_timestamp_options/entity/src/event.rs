@@ -0,0 +1,50 @@
+use async_graphql::*;
+use chrono::Utc;
+use sea_orm::{entity::prelude::*, DeleteMany, Set};
+use serde::{Deserialize, Serialize};
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    DeriveEntityModel,
+    Serialize,
+    Deserialize,
+    SimpleObject
+)]
+#[sea_orm(table_name = "event")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    #[serde(skip_deserializing)]
+    pub id: i32,
+    pub starts_at: DateTimeWithTimeZone,
+    #[sea_orm(indexed)]
+    pub created_at: DateTimeWithTimeZone,
+    #[sea_orm(indexed)]
+    pub updated_at: DateTimeWithTimeZone,
+    #[sea_orm(nullable)]
+    pub deleted_at: Option<DateTimeWithTimeZone>,
+}
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+impl ActiveModelBehavior for ActiveModel {
+    fn before_save(mut self, insert: bool) -> Result<Self, DbErr> {
+        let now = DateTimeWithTimeZone::from(Utc::now());
+        if insert && self.created_at.is_not_set() {
+            self.created_at = Set(now);
+        }
+        self.updated_at = Set(now);
+        Ok(self)
+    }
+}
+impl Entity {
+    pub fn find_by_id(id: i32) -> Select<Entity> {
+        Self::find().filter(Column::Id.eq(id)).filter(Column::DeletedAt.is_null())
+    }
+    /// A soft deleted row, to restore it.
+    pub fn find_deleted_by_id(id: i32) -> Select<Entity> {
+        Self::find().filter(Column::Id.eq(id)).filter(Column::DeletedAt.is_not_null())
+    }
+    pub fn delete_by_id(id: i32) -> DeleteMany<Entity> {
+        Self::delete_many().filter(Column::Id.eq(id))
+    }
+}
@@ -0,0 +1,104 @@
+use async_graphql::{Context, Object, Result, Error, ErrorExtensions};
+use entity::async_graphql::{self, InputObject};
+use entity::event;
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, Set};
+use sea_orm::prelude::DateTimeWithTimeZone;
+use crate::graphql::mutation::common::*;
+use crate::db::Database;
+#[derive(InputObject)]
+pub struct CreateEventInput {
+    pub id: i32,
+    pub starts_at: DateTimeWithTimeZone,
+}
+#[derive(InputObject)]
+pub struct UpdateEventInput {
+    pub starts_at: Option<DateTimeWithTimeZone>,
+}
+fn not_found(id: i32) -> Error {
+    Error::new(format!("event {} not found", id))
+        .extend_with(|_, e| e.set("code", "NOT_FOUND"))
+}
+#[derive(Default)]
+pub struct EventMutation;
+#[Object]
+impl EventMutation {
+    pub async fn create_event(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateEventInput,
+    ) -> Result<event::Model> {
+        let db = ctx.data::<Database>().unwrap();
+        let event = event::ActiveModel {
+            id: Set(input.id),
+            starts_at: Set(input.starts_at),
+            ..Default::default()
+        };
+        Ok(event.insert(db.get_connection()).await?)
+    }
+    pub async fn update_event(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+        input: UpdateEventInput,
+    ) -> Result<event::Model, Error> {
+        let db = ctx.data::<Database>().unwrap();
+        let event: event::Model = event::Entity::find_by_id(id)
+            .one(db.get_connection())
+            .await?
+            .ok_or_else(|| not_found(id))?;
+        let mut event: event::ActiveModel = event.into();
+        if let Some(starts_at) = input.starts_at {
+            event.starts_at = Set(starts_at);
+        }
+        let event: event::Model = event.update(db.get_connection()).await?;
+        Ok(event)
+    }
+    pub async fn delete_event(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+    ) -> Result<DeleteResult> {
+        let db = ctx.data::<Database>().unwrap();
+        let event: event::Model = event::Entity::find_by_id(id)
+            .one(db.get_connection())
+            .await?
+            .ok_or_else(|| not_found(id))?;
+        let mut event: event::ActiveModel = event.into();
+        event.deleted_at = Set(Some(DateTimeWithTimeZone::from(Utc::now())));
+        event.update(db.get_connection()).await?;
+        Ok(DeleteResult {
+            success: true,
+            rows_affected: 1,
+        })
+    }
+    pub async fn restore_event(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+    ) -> Result<event::Model> {
+        let db = ctx.data::<Database>().unwrap();
+        let event: event::Model = event::Entity::find_deleted_by_id(id)
+            .one(db.get_connection())
+            .await?
+            .ok_or_else(|| not_found(id))?;
+        let mut event: event::ActiveModel = event.into();
+        event.deleted_at = Set(None);
+        Ok(event.update(db.get_connection()).await?)
+    }
+    pub async fn hard_delete_event(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+    ) -> Result<DeleteResult> {
+        let db = ctx.data::<Database>().unwrap();
+        let res = event::Entity::delete_by_id(id).exec(db.get_connection()).await?;
+        if res.rows_affected == 0 {
+            return Err(not_found(id));
+        }
+        Ok(DeleteResult {
+            success: true,
+            rows_affected: res.rows_affected,
+        })
+    }
+}
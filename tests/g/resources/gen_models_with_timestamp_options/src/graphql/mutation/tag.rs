@@ -0,0 +1,66 @@
+use async_graphql::{Context, Object, Result, Error, ErrorExtensions};
+use entity::async_graphql::{self, InputObject};
+use entity::tag;
+use sea_orm::{ActiveModelTrait, Set};
+use crate::graphql::mutation::common::*;
+use crate::db::Database;
+#[derive(InputObject)]
+pub struct CreateTagInput {
+    pub id: i32,
+    pub name: String,
+}
+#[derive(InputObject)]
+pub struct UpdateTagInput {
+    pub name: Option<String>,
+}
+fn not_found(id: i32) -> Error {
+    Error::new(format!("tag {} not found", id))
+        .extend_with(|_, e| e.set("code", "NOT_FOUND"))
+}
+#[derive(Default)]
+pub struct TagMutation;
+#[Object]
+impl TagMutation {
+    pub async fn create_tag(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateTagInput,
+    ) -> Result<tag::Model> {
+        let db = ctx.data::<Database>().unwrap();
+        let tag = tag::ActiveModel {
+            id: Set(input.id),
+            name: Set(input.name),
+            ..Default::default()
+        };
+        Ok(tag.insert(db.get_connection()).await?)
+    }
+    pub async fn update_tag(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+        input: UpdateTagInput,
+    ) -> Result<tag::Model, Error> {
+        let db = ctx.data::<Database>().unwrap();
+        let tag: tag::Model = tag::Entity::find_by_id(id)
+            .one(db.get_connection())
+            .await?
+            .ok_or_else(|| not_found(id))?;
+        let mut tag: tag::ActiveModel = tag.into();
+        if let Some(name) = input.name {
+            tag.name = Set(name);
+        }
+        let tag: tag::Model = tag.update(db.get_connection()).await?;
+        Ok(tag)
+    }
+    pub async fn delete_tag(&self, ctx: &Context<'_>, id: i32) -> Result<DeleteResult> {
+        let db = ctx.data::<Database>().unwrap();
+        let res = tag::Entity::delete_by_id(id).exec(db.get_connection()).await?;
+        if res.rows_affected == 0 {
+            return Err(not_found(id));
+        }
+        Ok(DeleteResult {
+            success: true,
+            rows_affected: res.rows_affected,
+        })
+    }
+}
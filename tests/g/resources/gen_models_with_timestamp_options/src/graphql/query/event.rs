@@ -0,0 +1,258 @@
+use async_graphql::connection::{query, Connection, Edge};
+use async_graphql::{Context, Enum, InputObject, Object, Result, SimpleObject};
+use entity::{async_graphql, event};
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::{
+    ColumnTrait, Condition, EntityTrait, Order, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Select, Value,
+};
+use crate::db::Database;
+use crate::graphql::query::filter::Filter;
+/// Page size when neither `first` nor `last` is given.
+const DEFAULT_PAGE_SIZE: usize = 20;
+#[derive(SimpleObject)]
+pub struct EventConnectionFields {
+    pub total_count: u64,
+}
+#[derive(InputObject)]
+pub struct EventFilter {
+    pub id: Option<Filter<i32>>,
+    pub starts_at: Option<Filter<DateTimeWithTimeZone>>,
+    pub created_at: Option<Filter<DateTimeWithTimeZone>>,
+    pub updated_at: Option<Filter<DateTimeWithTimeZone>>,
+    pub and: Option<Vec<EventFilter>>,
+    pub or: Option<Vec<EventFilter>>,
+}
+impl EventFilter {
+    pub fn condition(self) -> Condition {
+        let mut condition = Condition::all();
+        if let Some(id) = self.id {
+            condition = condition.add(id.condition(event::Column::Id));
+        }
+        if let Some(starts_at) = self.starts_at {
+            condition = condition.add(starts_at.condition(event::Column::StartsAt));
+        }
+        if let Some(created_at) = self.created_at {
+            condition = condition.add(created_at.condition(event::Column::CreatedAt));
+        }
+        if let Some(updated_at) = self.updated_at {
+            condition = condition.add(updated_at.condition(event::Column::UpdatedAt));
+        }
+        for filter in self.and.unwrap_or_default() {
+            condition = condition.add(filter.condition());
+        }
+        if let Some(or) = self.or {
+            let mut any = Condition::any();
+            for filter in or {
+                any = any.add(filter.condition());
+            }
+            condition = condition.add(any);
+        }
+        condition
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Enum)]
+pub enum EventOrderBy {
+    IdAsc,
+    IdDesc,
+    StartsAtAsc,
+    StartsAtDesc,
+    CreatedAtAsc,
+    CreatedAtDesc,
+    UpdatedAtAsc,
+    UpdatedAtDesc,
+}
+impl EventOrderBy {
+    fn column(self) -> (event::Column, Order) {
+        match self {
+            EventOrderBy::IdAsc => (event::Column::Id, Order::Asc),
+            EventOrderBy::IdDesc => (event::Column::Id, Order::Desc),
+            EventOrderBy::StartsAtAsc => (event::Column::StartsAt, Order::Asc),
+            EventOrderBy::StartsAtDesc => (event::Column::StartsAt, Order::Desc),
+            EventOrderBy::CreatedAtAsc => (event::Column::CreatedAt, Order::Asc),
+            EventOrderBy::CreatedAtDesc => (event::Column::CreatedAt, Order::Desc),
+            EventOrderBy::UpdatedAtAsc => (event::Column::UpdatedAt, Order::Asc),
+            EventOrderBy::UpdatedAtDesc => (event::Column::UpdatedAt, Order::Desc),
+        }
+    }
+    /// Sorts by the column, ties by `id`, all reversed when paging
+    /// backwards with `last`.
+    pub fn apply(
+        self,
+        select: Select<event::Entity>,
+        backward: bool,
+    ) -> Select<event::Entity> {
+        let (column, order) = self.column();
+        let (order, id_order) = match (order, backward) {
+            (order, false) => (order, Order::Asc),
+            (Order::Asc, true) => (Order::Desc, Order::Desc),
+            (_, true) => (Order::Asc, Order::Desc),
+        };
+        select.order_by(column, order).order_by(event::Column::Id, id_order)
+    }
+    /// Rows after the `anchor` row of a cursor, or before it.
+    ///
+    /// Postgres sorts nulls above every value.
+    pub fn beyond(self, anchor: &event::Model, before: bool) -> Condition {
+        let (column, order) = self.column();
+        let value = match self {
+            EventOrderBy::IdAsc | EventOrderBy::IdDesc => Some(Value::from(anchor.id)),
+            EventOrderBy::StartsAtAsc | EventOrderBy::StartsAtDesc => {
+                Some(Value::from(anchor.starts_at))
+            }
+            EventOrderBy::CreatedAtAsc | EventOrderBy::CreatedAtDesc => {
+                Some(Value::from(anchor.created_at))
+            }
+            EventOrderBy::UpdatedAtAsc | EventOrderBy::UpdatedAtDesc => {
+                Some(Value::from(anchor.updated_at))
+            }
+        };
+        let id = event::Column::Id;
+        let tie = if before { id.lt(anchor.id) } else { id.gt(anchor.id) };
+        let larger = (order == Order::Desc) == before;
+        match value {
+            Some(value) => {
+                let past = if larger {
+                    column.gt(value.clone())
+                } else {
+                    column.lt(value.clone())
+                };
+                let mut condition = Condition::any()
+                    .add(past)
+                    .add(Condition::all().add(column.eq(value)).add(tie));
+                if larger {
+                    condition = condition.add(column.is_null());
+                }
+                condition
+            }
+            None => {
+                let mut condition = Condition::any()
+                    .add(Condition::all().add(column.is_null()).add(tie));
+                if !larger {
+                    condition = condition.add(column.is_not_null());
+                }
+                condition
+            }
+        }
+    }
+}
+#[derive(Default)]
+pub struct EventQuery;
+#[Object]
+impl EventQuery {
+    /// Cursors are the `id` of their row, so pages stay put when rows
+    /// are added or removed. Without `order_by` pages follow `id`.
+    #[allow(clippy::too_many_arguments)]
+    async fn get_events(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<EventFilter>,
+        order_by: Option<EventOrderBy>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<Connection<String, event::Model, EventConnectionFields>> {
+        let db = ctx.data::<Database>().unwrap().get_connection();
+        let mut select = event::Entity::find()
+            .filter(event::Column::DeletedAt.is_null());
+        if let Some(filter) = filter {
+            select = select.filter(filter.condition());
+        }
+        query(
+                after,
+                before,
+                first,
+                last,
+                |
+                    after: Option<String>,
+                    before: Option<String>,
+                    first: Option<usize>,
+                    last: Option<usize>|
+                async move {
+                    let total_count = select.clone().count(db).await?;
+                    let after = after.map(|after| after.parse::<i32>()).transpose()?;
+                    let before = before.map(|before| before.parse::<i32>()).transpose()?;
+                    let backward = first.is_none() && last.is_some();
+                    let limit = first.or(last).unwrap_or(DEFAULT_PAGE_SIZE);
+                    let fetched = limit as u64 + 1;
+                    let mut events = match order_by {
+                        None => {
+                            let mut cursor = select.cursor_by(event::Column::Id);
+                            if let Some(after) = after {
+                                cursor.after(after);
+                            }
+                            if let Some(before) = before {
+                                cursor.before(before);
+                            }
+                            if backward {
+                                cursor.last(fetched);
+                            } else {
+                                cursor.first(fetched);
+                            }
+                            cursor.all(db).await?
+                        }
+                        Some(order_by) => {
+                            for (cursor, before) in [(after, false), (before, true)] {
+                                if let Some(id) = cursor {
+                                    let anchor = event::Entity::find_by_id(id)
+                                        .one(db)
+                                        .await?
+                                        .ok_or("the row of the cursor is gone")?;
+                                    select = select.filter(order_by.beyond(&anchor, before));
+                                }
+                            }
+                            let mut events = order_by
+                                .apply(select, backward)
+                                .limit(fetched)
+                                .all(db)
+                                .await?;
+                            if backward {
+                                events.reverse();
+                            }
+                            events
+                        }
+                    };
+                    let has_more = events.len() > limit;
+                    let (has_previous_page, has_next_page) = if backward {
+                        if has_more {
+                            events.remove(0);
+                        }
+                        (has_more, before.is_some())
+                    } else {
+                        events.truncate(limit);
+                        (after.is_some(), has_more)
+                    };
+                    let mut connection = Connection::with_additional_fields(
+                        has_previous_page,
+                        has_next_page,
+                        EventConnectionFields {
+                            total_count,
+                        },
+                    );
+                    connection
+                        .edges
+                        .extend(
+                            events
+                                .into_iter()
+                                .map(|event| Edge::new(event.id.to_string(), event)),
+                        );
+                    Ok::<_, async_graphql::Error>(connection)
+                },
+            )
+            .await
+    }
+    async fn get_event_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+    ) -> Result<Option<event::Model>> {
+        let db = ctx.data::<Database>().unwrap();
+        Ok(
+            event::Entity::find_by_id(id)
+                .one(db.get_connection())
+                .await
+                .map_err(|e| e.to_string())?,
+        )
+    }
+}
@@ -0,0 +1,232 @@
+use async_graphql::connection::{query, Connection, Edge};
+use async_graphql::{Context, Enum, InputObject, Object, Result, SimpleObject};
+use entity::{async_graphql, tag};
+use sea_orm::{
+    ColumnTrait, Condition, EntityTrait, Order, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Select, Value,
+};
+use crate::db::Database;
+use crate::graphql::query::filter::{Filter, StringFilter};
+/// Page size when neither `first` nor `last` is given.
+const DEFAULT_PAGE_SIZE: usize = 20;
+#[derive(SimpleObject)]
+pub struct TagConnectionFields {
+    pub total_count: u64,
+}
+#[derive(InputObject)]
+pub struct TagFilter {
+    pub id: Option<Filter<i32>>,
+    pub name: Option<StringFilter>,
+    pub and: Option<Vec<TagFilter>>,
+    pub or: Option<Vec<TagFilter>>,
+}
+impl TagFilter {
+    pub fn condition(self) -> Condition {
+        let mut condition = Condition::all();
+        if let Some(id) = self.id {
+            condition = condition.add(id.condition(tag::Column::Id));
+        }
+        if let Some(name) = self.name {
+            condition = condition.add(name.condition(tag::Column::Name));
+        }
+        for filter in self.and.unwrap_or_default() {
+            condition = condition.add(filter.condition());
+        }
+        if let Some(or) = self.or {
+            let mut any = Condition::any();
+            for filter in or {
+                any = any.add(filter.condition());
+            }
+            condition = condition.add(any);
+        }
+        condition
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Enum)]
+pub enum TagOrderBy {
+    IdAsc,
+    IdDesc,
+    NameAsc,
+    NameDesc,
+}
+impl TagOrderBy {
+    fn column(self) -> (tag::Column, Order) {
+        match self {
+            TagOrderBy::IdAsc => (tag::Column::Id, Order::Asc),
+            TagOrderBy::IdDesc => (tag::Column::Id, Order::Desc),
+            TagOrderBy::NameAsc => (tag::Column::Name, Order::Asc),
+            TagOrderBy::NameDesc => (tag::Column::Name, Order::Desc),
+        }
+    }
+    /// Sorts by the column, ties by `id`, all reversed when paging
+    /// backwards with `last`.
+    pub fn apply(
+        self,
+        select: Select<tag::Entity>,
+        backward: bool,
+    ) -> Select<tag::Entity> {
+        let (column, order) = self.column();
+        let (order, id_order) = match (order, backward) {
+            (order, false) => (order, Order::Asc),
+            (Order::Asc, true) => (Order::Desc, Order::Desc),
+            (_, true) => (Order::Asc, Order::Desc),
+        };
+        select.order_by(column, order).order_by(tag::Column::Id, id_order)
+    }
+    /// Rows after the `anchor` row of a cursor, or before it.
+    ///
+    /// Postgres sorts nulls above every value.
+    pub fn beyond(self, anchor: &tag::Model, before: bool) -> Condition {
+        let (column, order) = self.column();
+        let value = match self {
+            TagOrderBy::IdAsc | TagOrderBy::IdDesc => Some(Value::from(anchor.id)),
+            TagOrderBy::NameAsc | TagOrderBy::NameDesc => {
+                Some(Value::from(anchor.name.clone()))
+            }
+        };
+        let id = tag::Column::Id;
+        let tie = if before { id.lt(anchor.id) } else { id.gt(anchor.id) };
+        let larger = (order == Order::Desc) == before;
+        match value {
+            Some(value) => {
+                let past = if larger {
+                    column.gt(value.clone())
+                } else {
+                    column.lt(value.clone())
+                };
+                let mut condition = Condition::any()
+                    .add(past)
+                    .add(Condition::all().add(column.eq(value)).add(tie));
+                if larger {
+                    condition = condition.add(column.is_null());
+                }
+                condition
+            }
+            None => {
+                let mut condition = Condition::any()
+                    .add(Condition::all().add(column.is_null()).add(tie));
+                if !larger {
+                    condition = condition.add(column.is_not_null());
+                }
+                condition
+            }
+        }
+    }
+}
+#[derive(Default)]
+pub struct TagQuery;
+#[Object]
+impl TagQuery {
+    /// Cursors are the `id` of their row, so pages stay put when rows
+    /// are added or removed. Without `order_by` pages follow `id`.
+    #[allow(clippy::too_many_arguments)]
+    async fn get_tags(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<TagFilter>,
+        order_by: Option<TagOrderBy>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<Connection<String, tag::Model, TagConnectionFields>> {
+        let db = ctx.data::<Database>().unwrap().get_connection();
+        let mut select = tag::Entity::find();
+        if let Some(filter) = filter {
+            select = select.filter(filter.condition());
+        }
+        query(
+                after,
+                before,
+                first,
+                last,
+                |
+                    after: Option<String>,
+                    before: Option<String>,
+                    first: Option<usize>,
+                    last: Option<usize>|
+                async move {
+                    let total_count = select.clone().count(db).await?;
+                    let after = after.map(|after| after.parse::<i32>()).transpose()?;
+                    let before = before.map(|before| before.parse::<i32>()).transpose()?;
+                    let backward = first.is_none() && last.is_some();
+                    let limit = first.or(last).unwrap_or(DEFAULT_PAGE_SIZE);
+                    let fetched = limit as u64 + 1;
+                    let mut tags = match order_by {
+                        None => {
+                            let mut cursor = select.cursor_by(tag::Column::Id);
+                            if let Some(after) = after {
+                                cursor.after(after);
+                            }
+                            if let Some(before) = before {
+                                cursor.before(before);
+                            }
+                            if backward {
+                                cursor.last(fetched);
+                            } else {
+                                cursor.first(fetched);
+                            }
+                            cursor.all(db).await?
+                        }
+                        Some(order_by) => {
+                            for (cursor, before) in [(after, false), (before, true)] {
+                                if let Some(id) = cursor {
+                                    let anchor = tag::Entity::find_by_id(id)
+                                        .one(db)
+                                        .await?
+                                        .ok_or("the row of the cursor is gone")?;
+                                    select = select.filter(order_by.beyond(&anchor, before));
+                                }
+                            }
+                            let mut tags = order_by
+                                .apply(select, backward)
+                                .limit(fetched)
+                                .all(db)
+                                .await?;
+                            if backward {
+                                tags.reverse();
+                            }
+                            tags
+                        }
+                    };
+                    let has_more = tags.len() > limit;
+                    let (has_previous_page, has_next_page) = if backward {
+                        if has_more {
+                            tags.remove(0);
+                        }
+                        (has_more, before.is_some())
+                    } else {
+                        tags.truncate(limit);
+                        (after.is_some(), has_more)
+                    };
+                    let mut connection = Connection::with_additional_fields(
+                        has_previous_page,
+                        has_next_page,
+                        TagConnectionFields { total_count },
+                    );
+                    connection
+                        .edges
+                        .extend(
+                            tags
+                                .into_iter()
+                                .map(|tag| Edge::new(tag.id.to_string(), tag)),
+                        );
+                    Ok::<_, async_graphql::Error>(connection)
+                },
+            )
+            .await
+    }
+    async fn get_tag_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+    ) -> Result<Option<tag::Model>> {
+        let db = ctx.data::<Database>().unwrap();
+        Ok(
+            tag::Entity::find_by_id(id)
+                .one(db.get_connection())
+                .await
+                .map_err(|e| e.to_string())?,
+        )
+    }
+}
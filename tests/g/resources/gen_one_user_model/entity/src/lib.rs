@@ -1,2 +1,4 @@
 pub use async_graphql;
+#[cfg(any(test, feature = "factories"))]
+pub mod factories;
 pub mod user;
@@ -1,5 +1,6 @@
 use async_graphql::*;
-use sea_orm::{entity::prelude::*, DeleteMany};
+use chrono::Utc;
+use sea_orm::{entity::prelude::*, DeleteMany, Set};
 use serde::{Deserialize, Serialize};
 #[derive(
     Clone,
@@ -10,8 +11,7 @@ use serde::{Deserialize, Serialize};
     Deserialize,
     SimpleObject
 )]
-#[sea_orm(table_name = user)]
-#[graphql(concrete(name = "#cap_model", params()))]
+#[sea_orm(table_name = "user")]
 pub struct Model {
     #[sea_orm(primary_key)]
     #[serde(skip_deserializing)]
@@ -21,14 +21,18 @@ pub struct Model {
     #[sea_orm(indexed)]
     pub updated_at: DateTime,
 }
-#[derive(Copy, Clone, Debug, EnumIter)]
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {}
-impl RelationTrait for Relation {
-    fn def(&self) -> RelationDef {
-        panic!("No RelationDef")
+impl ActiveModelBehavior for ActiveModel {
+    fn before_save(mut self, insert: bool) -> Result<Self, DbErr> {
+        let now = Utc::now().naive_utc();
+        if insert && self.created_at.is_not_set() {
+            self.created_at = Set(now);
+        }
+        self.updated_at = Set(now);
+        Ok(self)
     }
 }
-impl ActiveModelBehavior for ActiveModel {}
 impl Entity {
     pub fn find_by_id(id: i32) -> Select<Entity> {
         Self::find().filter(Column::Id.eq(id))
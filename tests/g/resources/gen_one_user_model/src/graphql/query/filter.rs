@@ -0,0 +1,113 @@
+use async_graphql::{InputObject, InputType};
+use entity::async_graphql;
+use sea_orm::prelude::{Date, DateTime, DateTimeWithTimeZone, Uuid};
+use sea_orm::{ColumnTrait, Condition, Value};
+#[derive(InputObject)]
+#[graphql(concrete(name = "IntFilter", params(i32)))]
+#[graphql(concrete(name = "BigIntFilter", params(i64)))]
+#[graphql(concrete(name = "Float32Filter", params(f32)))]
+#[graphql(concrete(name = "FloatFilter", params(f64)))]
+#[graphql(concrete(name = "BooleanFilter", params(bool)))]
+#[graphql(concrete(name = "DateFilter", params(Date)))]
+#[graphql(concrete(name = "DateTimeFilter", params(DateTime)))]
+#[graphql(concrete(name = "DateTimeTzFilter", params(DateTimeWithTimeZone)))]
+pub struct Filter<T: InputType> {
+    pub eq: Option<T>,
+    pub ne: Option<T>,
+    #[graphql(name = "in")]
+    pub is_in: Option<Vec<T>>,
+    pub gt: Option<T>,
+    pub lt: Option<T>,
+    pub is_null: Option<bool>,
+}
+impl<T: InputType + Into<Value>> Filter<T> {
+    pub fn condition(self, column: impl ColumnTrait) -> Condition {
+        let mut condition = Condition::all();
+        if let Some(eq) = self.eq {
+            condition = condition.add(column.eq(eq));
+        }
+        if let Some(ne) = self.ne {
+            condition = condition.add(column.ne(ne));
+        }
+        if let Some(is_in) = self.is_in {
+            condition = condition.add(column.is_in(is_in));
+        }
+        if let Some(gt) = self.gt {
+            condition = condition.add(column.gt(gt));
+        }
+        if let Some(lt) = self.lt {
+            condition = condition.add(column.lt(lt));
+        }
+        if let Some(is_null) = self.is_null {
+            condition = condition
+                .add(if is_null { column.is_null() } else { column.is_not_null() });
+        }
+        condition
+    }
+}
+#[derive(InputObject)]
+pub struct StringFilter {
+    pub eq: Option<String>,
+    pub ne: Option<String>,
+    #[graphql(name = "in")]
+    pub is_in: Option<Vec<String>>,
+    pub contains: Option<String>,
+    pub gt: Option<String>,
+    pub lt: Option<String>,
+    pub is_null: Option<bool>,
+}
+impl StringFilter {
+    pub fn condition(self, column: impl ColumnTrait) -> Condition {
+        let mut condition = Condition::all();
+        if let Some(eq) = self.eq {
+            condition = condition.add(column.eq(eq));
+        }
+        if let Some(ne) = self.ne {
+            condition = condition.add(column.ne(ne));
+        }
+        if let Some(is_in) = self.is_in {
+            condition = condition.add(column.is_in(is_in));
+        }
+        if let Some(contains) = self.contains {
+            condition = condition.add(column.contains(&contains));
+        }
+        if let Some(gt) = self.gt {
+            condition = condition.add(column.gt(gt));
+        }
+        if let Some(lt) = self.lt {
+            condition = condition.add(column.lt(lt));
+        }
+        if let Some(is_null) = self.is_null {
+            condition = condition
+                .add(if is_null { column.is_null() } else { column.is_not_null() });
+        }
+        condition
+    }
+}
+#[derive(InputObject)]
+pub struct UuidFilter {
+    pub eq: Option<Uuid>,
+    pub ne: Option<Uuid>,
+    #[graphql(name = "in")]
+    pub is_in: Option<Vec<Uuid>>,
+    pub is_null: Option<bool>,
+}
+impl UuidFilter {
+    pub fn condition(self, column: impl ColumnTrait) -> Condition {
+        let mut condition = Condition::all();
+        if let Some(eq) = self.eq {
+            condition = condition.add(column.eq(eq));
+        }
+        if let Some(ne) = self.ne {
+            condition = condition.add(column.ne(ne));
+        }
+        if let Some(is_in) = self.is_in {
+            condition = condition.add(column.is_in(is_in));
+        }
+        if let Some(is_null) = self.is_null {
+            condition = condition
+                .add(if is_null { column.is_null() } else { column.is_not_null() });
+        }
+        condition
+    }
+}
@@ -1,4 +1,5 @@
 use entity::async_graphql;
+pub mod filter;
 pub mod user;
 pub use user::UserQuery;
 #[derive(async_graphql::MergedObject, Default)]
@@ -1,19 +1,234 @@
-use async_graphql::{Context, Object, Result};
+use async_graphql::connection::{query, Connection, Edge};
+use async_graphql::{Context, Enum, InputObject, Object, Result, SimpleObject};
 use entity::{async_graphql, user};
-use sea_orm::EntityTrait;
+use sea_orm::prelude::DateTime;
+use sea_orm::{
+    ColumnTrait, Condition, EntityTrait, Order, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Select, Value,
+};
 use crate::db::Database;
+use crate::graphql::query::filter::Filter;
+/// Page size when neither `first` nor `last` is given.
+const DEFAULT_PAGE_SIZE: usize = 20;
+#[derive(SimpleObject)]
+pub struct UserConnectionFields {
+    pub total_count: u64,
+}
+#[derive(InputObject)]
+pub struct UserFilter {
+    pub id: Option<Filter<i32>>,
+    pub created_at: Option<Filter<DateTime>>,
+    pub updated_at: Option<Filter<DateTime>>,
+    pub and: Option<Vec<UserFilter>>,
+    pub or: Option<Vec<UserFilter>>,
+}
+impl UserFilter {
+    pub fn condition(self) -> Condition {
+        let mut condition = Condition::all();
+        if let Some(id) = self.id {
+            condition = condition.add(id.condition(user::Column::Id));
+        }
+        if let Some(created_at) = self.created_at {
+            condition = condition.add(created_at.condition(user::Column::CreatedAt));
+        }
+        if let Some(updated_at) = self.updated_at {
+            condition = condition.add(updated_at.condition(user::Column::UpdatedAt));
+        }
+        for filter in self.and.unwrap_or_default() {
+            condition = condition.add(filter.condition());
+        }
+        if let Some(or) = self.or {
+            let mut any = Condition::any();
+            for filter in or {
+                any = any.add(filter.condition());
+            }
+            condition = condition.add(any);
+        }
+        condition
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Enum)]
+pub enum UserOrderBy {
+    IdAsc,
+    IdDesc,
+    CreatedAtAsc,
+    CreatedAtDesc,
+    UpdatedAtAsc,
+    UpdatedAtDesc,
+}
+impl UserOrderBy {
+    fn column(self) -> (user::Column, Order) {
+        match self {
+            UserOrderBy::IdAsc => (user::Column::Id, Order::Asc),
+            UserOrderBy::IdDesc => (user::Column::Id, Order::Desc),
+            UserOrderBy::CreatedAtAsc => (user::Column::CreatedAt, Order::Asc),
+            UserOrderBy::CreatedAtDesc => (user::Column::CreatedAt, Order::Desc),
+            UserOrderBy::UpdatedAtAsc => (user::Column::UpdatedAt, Order::Asc),
+            UserOrderBy::UpdatedAtDesc => (user::Column::UpdatedAt, Order::Desc),
+        }
+    }
+    /// Sorts by the column, ties by `id`, all reversed when paging
+    /// backwards with `last`.
+    pub fn apply(
+        self,
+        select: Select<user::Entity>,
+        backward: bool,
+    ) -> Select<user::Entity> {
+        let (column, order) = self.column();
+        let (order, id_order) = match (order, backward) {
+            (order, false) => (order, Order::Asc),
+            (Order::Asc, true) => (Order::Desc, Order::Desc),
+            (_, true) => (Order::Asc, Order::Desc),
+        };
+        select.order_by(column, order).order_by(user::Column::Id, id_order)
+    }
+    /// Rows after the `anchor` row of a cursor, or before it.
+    ///
+    /// Postgres sorts nulls above every value.
+    pub fn beyond(self, anchor: &user::Model, before: bool) -> Condition {
+        let (column, order) = self.column();
+        let value = match self {
+            UserOrderBy::IdAsc | UserOrderBy::IdDesc => Some(Value::from(anchor.id)),
+            UserOrderBy::CreatedAtAsc | UserOrderBy::CreatedAtDesc => {
+                Some(Value::from(anchor.created_at))
+            }
+            UserOrderBy::UpdatedAtAsc | UserOrderBy::UpdatedAtDesc => {
+                Some(Value::from(anchor.updated_at))
+            }
+        };
+        let id = user::Column::Id;
+        let tie = if before { id.lt(anchor.id) } else { id.gt(anchor.id) };
+        let larger = (order == Order::Desc) == before;
+        match value {
+            Some(value) => {
+                let past = if larger {
+                    column.gt(value.clone())
+                } else {
+                    column.lt(value.clone())
+                };
+                let mut condition = Condition::any()
+                    .add(past)
+                    .add(Condition::all().add(column.eq(value)).add(tie));
+                if larger {
+                    condition = condition.add(column.is_null());
+                }
+                condition
+            }
+            None => {
+                let mut condition = Condition::any()
+                    .add(Condition::all().add(column.is_null()).add(tie));
+                if !larger {
+                    condition = condition.add(column.is_not_null());
+                }
+                condition
+            }
+        }
+    }
+}
 #[derive(Default)]
 pub struct UserQuery;
 #[Object]
-impl model_query {
-    async fn get_user(&self, ctx: &Context<'_>) -> Result<Vec<user::Model>> {
-        let db = ctx.data::<Database>().unwrap();
-        Ok(
-            user::Entity::find()
-                .all(db.get_connection())
-                .await
-                .map_err(|e| e.to_string())?,
-        )
+impl UserQuery {
+    /// Cursors are the `id` of their row, so pages stay put when rows
+    /// are added or removed. Without `order_by` pages follow `id`.
+    #[allow(clippy::too_many_arguments)]
+    async fn get_users(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<UserFilter>,
+        order_by: Option<UserOrderBy>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<Connection<String, user::Model, UserConnectionFields>> {
+        let db = ctx.data::<Database>().unwrap().get_connection();
+        let mut select = user::Entity::find();
+        if let Some(filter) = filter {
+            select = select.filter(filter.condition());
+        }
+        query(
+                after,
+                before,
+                first,
+                last,
+                |
+                    after: Option<String>,
+                    before: Option<String>,
+                    first: Option<usize>,
+                    last: Option<usize>|
+                async move {
+                    let total_count = select.clone().count(db).await?;
+                    let after = after.map(|after| after.parse::<i32>()).transpose()?;
+                    let before = before.map(|before| before.parse::<i32>()).transpose()?;
+                    let backward = first.is_none() && last.is_some();
+                    let limit = first.or(last).unwrap_or(DEFAULT_PAGE_SIZE);
+                    let fetched = limit as u64 + 1;
+                    let mut users = match order_by {
+                        None => {
+                            let mut cursor = select.cursor_by(user::Column::Id);
+                            if let Some(after) = after {
+                                cursor.after(after);
+                            }
+                            if let Some(before) = before {
+                                cursor.before(before);
+                            }
+                            if backward {
+                                cursor.last(fetched);
+                            } else {
+                                cursor.first(fetched);
+                            }
+                            cursor.all(db).await?
+                        }
+                        Some(order_by) => {
+                            for (cursor, before) in [(after, false), (before, true)] {
+                                if let Some(id) = cursor {
+                                    let anchor = user::Entity::find_by_id(id)
+                                        .one(db)
+                                        .await?
+                                        .ok_or("the row of the cursor is gone")?;
+                                    select = select.filter(order_by.beyond(&anchor, before));
+                                }
+                            }
+                            let mut users = order_by
+                                .apply(select, backward)
+                                .limit(fetched)
+                                .all(db)
+                                .await?;
+                            if backward {
+                                users.reverse();
+                            }
+                            users
+                        }
+                    };
+                    let has_more = users.len() > limit;
+                    let (has_previous_page, has_next_page) = if backward {
+                        if has_more {
+                            users.remove(0);
+                        }
+                        (has_more, before.is_some())
+                    } else {
+                        users.truncate(limit);
+                        (after.is_some(), has_more)
+                    };
+                    let mut connection = Connection::with_additional_fields(
+                        has_previous_page,
+                        has_next_page,
+                        UserConnectionFields {
+                            total_count,
+                        },
+                    );
+                    connection
+                        .edges
+                        .extend(
+                            users
+                                .into_iter()
+                                .map(|user| Edge::new(user.id.to_string(), user)),
+                        );
+                    Ok::<_, async_graphql::Error>(connection)
+                },
+            )
+            .await
     }
     async fn get_user_by_id(
         &self,
@@ -0,0 +1,5 @@
+pub use async_graphql;
+#[cfg(any(test, feature = "factories"))]
+pub mod factories;
+pub mod post;
+pub mod user;
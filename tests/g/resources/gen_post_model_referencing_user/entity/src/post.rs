@@ -0,0 +1,93 @@
+use async_graphql::*;
+use chrono::Utc;
+use sea_orm::{entity::prelude::*, DeleteMany, Set};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    DeriveEntityModel,
+    Serialize,
+    Deserialize,
+    SimpleObject
+)]
+#[sea_orm(table_name = "post")]
+#[graphql(complex)]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    #[serde(skip_deserializing)]
+    pub id: i32,
+    pub title: String,
+    pub user_id: i32,
+    #[sea_orm(indexed)]
+    pub created_at: DateTime,
+    #[sea_orm(indexed)]
+    pub updated_at: DateTime,
+}
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+impl ActiveModelBehavior for ActiveModel {
+    fn before_save(mut self, insert: bool) -> Result<Self, DbErr> {
+        let now = Utc::now().naive_utc();
+        if insert && self.created_at.is_not_set() {
+            self.created_at = Set(now);
+        }
+        self.updated_at = Set(now);
+        Ok(self)
+    }
+}
+impl Entity {
+    pub fn find_by_id(id: i32) -> Select<Entity> {
+        Self::find().filter(Column::Id.eq(id))
+    }
+    pub fn delete_by_id(id: i32) -> DeleteMany<Entity> {
+        Self::delete_many().filter(Column::Id.eq(id))
+    }
+}
+/// Batches lookups of the rows belonging to `user`s.
+pub struct UserIdLoader {
+    db: DatabaseConnection,
+}
+impl UserIdLoader {
+    pub fn new(db: DatabaseConnection) -> Self {
+        UserIdLoader { db }
+    }
+}
+#[async_trait::async_trait]
+impl dataloader::Loader<i32> for UserIdLoader {
+    type Value = Vec<Model>;
+    type Error = Arc<DbErr>;
+    async fn load(&self, keys: &[i32]) -> Result<HashMap<i32, Vec<Model>>, Self::Error> {
+        let models = Entity::find()
+            .filter(Column::UserId.is_in(keys.iter().cloned()))
+            .all(&self.db)
+            .await?;
+        let mut grouped = HashMap::<i32, Vec<Model>>::new();
+        for model in models {
+            grouped.entry(model.user_id).or_default().push(model);
+        }
+        Ok(grouped)
+    }
+}
+#[ComplexObject]
+impl Model {
+    async fn user(&self, ctx: &Context<'_>) -> Result<Option<super::user::Model>> {
+        let loader = ctx.data::<dataloader::DataLoader<super::user::IdLoader>>()?;
+        Ok(loader.load_one(self.user_id).await?)
+    }
+}
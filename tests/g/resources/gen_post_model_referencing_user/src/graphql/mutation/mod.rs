@@ -0,0 +1,8 @@
+use entity::async_graphql;
+pub mod common;
+pub mod post;
+pub mod user;
+pub use post::PostMutation;
+pub use user::UserMutation;
+#[derive(async_graphql::MergedObject, Default)]
+pub struct Mutation(PostMutation, UserMutation);
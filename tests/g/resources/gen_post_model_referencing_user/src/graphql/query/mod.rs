@@ -0,0 +1,8 @@
+use entity::async_graphql;
+pub mod filter;
+pub mod post;
+pub mod user;
+pub use post::PostQuery;
+pub use user::UserQuery;
+#[derive(async_graphql::MergedObject, Default)]
+pub struct Query(PostQuery, UserQuery);
@@ -0,0 +1,134 @@
+use entity::async_graphql::{value, EmptySubscription, ErrorExtensionValues, Schema};
+use entity::post;
+use my_app::db::Database;
+use my_app::graphql::mutation::Mutation;
+use my_app::graphql::query::Query;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase};
+fn schema(db: DatabaseConnection) -> Schema<Query, Mutation, EmptySubscription> {
+    Schema::build(Query::default(), Mutation::default(), EmptySubscription)
+        .data(Database::from_connection(db))
+        .finish()
+}
+/// A second handle on the mocked connection, which keeps the
+/// transaction log readable once the schema owns the first.
+fn log_handle(db: &DatabaseConnection) -> DatabaseConnection {
+    match db {
+        DatabaseConnection::MockDatabaseConnection(mock) => {
+            DatabaseConnection::MockDatabaseConnection(mock.clone())
+        }
+        _ => unreachable!(),
+    }
+}
+/// The row returned by every mocked query.
+fn sample() -> post::Model {
+    post::Model {
+        id: "67e55044-10b1-426f-9247-bb680e5fe0c8".parse().unwrap(),
+        title: "titlexxx".to_owned(),
+        status: post::Status::Draft,
+        rating: Some(1),
+        published_on: Some("2022-07-16".parse().unwrap()),
+        created_at: "2022-07-16T23:39:33".parse().unwrap(),
+        updated_at: "2022-07-16T23:39:33".parse().unwrap(),
+        deleted_at: None,
+    }
+}
+#[tokio::test]
+async fn create_post() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![sample()]])
+        .into_connection();
+    let log = log_handle(&db);
+    let res = schema(db)
+        .execute(
+            r#"mutation { createPost(input: { title: "titlexxx", status: DRAFT, rating: 1, publishedOn: "2022-07-16" }) { id title status rating publishedOn } }"#,
+        )
+        .await;
+    assert!(res.errors.is_empty(), "{:?}", res.errors);
+    assert_eq!(
+        res.data, value!({ "createPost" : { "id" :
+        "67e55044-10b1-426f-9247-bb680e5fe0c8", "title" : "titlexxx", "status" : "DRAFT",
+        "rating" : 1, "publishedOn" : "2022-07-16" } })
+    );
+    let log = format!("{:?}", log.into_transaction_log());
+    for value in [
+        "INSERT INTO \"post\"",
+        "\"titlexxx\"",
+        "\"draft\"",
+        "Int(Some(1))",
+        "2022-07-16",
+    ] {
+        assert!(log.contains(value), "{} not in {}", value, log);
+    }
+}
+#[tokio::test]
+async fn get_post_by_id() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![sample()]])
+        .into_connection();
+    let res = schema(db)
+        .execute(
+            r#"query { getPostById(id: "67e55044-10b1-426f-9247-bb680e5fe0c8") { id title status rating publishedOn } }"#,
+        )
+        .await;
+    assert!(res.errors.is_empty(), "{:?}", res.errors);
+    assert_eq!(
+        res.data, value!({ "getPostById" : { "id" :
+        "67e55044-10b1-426f-9247-bb680e5fe0c8", "title" : "titlexxx", "status" : "DRAFT",
+        "rating" : 1, "publishedOn" : "2022-07-16" } })
+    );
+}
+#[tokio::test]
+async fn update_post() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![sample()], vec![sample()]])
+        .into_connection();
+    let log = log_handle(&db);
+    let res = schema(db)
+        .execute(
+            r#"mutation { updatePost(id: "67e55044-10b1-426f-9247-bb680e5fe0c8", input: { title: "xxxeltit" }) { id } }"#,
+        )
+        .await;
+    assert!(res.errors.is_empty(), "{:?}", res.errors);
+    assert_eq!(
+        res.data, value!({ "updatePost" : { "id" : "67e55044-10b1-426f-9247-bb680e5fe0c8"
+        } })
+    );
+    let log = format!("{:?}", log.into_transaction_log());
+    for value in ["UPDATE \"post\"", "\"xxxeltit\""] {
+        assert!(log.contains(value), "{} not in {}", value, log);
+    }
+}
+#[tokio::test]
+async fn delete_post() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![vec![sample()], vec![sample()]])
+        .into_connection();
+    let res = schema(db)
+        .execute(
+            r#"mutation { deletePost(id: "67e55044-10b1-426f-9247-bb680e5fe0c8") { success rowsAffected } }"#,
+        )
+        .await;
+    assert!(res.errors.is_empty(), "{:?}", res.errors);
+    assert_eq!(
+        res.data, value!({ "deletePost" : { "success" : true, "rowsAffected" : 1 } })
+    );
+}
+#[tokio::test]
+async fn delete_missing_post() {
+    let no_rows: Vec<post::Model> = vec![];
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![no_rows])
+        .into_connection();
+    let res = schema(db)
+        .execute(
+            r#"mutation { deletePost(id: "67e55044-10b1-426f-9247-bb680e5fe0c8") { success rowsAffected } }"#,
+        )
+        .await;
+    let mut not_found = ErrorExtensionValues::default();
+    not_found.set("code", "NOT_FOUND");
+    assert_eq!(res.errors.len(), 1, "{:?}", res.errors);
+    assert_eq!(
+        res.errors[0].message, "post 67e55044-10b1-426f-9247-bb680e5fe0c8 not found"
+    );
+    assert_eq!(res.errors[0].extensions, Some(not_found));
+}
@@ -0,0 +1,95 @@
+use async_graphql::{Context, Object, Result, Error, ErrorExtensions};
+use entity::async_graphql::{self, InputObject};
+use entity::post;
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, Set};
+use crate::graphql::mutation::common::*;
+use crate::db::Database;
+#[derive(InputObject)]
+pub struct CreatePostInput {
+    pub id: i32,
+    pub title: String,
+}
+#[derive(InputObject)]
+pub struct UpdatePostInput {
+    pub title: Option<String>,
+}
+fn not_found(id: i32) -> Error {
+    Error::new(format!("post {} not found", id))
+        .extend_with(|_, e| e.set("code", "NOT_FOUND"))
+}
+#[derive(Default)]
+pub struct PostMutation;
+#[Object]
+impl PostMutation {
+    pub async fn create_post(
+        &self,
+        ctx: &Context<'_>,
+        input: CreatePostInput,
+    ) -> Result<post::Model> {
+        let db = ctx.data::<Database>().unwrap();
+        let post = post::ActiveModel {
+            id: Set(input.id),
+            title: Set(input.title),
+            ..Default::default()
+        };
+        Ok(post.insert(db.get_connection()).await?)
+    }
+    pub async fn update_post(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+        input: UpdatePostInput,
+    ) -> Result<post::Model, Error> {
+        let db = ctx.data::<Database>().unwrap();
+        let post: post::Model = post::Entity::find_by_id(id)
+            .one(db.get_connection())
+            .await?
+            .ok_or_else(|| not_found(id))?;
+        let mut post: post::ActiveModel = post.into();
+        if let Some(title) = input.title {
+            post.title = Set(title);
+        }
+        let post: post::Model = post.update(db.get_connection()).await?;
+        Ok(post)
+    }
+    pub async fn delete_post(&self, ctx: &Context<'_>, id: i32) -> Result<DeleteResult> {
+        let db = ctx.data::<Database>().unwrap();
+        let post: post::Model = post::Entity::find_by_id(id)
+            .one(db.get_connection())
+            .await?
+            .ok_or_else(|| not_found(id))?;
+        let mut post: post::ActiveModel = post.into();
+        post.deleted_at = Set(Some(Utc::now().naive_utc()));
+        post.update(db.get_connection()).await?;
+        Ok(DeleteResult {
+            success: true,
+            rows_affected: 1,
+        })
+    }
+    pub async fn restore_post(&self, ctx: &Context<'_>, id: i32) -> Result<post::Model> {
+        let db = ctx.data::<Database>().unwrap();
+        let post: post::Model = post::Entity::find_deleted_by_id(id)
+            .one(db.get_connection())
+            .await?
+            .ok_or_else(|| not_found(id))?;
+        let mut post: post::ActiveModel = post.into();
+        post.deleted_at = Set(None);
+        Ok(post.update(db.get_connection()).await?)
+    }
+    pub async fn hard_delete_post(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+    ) -> Result<DeleteResult> {
+        let db = ctx.data::<Database>().unwrap();
+        let res = post::Entity::delete_by_id(id).exec(db.get_connection()).await?;
+        if res.rows_affected == 0 {
+            return Err(not_found(id));
+        }
+        Ok(DeleteResult {
+            success: true,
+            rows_affected: res.rows_affected,
+        })
+    }
+}
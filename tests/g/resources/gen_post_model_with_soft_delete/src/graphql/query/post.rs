@@ -0,0 +1,257 @@
+use async_graphql::connection::{query, Connection, Edge};
+use async_graphql::{Context, Enum, InputObject, Object, Result, SimpleObject};
+use entity::{async_graphql, post};
+use sea_orm::prelude::DateTime;
+use sea_orm::{
+    ColumnTrait, Condition, EntityTrait, Order, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Select, Value,
+};
+use crate::db::Database;
+use crate::graphql::query::filter::{Filter, StringFilter};
+/// Page size when neither `first` nor `last` is given.
+const DEFAULT_PAGE_SIZE: usize = 20;
+#[derive(SimpleObject)]
+pub struct PostConnectionFields {
+    pub total_count: u64,
+}
+#[derive(InputObject)]
+pub struct PostFilter {
+    pub id: Option<Filter<i32>>,
+    pub title: Option<StringFilter>,
+    pub created_at: Option<Filter<DateTime>>,
+    pub updated_at: Option<Filter<DateTime>>,
+    pub and: Option<Vec<PostFilter>>,
+    pub or: Option<Vec<PostFilter>>,
+}
+impl PostFilter {
+    pub fn condition(self) -> Condition {
+        let mut condition = Condition::all();
+        if let Some(id) = self.id {
+            condition = condition.add(id.condition(post::Column::Id));
+        }
+        if let Some(title) = self.title {
+            condition = condition.add(title.condition(post::Column::Title));
+        }
+        if let Some(created_at) = self.created_at {
+            condition = condition.add(created_at.condition(post::Column::CreatedAt));
+        }
+        if let Some(updated_at) = self.updated_at {
+            condition = condition.add(updated_at.condition(post::Column::UpdatedAt));
+        }
+        for filter in self.and.unwrap_or_default() {
+            condition = condition.add(filter.condition());
+        }
+        if let Some(or) = self.or {
+            let mut any = Condition::any();
+            for filter in or {
+                any = any.add(filter.condition());
+            }
+            condition = condition.add(any);
+        }
+        condition
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Enum)]
+pub enum PostOrderBy {
+    IdAsc,
+    IdDesc,
+    TitleAsc,
+    TitleDesc,
+    CreatedAtAsc,
+    CreatedAtDesc,
+    UpdatedAtAsc,
+    UpdatedAtDesc,
+}
+impl PostOrderBy {
+    fn column(self) -> (post::Column, Order) {
+        match self {
+            PostOrderBy::IdAsc => (post::Column::Id, Order::Asc),
+            PostOrderBy::IdDesc => (post::Column::Id, Order::Desc),
+            PostOrderBy::TitleAsc => (post::Column::Title, Order::Asc),
+            PostOrderBy::TitleDesc => (post::Column::Title, Order::Desc),
+            PostOrderBy::CreatedAtAsc => (post::Column::CreatedAt, Order::Asc),
+            PostOrderBy::CreatedAtDesc => (post::Column::CreatedAt, Order::Desc),
+            PostOrderBy::UpdatedAtAsc => (post::Column::UpdatedAt, Order::Asc),
+            PostOrderBy::UpdatedAtDesc => (post::Column::UpdatedAt, Order::Desc),
+        }
+    }
+    /// Sorts by the column, ties by `id`, all reversed when paging
+    /// backwards with `last`.
+    pub fn apply(
+        self,
+        select: Select<post::Entity>,
+        backward: bool,
+    ) -> Select<post::Entity> {
+        let (column, order) = self.column();
+        let (order, id_order) = match (order, backward) {
+            (order, false) => (order, Order::Asc),
+            (Order::Asc, true) => (Order::Desc, Order::Desc),
+            (_, true) => (Order::Asc, Order::Desc),
+        };
+        select.order_by(column, order).order_by(post::Column::Id, id_order)
+    }
+    /// Rows after the `anchor` row of a cursor, or before it.
+    ///
+    /// Postgres sorts nulls above every value.
+    pub fn beyond(self, anchor: &post::Model, before: bool) -> Condition {
+        let (column, order) = self.column();
+        let value = match self {
+            PostOrderBy::IdAsc | PostOrderBy::IdDesc => Some(Value::from(anchor.id)),
+            PostOrderBy::TitleAsc | PostOrderBy::TitleDesc => {
+                Some(Value::from(anchor.title.clone()))
+            }
+            PostOrderBy::CreatedAtAsc | PostOrderBy::CreatedAtDesc => {
+                Some(Value::from(anchor.created_at))
+            }
+            PostOrderBy::UpdatedAtAsc | PostOrderBy::UpdatedAtDesc => {
+                Some(Value::from(anchor.updated_at))
+            }
+        };
+        let id = post::Column::Id;
+        let tie = if before { id.lt(anchor.id) } else { id.gt(anchor.id) };
+        let larger = (order == Order::Desc) == before;
+        match value {
+            Some(value) => {
+                let past = if larger {
+                    column.gt(value.clone())
+                } else {
+                    column.lt(value.clone())
+                };
+                let mut condition = Condition::any()
+                    .add(past)
+                    .add(Condition::all().add(column.eq(value)).add(tie));
+                if larger {
+                    condition = condition.add(column.is_null());
+                }
+                condition
+            }
+            None => {
+                let mut condition = Condition::any()
+                    .add(Condition::all().add(column.is_null()).add(tie));
+                if !larger {
+                    condition = condition.add(column.is_not_null());
+                }
+                condition
+            }
+        }
+    }
+}
+#[derive(Default)]
+pub struct PostQuery;
+#[Object]
+impl PostQuery {
+    /// Cursors are the `id` of their row, so pages stay put when rows
+    /// are added or removed. Without `order_by` pages follow `id`.
+    #[allow(clippy::too_many_arguments)]
+    async fn get_posts(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<PostFilter>,
+        order_by: Option<PostOrderBy>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<Connection<String, post::Model, PostConnectionFields>> {
+        let db = ctx.data::<Database>().unwrap().get_connection();
+        let mut select = post::Entity::find().filter(post::Column::DeletedAt.is_null());
+        if let Some(filter) = filter {
+            select = select.filter(filter.condition());
+        }
+        query(
+                after,
+                before,
+                first,
+                last,
+                |
+                    after: Option<String>,
+                    before: Option<String>,
+                    first: Option<usize>,
+                    last: Option<usize>|
+                async move {
+                    let total_count = select.clone().count(db).await?;
+                    let after = after.map(|after| after.parse::<i32>()).transpose()?;
+                    let before = before.map(|before| before.parse::<i32>()).transpose()?;
+                    let backward = first.is_none() && last.is_some();
+                    let limit = first.or(last).unwrap_or(DEFAULT_PAGE_SIZE);
+                    let fetched = limit as u64 + 1;
+                    let mut posts = match order_by {
+                        None => {
+                            let mut cursor = select.cursor_by(post::Column::Id);
+                            if let Some(after) = after {
+                                cursor.after(after);
+                            }
+                            if let Some(before) = before {
+                                cursor.before(before);
+                            }
+                            if backward {
+                                cursor.last(fetched);
+                            } else {
+                                cursor.first(fetched);
+                            }
+                            cursor.all(db).await?
+                        }
+                        Some(order_by) => {
+                            for (cursor, before) in [(after, false), (before, true)] {
+                                if let Some(id) = cursor {
+                                    let anchor = post::Entity::find_by_id(id)
+                                        .one(db)
+                                        .await?
+                                        .ok_or("the row of the cursor is gone")?;
+                                    select = select.filter(order_by.beyond(&anchor, before));
+                                }
+                            }
+                            let mut posts = order_by
+                                .apply(select, backward)
+                                .limit(fetched)
+                                .all(db)
+                                .await?;
+                            if backward {
+                                posts.reverse();
+                            }
+                            posts
+                        }
+                    };
+                    let has_more = posts.len() > limit;
+                    let (has_previous_page, has_next_page) = if backward {
+                        if has_more {
+                            posts.remove(0);
+                        }
+                        (has_more, before.is_some())
+                    } else {
+                        posts.truncate(limit);
+                        (after.is_some(), has_more)
+                    };
+                    let mut connection = Connection::with_additional_fields(
+                        has_previous_page,
+                        has_next_page,
+                        PostConnectionFields {
+                            total_count,
+                        },
+                    );
+                    connection
+                        .edges
+                        .extend(
+                            posts
+                                .into_iter()
+                                .map(|post| Edge::new(post.id.to_string(), post)),
+                        );
+                    Ok::<_, async_graphql::Error>(connection)
+                },
+            )
+            .await
+    }
+    async fn get_post_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+    ) -> Result<Option<post::Model>> {
+        let db = ctx.data::<Database>().unwrap();
+        Ok(
+            post::Entity::find_by_id(id)
+                .one(db.get_connection())
+                .await
+                .map_err(|e| e.to_string())?,
+        )
+    }
+}
@@ -0,0 +1,4 @@
+pub mod post;
+pub mod user;
+pub use post::PostFactory;
+pub use user::UserFactory;
@@ -0,0 +1,77 @@
+use crate::post;
+use fake::Fake;
+use fake::faker::boolean::en::Boolean;
+use fake::faker::lorem::en::{Paragraph, Words};
+use fake::faker::name::en::FirstName;
+use sea_orm::prelude::*;
+use sea_orm::Set;
+use std::sync::atomic::{AtomicU32, Ordering};
+/// Numbers the rows built by the factory, keeping their values apart.
+static SEQUENCE: AtomicU32 = AtomicU32::new(1);
+/// Builds `post` rows with every column filled.
+///
+/// Override columns with their setters, e.g.
+/// `PostFactory::new().title(..).insert(&db)`.
+pub struct PostFactory {
+    model: post::ActiveModel,
+}
+impl PostFactory {
+    pub fn new() -> Self {
+        let n = SEQUENCE.fetch_add(1, Ordering::Relaxed);
+        let contact_local: String = FirstName().fake::<String>().to_lowercase()
+            + &n.to_string();
+        PostFactory {
+            model: post::ActiveModel {
+                title: Set(Words(1..4).fake::<Vec<String>>().join(" ")),
+                body: Set(Paragraph(1..3).fake()),
+                score: Set((0.0f32..1000.0).fake()),
+                published: Set(Some(Boolean(50).fake())),
+                contact: Set(format!("{:x<4.28}@example.com", contact_local)),
+                user_id: Set(1),
+                ..Default::default()
+            },
+        }
+    }
+    /// Overrides the generated `title`.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.model.title = Set(title.into());
+        self
+    }
+    /// Overrides the generated `body`.
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.model.body = Set(body.into());
+        self
+    }
+    /// Overrides the generated `score`.
+    pub fn score(mut self, score: impl Into<f32>) -> Self {
+        self.model.score = Set(score.into());
+        self
+    }
+    /// Overrides the generated `published`.
+    pub fn published(mut self, published: impl Into<Option<bool>>) -> Self {
+        self.model.published = Set(published.into());
+        self
+    }
+    /// Overrides the generated `contact`.
+    pub fn contact(mut self, contact: impl Into<String>) -> Self {
+        self.model.contact = Set(contact.into());
+        self
+    }
+    /// The `user` the row belongs to.
+    pub fn user_id(mut self, user_id: impl Into<i32>) -> Self {
+        self.model.user_id = Set(user_id.into());
+        self
+    }
+    /// The row as it would be inserted.
+    pub fn build(self) -> post::ActiveModel {
+        self.model
+    }
+    pub async fn insert(self, db: &DatabaseConnection) -> Result<post::Model, DbErr> {
+        self.model.insert(db).await
+    }
+}
+impl Default for PostFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,70 @@
+use crate::user::{self, Role};
+use fake::Fake;
+use fake::faker::name::en::{FirstName, Name};
+use sea_orm::prelude::*;
+use sea_orm::Set;
+use std::sync::atomic::{AtomicU32, Ordering};
+/// Numbers the rows built by the factory, keeping their values apart.
+static SEQUENCE: AtomicU32 = AtomicU32::new(1);
+/// Builds `user` rows with every column filled.
+///
+/// Override columns with their setters, e.g.
+/// `UserFactory::new().email(..).insert(&db)`.
+pub struct UserFactory {
+    model: user::ActiveModel,
+}
+impl UserFactory {
+    pub fn new() -> Self {
+        let n = SEQUENCE.fetch_add(1, Ordering::Relaxed);
+        let email_local: String = FirstName().fake::<String>().to_lowercase()
+            + &n.to_string();
+        let name: String = Name().fake();
+        UserFactory {
+            model: user::ActiveModel {
+                email: Set(format!("{}@example.com", email_local)),
+                name: Set(format!("{:x<8.100}", name)),
+                age: Set(Some((18i32..=150i32).fake())),
+                role: Set(user::Role::Admin),
+                born_on: Set(Some("2022-07-16".parse().unwrap())),
+                ..Default::default()
+            },
+        }
+    }
+    /// Overrides the generated `email`.
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.model.email = Set(email.into());
+        self
+    }
+    /// Overrides the generated `name`.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.model.name = Set(name.into());
+        self
+    }
+    /// Overrides the generated `age`.
+    pub fn age(mut self, age: impl Into<Option<i32>>) -> Self {
+        self.model.age = Set(age.into());
+        self
+    }
+    /// Overrides the generated `role`.
+    pub fn role(mut self, role: impl Into<Role>) -> Self {
+        self.model.role = Set(role.into());
+        self
+    }
+    /// Overrides the generated `born_on`.
+    pub fn born_on(mut self, born_on: impl Into<Option<Date>>) -> Self {
+        self.model.born_on = Set(born_on.into());
+        self
+    }
+    /// The row as it would be inserted.
+    pub fn build(self) -> user::ActiveModel {
+        self.model
+    }
+    pub async fn insert(self, db: &DatabaseConnection) -> Result<user::Model, DbErr> {
+        self.model.insert(db).await
+    }
+}
+impl Default for UserFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,26 @@
+use sea_orm::{Database, DatabaseConnection, DbErr};
+mod s20220716_233933_seed_user;
+mod s20220716_233934_seed_post;
+#[async_trait::async_trait]
+pub trait SeederTrait {
+    async fn run(&self, db: &DatabaseConnection) -> Result<(), DbErr>;
+}
+pub struct Seed;
+impl Seed {
+    fn seeders() -> Vec<Box<dyn SeederTrait>> {
+        vec![
+            Box::new(s20220716_233933_seed_user::Seeder),
+            Box::new(s20220716_233934_seed_post::Seeder)
+        ]
+    }
+}
+/// Runs every seeder, oldest first, against `DATABASE_URL`.
+#[tokio::main]
+async fn main() -> Result<(), DbErr> {
+    let url = std::env::var("DATABASE_URL").expect("DATABASE_URL is not set");
+    let db = Database::connect(url).await?;
+    for seeder in Seed::seeders() {
+        seeder.run(&db).await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use crate::SeederTrait;
+use entity::user;
+use sea_orm::prelude::Uuid;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter,
+    Set,
+};
+pub struct Seeder;
+#[async_trait::async_trait]
+impl SeederTrait for Seeder {
+    async fn run(&self, db: &DatabaseConnection) -> Result<(), DbErr> {
+        let id: Uuid = "67e55044-10b1-426f-9247-bb680e5fe0c8".parse().unwrap();
+        let existing = user::Entity::find()
+            .filter(user::Column::Id.eq(id))
+            .one(db)
+            .await?;
+        if existing.is_some() {
+            return Ok(());
+        }
+        user::ActiveModel {
+            id: Set(id),
+            email: Set("emai@example.com".to_owned()),
+            name: Set("namexxxx".to_owned()),
+            role: Set(user::Role::Admin),
+            ..Default::default()
+        }
+            .insert(db)
+            .await?;
+        Ok(())
+    }
+}
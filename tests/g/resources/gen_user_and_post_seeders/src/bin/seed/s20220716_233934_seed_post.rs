@@ -0,0 +1,17 @@
+use crate::SeederTrait;
+use entity::post;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, DbErr, Set};
+pub struct Seeder;
+#[async_trait::async_trait]
+impl SeederTrait for Seeder {
+    async fn run(&self, db: &DatabaseConnection) -> Result<(), DbErr> {
+        post::ActiveModel {
+            title: Set("title".to_owned()),
+            user_id: Set("67e55044-10b1-426f-9247-bb680e5fe0c8".parse().unwrap()),
+            ..Default::default()
+        }
+            .insert(db)
+            .await?;
+        Ok(())
+    }
+}
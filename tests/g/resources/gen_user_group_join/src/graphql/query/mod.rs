@@ -0,0 +1,10 @@
+use entity::async_graphql;
+pub mod filter;
+pub mod group;
+pub mod user;
+pub mod user_group;
+pub use group::GroupQuery;
+pub use user::UserQuery;
+pub use user_group::UserGroupQuery;
+#[derive(async_graphql::MergedObject, Default)]
+pub struct Query(GroupQuery, UserQuery, UserGroupQuery);
@@ -0,0 +1,47 @@
+use async_graphql::{Context, Object, Result};
+use entity::{async_graphql, user, group};
+use sea_orm::{EntityTrait, ModelTrait};
+use crate::db::Database;
+#[derive(Default)]
+pub struct UserGroupQuery;
+#[Object]
+impl UserGroupQuery {
+    async fn get_user_groups(
+        &self,
+        ctx: &Context<'_>,
+        user_id: i32,
+    ) -> Result<Vec<group::Model>> {
+        let db = ctx.data::<Database>().unwrap();
+        match user::Entity::find_by_id(user_id).one(db.get_connection()).await? {
+            Some(user) => {
+                Ok(
+                    user
+                        .find_related(group::Entity)
+                        .all(db.get_connection())
+                        .await
+                        .map_err(|e| e.to_string())?,
+                )
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+    async fn get_group_users(
+        &self,
+        ctx: &Context<'_>,
+        group_id: i32,
+    ) -> Result<Vec<user::Model>> {
+        let db = ctx.data::<Database>().unwrap();
+        match group::Entity::find_by_id(group_id).one(db.get_connection()).await? {
+            Some(group) => {
+                Ok(
+                    group
+                        .find_related(user::Entity)
+                        .all(db.get_connection())
+                        .await
+                        .map_err(|e| e.to_string())?,
+                )
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+}
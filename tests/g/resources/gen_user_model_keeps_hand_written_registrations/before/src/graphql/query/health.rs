@@ -0,0 +1,11 @@
+use entity::async_graphql::{self, Object};
+
+#[derive(Default)]
+pub struct HealthQuery;
+
+#[Object]
+impl HealthQuery {
+    async fn health(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,18 @@
+use entity::async_graphql;
+
+// Queries written by hand live next to the generated ones.
+pub mod health;
+
+pub use health::HealthQuery;
+
+/// Every query of the app.
+#[derive(async_graphql::MergedObject, Default)]
+pub struct Query(
+    // Liveness probe for the load balancer.
+    HealthQuery,
+);
+
+// Reported in the playground title.
+pub fn schema_name() -> &'static str {
+    "zapp" // keep in sync with the deployment
+}
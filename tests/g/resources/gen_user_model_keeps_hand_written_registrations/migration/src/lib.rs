@@ -0,0 +1,23 @@
+pub use sea_orm_migration::prelude::*;
+
+pub struct Migrator;
+
+// Extensions have to exist before any table uses them.
+mod m20220101_000000_create_extensions;
+mod m20220716_233933_create_user_table;
+
+#[async_trait::async_trait]
+impl MigratorTrait for Migrator {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        vec![
+            // pgcrypto, for gen_random_uuid()
+            Box::new(m20220101_000000_create_extensions::Migration),
+            Box::new(m20220716_233933_create_user_table::Migration),
+        ]
+    }
+
+    /// Shared with the other services on the database.
+    fn migration_table_name() -> sea_orm::DynIden {
+        Alias::new("zapp_migrations").into_iden()
+    }
+}
@@ -0,0 +1,22 @@
+use entity::async_graphql;
+
+// Queries written by hand live next to the generated ones.
+pub mod filter;
+pub mod health;
+pub mod user;
+
+pub use health::HealthQuery;
+pub use user::UserQuery;
+
+/// Every query of the app.
+#[derive(async_graphql::MergedObject, Default)]
+pub struct Query(
+    // Liveness probe for the load balancer.
+    HealthQuery,
+    UserQuery,
+);
+
+// Reported in the playground title.
+pub fn schema_name() -> &'static str {
+    "zapp" // keep in sync with the deployment
+}
@@ -0,0 +1,47 @@
+use entity::user;
+use sea_orm::{DbBackend, EntityTrait, Schema};
+use sea_orm::sea_query::extension::postgres::Type;
+use sea_orm_migration::prelude::*;
+pub struct Migration;
+fn get_seaorm_create_stmt<E: EntityTrait>(e: E) -> TableCreateStatement {
+    let schema = Schema::new(DbBackend::Postgres);
+    schema.create_table_from_entity(e).if_not_exists().to_owned()
+}
+fn get_seaorm_drop_stmt<E: EntityTrait>(e: E) -> TableDropStatement {
+    Table::drop().table(e).if_exists().to_owned()
+}
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20220716_233933_create_user_table"
+    }
+}
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let schema = Schema::new(DbBackend::Postgres);
+        manager
+            .create_type(schema.create_enum_from_active_enum::<user::Status>())
+            .await?;
+        manager.create_type(schema.create_enum_from_active_enum::<user::Role>()).await?;
+        let stmts = vec![get_seaorm_create_stmt(user::Entity)];
+        for stmt in stmts {
+            manager.create_table(stmt.to_owned()).await?;
+        }
+        Ok(())
+    }
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let stmts = vec![get_seaorm_drop_stmt(user::Entity)];
+        for stmt in stmts {
+            manager.drop_table(stmt.to_owned()).await?;
+        }
+        manager
+            .drop_type(Type::drop().if_exists().name(Alias::new("user_role")).to_owned())
+            .await?;
+        manager
+            .drop_type(
+                Type::drop().if_exists().name(Alias::new("user_status")).to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use async_graphql::{Context, Object, Result, Error, ErrorExtensions};
+use entity::async_graphql::{self, InputObject, MaybeUndefined};
+use entity::user;
+use sea_orm::{ActiveModelTrait, Set};
+use entity::user::{Status, Role};
+use crate::graphql::mutation::common::*;
+use crate::db::Database;
+#[derive(InputObject)]
+pub struct CreateUserInput {
+    pub id: i32,
+    pub name: String,
+    pub status: Option<Status>,
+    pub role: Option<Role>,
+}
+#[derive(InputObject)]
+pub struct UpdateUserInput {
+    pub name: Option<String>,
+    pub status: Option<Status>,
+    pub role: MaybeUndefined<Role>,
+}
+fn not_found(id: i32) -> Error {
+    Error::new(format!("user {} not found", id))
+        .extend_with(|_, e| e.set("code", "NOT_FOUND"))
+}
+#[derive(Default)]
+pub struct UserMutation;
+#[Object]
+impl UserMutation {
+    pub async fn create_user(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateUserInput,
+    ) -> Result<user::Model> {
+        let db = ctx.data::<Database>().unwrap();
+        let user = user::ActiveModel {
+            id: Set(input.id),
+            name: Set(input.name),
+            status: Set(input.status.unwrap_or(Status::Active)),
+            role: Set(input.role),
+            ..Default::default()
+        };
+        Ok(user.insert(db.get_connection()).await?)
+    }
+    pub async fn update_user(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+        input: UpdateUserInput,
+    ) -> Result<user::Model, Error> {
+        let db = ctx.data::<Database>().unwrap();
+        let user: user::Model = user::Entity::find_by_id(id)
+            .one(db.get_connection())
+            .await?
+            .ok_or_else(|| not_found(id))?;
+        let mut user: user::ActiveModel = user.into();
+        if let Some(name) = input.name {
+            user.name = Set(name);
+        }
+        if let Some(status) = input.status {
+            user.status = Set(status);
+        }
+        match input.role {
+            MaybeUndefined::Value(role) => user.role = Set(Some(role)),
+            MaybeUndefined::Null => user.role = Set(None),
+            MaybeUndefined::Undefined => {}
+        }
+        let user: user::Model = user.update(db.get_connection()).await?;
+        Ok(user)
+    }
+    pub async fn delete_user(&self, ctx: &Context<'_>, id: i32) -> Result<DeleteResult> {
+        let db = ctx.data::<Database>().unwrap();
+        let res = user::Entity::delete_by_id(id).exec(db.get_connection()).await?;
+        if res.rows_affected == 0 {
+            return Err(not_found(id));
+        }
+        Ok(DeleteResult {
+            success: true,
+            rows_affected: res.rows_affected,
+        })
+    }
+}
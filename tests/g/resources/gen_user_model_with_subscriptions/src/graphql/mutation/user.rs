@@ -0,0 +1,72 @@
+use async_graphql::{Context, Object, Result, Error, ErrorExtensions};
+use entity::async_graphql::{self, InputObject};
+use entity::user;
+use sea_orm::{ActiveModelTrait, Set};
+use crate::graphql::mutation::common::*;
+use crate::graphql::subscription::broker::SimpleBroker;
+use crate::graphql::subscription::user::{UserCreated, UserUpdated, UserDeleted};
+use crate::db::Database;
+#[derive(InputObject)]
+pub struct CreateUserInput {
+    pub id: i32,
+    pub name: String,
+}
+#[derive(InputObject)]
+pub struct UpdateUserInput {
+    pub name: Option<String>,
+}
+fn not_found(id: i32) -> Error {
+    Error::new(format!("user {} not found", id))
+        .extend_with(|_, e| e.set("code", "NOT_FOUND"))
+}
+#[derive(Default)]
+pub struct UserMutation;
+#[Object]
+impl UserMutation {
+    pub async fn create_user(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateUserInput,
+    ) -> Result<user::Model> {
+        let db = ctx.data::<Database>().unwrap();
+        let user = user::ActiveModel {
+            id: Set(input.id),
+            name: Set(input.name),
+            ..Default::default()
+        };
+        let user = user.insert(db.get_connection()).await?;
+        SimpleBroker::publish(UserCreated(user.clone()));
+        Ok(user)
+    }
+    pub async fn update_user(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+        input: UpdateUserInput,
+    ) -> Result<user::Model, Error> {
+        let db = ctx.data::<Database>().unwrap();
+        let user: user::Model = user::Entity::find_by_id(id)
+            .one(db.get_connection())
+            .await?
+            .ok_or_else(|| not_found(id))?;
+        let mut user: user::ActiveModel = user.into();
+        if let Some(name) = input.name {
+            user.name = Set(name);
+        }
+        let user: user::Model = user.update(db.get_connection()).await?;
+        SimpleBroker::publish(UserUpdated(user.clone()));
+        Ok(user)
+    }
+    pub async fn delete_user(&self, ctx: &Context<'_>, id: i32) -> Result<DeleteResult> {
+        let db = ctx.data::<Database>().unwrap();
+        let res = user::Entity::delete_by_id(id).exec(db.get_connection()).await?;
+        if res.rows_affected == 0 {
+            return Err(not_found(id));
+        }
+        SimpleBroker::publish(UserDeleted(id));
+        Ok(DeleteResult {
+            success: true,
+            rows_affected: res.rows_affected,
+        })
+    }
+}
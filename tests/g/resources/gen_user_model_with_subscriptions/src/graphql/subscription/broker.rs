@@ -0,0 +1,37 @@
+use futures_util::stream::{self, Stream};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use tokio::sync::broadcast;
+/// Events kept for slow subscribers, which skip the ones they missed.
+const CAPACITY: usize = 256;
+static CHANNELS: OnceLock<Mutex<HashMap<TypeId, Box<dyn Any + Send>>>> = OnceLock::new();
+fn sender<T: Clone + Send + 'static>() -> broadcast::Sender<T> {
+    let mut channels = CHANNELS.get_or_init(Default::default).lock().unwrap();
+    channels
+        .entry(TypeId::of::<T>())
+        .or_insert_with(|| Box::new(broadcast::channel::<T>(CAPACITY).0))
+        .downcast_ref::<broadcast::Sender<T>>()
+        .unwrap()
+        .clone()
+}
+pub struct SimpleBroker;
+impl SimpleBroker {
+    pub fn publish<T: Clone + Send + 'static>(event: T) {
+        let _ = sender::<T>().send(event);
+    }
+    pub fn subscribe<T: Clone + Send + 'static>() -> impl Stream<Item = T> {
+        stream::unfold(
+            sender::<T>().subscribe(),
+            |mut receiver| async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(event) => return Some((event, receiver)),
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => return None,
+                    }
+                }
+            },
+        )
+    }
+}
@@ -0,0 +1,6 @@
+use entity::async_graphql;
+pub mod broker;
+pub mod user;
+pub use user::UserSubscription;
+#[derive(async_graphql::MergedSubscription, Default)]
+pub struct Subscription(UserSubscription);
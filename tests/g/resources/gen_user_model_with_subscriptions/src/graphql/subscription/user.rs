@@ -0,0 +1,25 @@
+use async_graphql::Subscription;
+use entity::{async_graphql, user};
+use futures_util::{Stream, StreamExt};
+use crate::graphql::subscription::broker::SimpleBroker;
+#[derive(Clone)]
+pub struct UserCreated(pub user::Model);
+#[derive(Clone)]
+pub struct UserUpdated(pub user::Model);
+#[derive(Clone)]
+pub struct UserDeleted(pub i32);
+#[derive(Default)]
+pub struct UserSubscription;
+#[Subscription]
+impl UserSubscription {
+    async fn user_created(&self) -> impl Stream<Item = user::Model> {
+        SimpleBroker::subscribe::<UserCreated>().map(|event| event.0)
+    }
+    async fn user_updated(&self) -> impl Stream<Item = user::Model> {
+        SimpleBroker::subscribe::<UserUpdated>().map(|event| event.0)
+    }
+    /// Ids of the deleted users.
+    async fn user_deleted(&self) -> impl Stream<Item = i32> {
+        SimpleBroker::subscribe::<UserDeleted>().map(|event| event.0)
+    }
+}
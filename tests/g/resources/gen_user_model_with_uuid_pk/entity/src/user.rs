@@ -0,0 +1,86 @@
+use async_graphql::*;
+use chrono::Utc;
+use sea_orm::{entity::prelude::*, DeleteMany, Set};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    DeriveEntityModel,
+    Serialize,
+    Deserialize,
+    SimpleObject
+)]
+#[sea_orm(table_name = "user")]
+#[graphql(complex)]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    #[serde(skip_deserializing)]
+    pub id: Uuid,
+    pub name: String,
+    #[sea_orm(indexed)]
+    pub created_at: DateTime,
+    #[sea_orm(indexed)]
+    pub updated_at: DateTime,
+}
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::post::Entity")]
+    Post,
+}
+impl ActiveModelBehavior for ActiveModel {
+    fn before_save(mut self, insert: bool) -> Result<Self, DbErr> {
+        if insert && self.id.is_not_set() {
+            self.id = Set(Uuid::new_v4());
+        }
+        let now = Utc::now().naive_utc();
+        if insert && self.created_at.is_not_set() {
+            self.created_at = Set(now);
+        }
+        self.updated_at = Set(now);
+        Ok(self)
+    }
+}
+impl Entity {
+    pub fn find_by_id(id: Uuid) -> Select<Entity> {
+        Self::find().filter(Column::Id.eq(id))
+    }
+    pub fn delete_by_id(id: Uuid) -> DeleteMany<Entity> {
+        Self::delete_many().filter(Column::Id.eq(id))
+    }
+}
+impl Related<super::post::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Post.def()
+    }
+}
+#[ComplexObject]
+impl Model {
+    async fn posts(&self, ctx: &Context<'_>) -> Result<Vec<super::post::Model>> {
+        let loader = ctx.data::<dataloader::DataLoader<super::post::UserIdLoader>>()?;
+        Ok(loader.load_one(self.id).await?.unwrap_or_default())
+    }
+}
+/// Batches lookups by id, for the relations pointing at this entity.
+pub struct IdLoader {
+    db: DatabaseConnection,
+}
+impl IdLoader {
+    pub fn new(db: DatabaseConnection) -> Self {
+        IdLoader { db }
+    }
+}
+#[async_trait::async_trait]
+impl dataloader::Loader<Uuid> for IdLoader {
+    type Value = Model;
+    type Error = Arc<DbErr>;
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Model>, Self::Error> {
+        let models = Entity::find()
+            .filter(Column::Id.is_in(keys.iter().cloned()))
+            .all(&self.db)
+            .await?;
+        Ok(models.into_iter().map(|model| (model.id, model)).collect())
+    }
+}
@@ -0,0 +1,69 @@
+use async_graphql::{Context, Object, Result, Error, ErrorExtensions};
+use entity::async_graphql::{self, InputObject};
+use entity::user;
+use sea_orm::{ActiveModelTrait, Set};
+use sea_orm::prelude::Uuid;
+use crate::graphql::mutation::common::*;
+use crate::db::Database;
+#[derive(InputObject)]
+pub struct CreateUserInput {
+    pub name: String,
+}
+#[derive(InputObject)]
+pub struct UpdateUserInput {
+    pub name: Option<String>,
+}
+fn not_found(id: Uuid) -> Error {
+    Error::new(format!("user {} not found", id))
+        .extend_with(|_, e| e.set("code", "NOT_FOUND"))
+}
+#[derive(Default)]
+pub struct UserMutation;
+#[Object]
+impl UserMutation {
+    pub async fn create_user(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateUserInput,
+    ) -> Result<user::Model> {
+        let db = ctx.data::<Database>().unwrap();
+        let user = user::ActiveModel {
+            name: Set(input.name),
+            ..Default::default()
+        };
+        Ok(user.insert(db.get_connection()).await?)
+    }
+    pub async fn update_user(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        input: UpdateUserInput,
+    ) -> Result<user::Model, Error> {
+        let db = ctx.data::<Database>().unwrap();
+        let user: user::Model = user::Entity::find_by_id(id)
+            .one(db.get_connection())
+            .await?
+            .ok_or_else(|| not_found(id))?;
+        let mut user: user::ActiveModel = user.into();
+        if let Some(name) = input.name {
+            user.name = Set(name);
+        }
+        let user: user::Model = user.update(db.get_connection()).await?;
+        Ok(user)
+    }
+    pub async fn delete_user(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+    ) -> Result<DeleteResult> {
+        let db = ctx.data::<Database>().unwrap();
+        let res = user::Entity::delete_by_id(id).exec(db.get_connection()).await?;
+        if res.rows_affected == 0 {
+            return Err(not_found(id));
+        }
+        Ok(DeleteResult {
+            success: true,
+            rows_affected: res.rows_affected,
+        })
+    }
+}
@@ -0,0 +1,37 @@
+use entity::user;
+use sea_orm::{DbBackend, EntityTrait, Schema};
+use sea_orm_migration::prelude::*;
+pub struct Migration;
+fn get_seaorm_create_stmt<E: EntityTrait>(e: E) -> TableCreateStatement {
+    let schema = Schema::new(DbBackend::Postgres);
+    schema.create_table_from_entity(e).if_not_exists().to_owned()
+}
+fn get_seaorm_drop_stmt<E: EntityTrait>(e: E) -> TableDropStatement {
+    Table::drop().table(e).if_exists().to_owned()
+}
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20220716_233933_create_user_table"
+    }
+}
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let stmts = vec![
+            get_seaorm_create_stmt(user::Entity)
+            .check(Expr::cust("char_length(\"name\") BETWEEN 1 AND 100"))
+            .check(Expr::cust("\"age\" BETWEEN 0 AND 150")).to_owned()
+        ];
+        for stmt in stmts {
+            manager.create_table(stmt.to_owned()).await?;
+        }
+        Ok(())
+    }
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let stmts = vec![get_seaorm_drop_stmt(user::Entity)];
+        for stmt in stmts {
+            manager.drop_table(stmt.to_owned()).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use async_graphql::{Context, Object, Result, Error, ErrorExtensions};
+use entity::async_graphql::{self, InputObject, MaybeUndefined};
+use entity::user;
+use sea_orm::{ActiveModelTrait, Set};
+use crate::graphql::mutation::common::*;
+use crate::db::Database;
+#[derive(InputObject)]
+pub struct CreateUserInput {
+    pub id: i32,
+    #[graphql(validator(email))]
+    pub email: String,
+    #[graphql(validator(min_length = 1, max_length = 100))]
+    pub name: String,
+    #[graphql(validator(minimum = 0, maximum = 150))]
+    pub age: Option<i32>,
+}
+#[derive(InputObject)]
+pub struct UpdateUserInput {
+    #[graphql(validator(email))]
+    pub email: Option<String>,
+    #[graphql(validator(min_length = 1, max_length = 100))]
+    pub name: Option<String>,
+    #[graphql(validator(minimum = 0, maximum = 150))]
+    pub age: MaybeUndefined<i32>,
+}
+fn not_found(id: i32) -> Error {
+    Error::new(format!("user {} not found", id))
+        .extend_with(|_, e| e.set("code", "NOT_FOUND"))
+}
+#[derive(Default)]
+pub struct UserMutation;
+#[Object]
+impl UserMutation {
+    pub async fn create_user(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateUserInput,
+    ) -> Result<user::Model> {
+        let db = ctx.data::<Database>().unwrap();
+        let user = user::ActiveModel {
+            id: Set(input.id),
+            email: Set(input.email),
+            name: Set(input.name),
+            age: Set(input.age),
+            ..Default::default()
+        };
+        Ok(user.insert(db.get_connection()).await?)
+    }
+    pub async fn update_user(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+        input: UpdateUserInput,
+    ) -> Result<user::Model, Error> {
+        let db = ctx.data::<Database>().unwrap();
+        let user: user::Model = user::Entity::find_by_id(id)
+            .one(db.get_connection())
+            .await?
+            .ok_or_else(|| not_found(id))?;
+        let mut user: user::ActiveModel = user.into();
+        if let Some(email) = input.email {
+            user.email = Set(email);
+        }
+        if let Some(name) = input.name {
+            user.name = Set(name);
+        }
+        match input.age {
+            MaybeUndefined::Value(age) => user.age = Set(Some(age)),
+            MaybeUndefined::Null => user.age = Set(None),
+            MaybeUndefined::Undefined => {}
+        }
+        let user: user::Model = user.update(db.get_connection()).await?;
+        Ok(user)
+    }
+    pub async fn delete_user(&self, ctx: &Context<'_>, id: i32) -> Result<DeleteResult> {
+        let db = ctx.data::<Database>().unwrap();
+        let res = user::Entity::delete_by_id(id).exec(db.get_connection()).await?;
+        if res.rows_affected == 0 {
+            return Err(not_found(id));
+        }
+        Ok(DeleteResult {
+            success: true,
+            rows_affected: res.rows_affected,
+        })
+    }
+}